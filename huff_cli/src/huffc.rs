@@ -64,6 +64,22 @@ struct Huff {
     #[clap(short = 'z', long = "optimize")]
     optimize: bool,
 
+    /// Treat warnings as errors
+    #[clap(long = "deny-warnings")]
+    deny_warnings: bool,
+
+    /// Allow entry point macros to declare a stack signature other than takes(0) returns(0)
+    #[clap(long = "lenient-entry-points")]
+    lenient_entry_points: bool,
+
+    /// Compile invoked test macros into the bytecode instead of removing their invocations
+    #[clap(long = "include-tests")]
+    include_tests: bool,
+
+    /// Include the offset and length of every macro in the artifacts
+    #[clap(long = "macro-map")]
+    macro_map: bool,
+
     /// Generate solidity interface for a Huff artifact
     #[clap(short = 'g', min_values = 0, long = "interface")]
     interface: Option<String>,
@@ -92,6 +108,10 @@ struct Huff {
     #[clap(short = 'c', long = "constants", multiple_values = true)]
     constants: Option<Vec<String>>,
 
+    /// Define names for `#ifdef` conditionals.
+    #[clap(short = 'D', long = "define", multiple_values = true)]
+    defines: Option<Vec<String>>,
+
     /// Compile a specific macro
     #[clap(short = 'm', long = "alt-main")]
     alternative_main: Option<String>,
@@ -219,9 +239,14 @@ fn main() {
         alternative_constructor: cli.alternative_constructor,
         construct_args: cli.inputs,
         constant_overrides: constants,
+        defines: cli.defines.unwrap_or_default().into_iter().collect(),
         optimize: cli.optimize,
+        deny_warnings: cli.deny_warnings,
+        lenient_entry_points: cli.lenient_entry_points,
+        include_tests: cli.include_tests,
         bytecode: cli.bytecode,
         cached: use_cache,
+        macro_map: cli.macro_map,
         file_provider: Arc::new(FileSystemFileProvider {}),
     };
 
@@ -372,6 +397,7 @@ fn main() {
                             .collect::<Vec<Span>>(),
                     ),
                     token: None,
+                    call_stack: vec![],
                 });
                 tracing::error!(target: "cli", "COMPILER ERRORED: {}", e);
                 eprintln!("{}", Paint::red(format!("{e}")));
@@ -450,16 +476,25 @@ fn main() {
                                                 .then(|| format!(" \"{}\"", input.name))
                                                 .unwrap_or_default()
                                         ));
+                                        let tokens = Codegen::encode_constructor_args(
+                                            Some(std::slice::from_ref(input)),
+                                            vec![arg_input],
+                                        )
+                                        .unwrap_or_else(|e| {
+                                            eprintln!(
+                                                "{}",
+                                                Paint::red(CompilerError::CodegenError(e))
+                                            );
+                                            std::process::exit(1);
+                                        });
                                         let encoded =
-                                            Codegen::encode_constructor_args(vec![arg_input])
-                                                .iter()
-                                                .fold(String::default(), |acc, str| {
-                                                    let inner: Vec<u8> =
-                                                        ethers_core::abi::encode(&[str.clone()]);
-                                                    let hex_args: String =
-                                                        hex::encode(inner.as_slice());
-                                                    format!("{acc}{hex_args}")
-                                                });
+                                            tokens.iter().fold(String::default(), |acc, str| {
+                                                let inner: Vec<u8> =
+                                                    ethers_core::abi::encode(&[str.clone()]);
+                                                let hex_args: String =
+                                                    hex::encode(inner.as_slice());
+                                                format!("{acc}{hex_args}")
+                                            });
                                         appended_args.push_str(&encoded);
                                     }
                                 }
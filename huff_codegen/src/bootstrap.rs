@@ -0,0 +1,28 @@
+use std::{fmt, sync::Arc};
+
+/// Generates the bootstrap code placed between the constructor and runtime bytecode
+///
+/// The generator receives the length of the runtime bytecode and the length of the constructor
+/// bytecode, both in bytes, and returns the hex encoded bootstrap code. The bootstrap code is
+/// responsible for copying the runtime bytecode into memory and returning it, so it needs to
+/// account for its own length when computing the runtime code offset.
+#[derive(Clone)]
+pub struct BootstrapGenerator(pub Arc<dyn Fn(usize, usize) -> String + Send + Sync>);
+
+impl BootstrapGenerator {
+    /// Public associated function to instantiate a new BootstrapGenerator.
+    pub fn new(generator: impl Fn(usize, usize) -> String + Send + Sync + 'static) -> Self {
+        Self(Arc::new(generator))
+    }
+
+    /// Generates the bootstrap code for the given runtime and constructor lengths
+    pub fn generate(&self, contract_length: usize, constructor_length: usize) -> String {
+        (self.0)(contract_length, constructor_length)
+    }
+}
+
+impl fmt::Debug for BootstrapGenerator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("BootstrapGenerator")
+    }
+}
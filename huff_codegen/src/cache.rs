@@ -0,0 +1,79 @@
+use huff_utils::prelude::*;
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fs,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+/// Parsed contracts cached by the content hash of their source files
+///
+/// Entries are keyed by [`ParseCache::key`], so a file is only lexed and parsed again once it or
+/// one of its dependencies changes. The lexed tokens are cached alongside the contracts. Only the
+/// tokens are persisted, since contracts can't be serialized, so a cache loaded from disk skips
+/// lexing but still parses each file once.
+#[derive(Debug, Default, Clone)]
+pub struct ParseCache {
+    /// Parsed contracts by cache key
+    pub contracts: HashMap<String, Contract>,
+    /// Lexed tokens by cache key
+    pub tokens: BTreeMap<String, Vec<Token>>,
+    /// File the lexed tokens are persisted to
+    pub path: Option<PathBuf>,
+}
+
+impl ParseCache {
+    /// Loads the tokens persisted at the given path, starting empty if the file doesn't exist yet
+    pub fn persisted(path: impl Into<PathBuf>) -> Result<Self, CodegenError> {
+        let path = path.into();
+        let tokens = match fs::read_to_string(&path) {
+            Ok(json) => serde_json::from_str(&json).map_err(|e| {
+                tracing::error!(target: "codegen", "FAILED TO DESERIALIZE PARSE CACHE: {}", e);
+                CodegenError::new(CodegenErrorKind::SerdeJson(e.to_string()), AstSpan(vec![]), None)
+            })?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(e) => {
+                return Err(CodegenError::new(
+                    CodegenErrorKind::IOError(e.to_string()),
+                    AstSpan(vec![]),
+                    None,
+                ))
+            }
+        };
+        Ok(Self { contracts: HashMap::new(), tokens, path: Some(path) })
+    }
+
+    /// Writes the cached tokens to the persistence path, if any
+    pub fn save(&self) -> Result<(), CodegenError> {
+        let Some(path) = &self.path else { return Ok(()) };
+        let json = serde_json::to_string(&self.tokens).map_err(|e| {
+            tracing::error!(target: "codegen", "FAILED TO SERIALIZE PARSE CACHE: {}", e);
+            CodegenError::new(CodegenErrorKind::SerdeJson(e.to_string()), AstSpan(vec![]), None)
+        })?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| {
+                CodegenError::new(CodegenErrorKind::IOError(e.to_string()), AstSpan(vec![]), None)
+            })?;
+        }
+        fs::write(path, json).map_err(|e| {
+            CodegenError::new(CodegenErrorKind::IOError(e.to_string()), AstSpan(vec![]), None)
+        })
+    }
+
+    /// The key of a file lexed with the given defines
+    ///
+    /// Combines the [content hash](FileSource::content_hash) of the file with the defines, since
+    /// they select the `#ifdef` branches that are lexed.
+    pub fn key(file: &FileSource, defines: &HashSet<String>) -> String {
+        let mut defines = defines.iter().map(String::as_str).collect::<Vec<_>>();
+        defines.sort_unstable();
+        format!("{}:{}", file.content_hash(), defines.join(","))
+    }
+}
+
+/// Clones a contract without sharing its constants, so passes run on the clone leave the
+/// original untouched
+pub(crate) fn detached(contract: &Contract) -> Contract {
+    let constants = contract.constants.lock().map(|c| c.clone()).unwrap_or_default();
+    Contract { constants: Arc::new(Mutex::new(constants)), ..contract.clone() }
+}
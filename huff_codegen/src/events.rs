@@ -0,0 +1,54 @@
+use serde_json::Value;
+use std::{
+    cell::RefCell,
+    fmt,
+    io::Write,
+    sync::{Arc, Mutex},
+};
+
+thread_local! {
+    /// The event sink attached to the code generation running on this thread
+    static ACTIVE_SINK: RefCell<Option<EventSink>> = const { RefCell::new(None) };
+}
+
+/// A writer receiving code generation progress events as JSON Lines
+#[derive(Clone)]
+pub struct EventSink(pub Arc<Mutex<Box<dyn Write + Send>>>);
+
+impl EventSink {
+    /// Public associated function to instantiate a new EventSink.
+    pub fn new(writer: Box<dyn Write + Send>) -> Self {
+        Self(Arc::new(Mutex::new(writer)))
+    }
+
+    /// Attaches the sink to all code generation run by `f` on the current thread
+    pub fn attach<T>(&self, f: impl FnOnce() -> T) -> T {
+        let previous = ACTIVE_SINK.with(|sink| sink.replace(Some(self.clone())));
+        let res = f();
+        ACTIVE_SINK.with(|sink| *sink.borrow_mut() = previous);
+        res
+    }
+}
+
+impl fmt::Debug for EventSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("EventSink")
+    }
+}
+
+/// Writes an event to the attached event sink, if any
+///
+/// Failing to write an event never fails code generation, it is only logged.
+pub(crate) fn emit(event: Value) {
+    ACTIVE_SINK.with(|sink| {
+        if let Some(sink) = sink.borrow().as_ref() {
+            let res = match sink.0.lock() {
+                Ok(mut writer) => writeln!(writer, "{event}"),
+                Err(_) => Ok(()),
+            };
+            if let Err(e) = res {
+                tracing::warn!(target: "codegen", "FAILED TO WRITE EVENT: {}", e);
+            }
+        }
+    });
+}
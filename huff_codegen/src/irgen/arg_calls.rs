@@ -1,6 +1,11 @@
 use huff_utils::prelude::*;
 use std::str::FromStr;
 
+use crate::{
+    irgen::statements::{codesize_push, statement_gen, StatementBytecode},
+    Codegen,
+};
+
 // Arguments can be literals, labels, opcodes, or constants
 // !! IF THERE IS AMBIGUOUS NOMENCLATURE
 // !! (E.G. BOTH OPCODE AND LABEL ARE THE SAME STRING)
@@ -8,37 +13,38 @@ use std::str::FromStr;
 
 /// Arg Call Bubbling
 #[allow(clippy::too_many_arguments)]
-pub fn bubble_arg_call(
+pub fn bubble_arg_call<'a>(
+    evm_version: &EVMVersion,
     arg_name: &str,
     bytes: &mut Vec<(usize, Bytes)>,
     macro_def: &MacroDefinition,
-    contract: &Contract,
-    scope: &mut [&MacroDefinition],
+    contract: &'a Contract,
+    scope: &mut [&'a MacroDefinition],
     offset: &mut usize,
     // mis: Parent macro invocations and their indices
     mis: &mut [(usize, MacroInvocation)],
     jump_table: &mut JumpTable,
+    label_indices: &mut LabelIndices,
+    table_instances: &mut Jumps,
+    codeoffset_instances: &mut Jumps,
+    utilized_tables: &mut Vec<TableDefinition>,
+    circular_codesize_invocations: &mut CircularCodeSizeIndices,
 ) -> Result<(), CodegenError> {
     let starting_offset = *offset;
 
     if let Some(macro_invoc) = mis.last() {
         // Literal, Ident & Arg Call Check
-        // First get this arg_nam position in the macro definition params
-        if let Some(pos) = macro_def
-            .parameters
-            .iter()
-            .position(|r| r.name.as_ref().map_or(false, |s| s.eq(arg_name)))
-        {
-            tracing::info!(target: "codegen", "GOT \"{}\" POS IN ARG LIST: {}", arg_name, pos);
+        // First check that arg_name is a parameter of the macro definition
+        if macro_def.parameters.iter().any(|r| r.name.as_deref() == Some(arg_name)) {
+            tracing::info!(target: "codegen", "GOT \"{}\" IN ARG LIST", arg_name);
 
-            if let Some(arg) = macro_invoc.1.args.get(pos) {
+            if let Some(arg) = macro_invoc.1.arg_for(macro_def, arg_name) {
                 tracing::info!(target: "codegen", "GOT \"{:?}\" ARG FROM MACRO INVOCATION", arg);
                 match arg {
                     MacroArg::Literal(l) => {
                         tracing::info!(target: "codegen", "GOT LITERAL {} ARG FROM MACRO INVOCATION", bytes32_to_string(l, false));
 
-                        let hex_literal: String = bytes32_to_string(l, false);
-                        let push_bytes = format!("{:02x}{hex_literal}", 95 + hex_literal.len() / 2);
+                        let push_bytes = literal_gen(evm_version, l);
                         let b = Bytes(push_bytes);
                         *offset += b.0.len() / 2;
                         bytes.push((starting_offset, b));
@@ -63,6 +69,7 @@ pub fn bubble_arg_call(
                                     ),
                                     span: bubbled_macro_invocation.span.clone(),
                                     token: None,
+                                    call_stack: vec![],
                                 })
                             }
                         };
@@ -70,6 +77,7 @@ pub fn bubble_arg_call(
                         let ac_ = &ac.to_string();
                         return if last_mi.1.macro_name.eq(&macro_def.name) {
                             bubble_arg_call(
+                                evm_version,
                                 ac_,
                                 bytes,
                                 bubbled_macro_invocation,
@@ -78,9 +86,15 @@ pub fn bubble_arg_call(
                                 offset,
                                 &mut mis[..mis_len.saturating_sub(1)],
                                 jump_table,
+                                label_indices,
+                                table_instances,
+                                codeoffset_instances,
+                                utilized_tables,
+                                circular_codesize_invocations,
                             )
                         } else {
                             bubble_arg_call(
+                                evm_version,
                                 ac_,
                                 bytes,
                                 bubbled_macro_invocation,
@@ -89,9 +103,75 @@ pub fn bubble_arg_call(
                                 offset,
                                 mis,
                                 jump_table,
+                                label_indices,
+                                table_instances,
+                                codeoffset_instances,
+                                utilized_tables,
+                                circular_codesize_invocations,
                             )
                         };
                     }
+                    MacroArg::Builtin(bf) => {
+                        tracing::info!(target: "codegen", "GOT BUILTIN FUNCTION CALL {:?} ARG FROM MACRO INVOCATION", bf.kind);
+
+                        // `__codesize()` sizes the macro the argument was written in rather than
+                        // the invoked macro
+                        let mut bf = bf.clone();
+                        if bf.kind == BuiltinFunctionKind::Codesize && bf.args.is_empty() {
+                            if let Some(invoker) = scope.len().checked_sub(2).map(|i| scope[i]) {
+                                bf.args.push(Argument {
+                                    name: Some(invoker.name.clone()),
+                                    span: bf.span.clone(),
+                                    ..Default::default()
+                                });
+                            }
+                        }
+
+                        // Evaluate the builtin as if it were a statement in the macro body
+                        let statement_bytecode = statement_gen(
+                            evm_version,
+                            &Statement {
+                                ty: StatementType::BuiltinFunctionCall(bf.clone()),
+                                span: bf.span.clone(),
+                            },
+                            contract,
+                            macro_def,
+                            &mut scope.to_vec(),
+                            offset,
+                            &mut mis.to_vec(),
+                            jump_table,
+                            label_indices,
+                            table_instances,
+                            codeoffset_instances,
+                            utilized_tables,
+                            circular_codesize_invocations,
+                            starting_offset,
+                        )?;
+                        match statement_bytecode {
+                            StatementBytecode::Bytes(mut push_bytes) => {
+                                bytes.append(&mut push_bytes)
+                            }
+                            StatementBytecode::Codesize(ir_macro) => {
+                                let mut sizing_scope = scope.to_vec();
+                                sizing_scope.push(ir_macro);
+                                let res = Codegen::macro_to_bytecode(
+                                    evm_version,
+                                    ir_macro,
+                                    contract,
+                                    &mut sizing_scope,
+                                    *offset,
+                                    &mut mis.to_vec(),
+                                    ir_macro.name.eq("CONSTRUCTOR"),
+                                    None,
+                                )?;
+                                let push_bytes = codesize_push(&res);
+                                *offset += push_bytes.len() / 2;
+                                bytes.push((starting_offset, Bytes(push_bytes)));
+                            }
+                            // Builtin function calls never invoke a macro
+                            StatementBytecode::Invocation(..) => {}
+                        }
+                    }
                     MacroArg::Ident(iden) => {
                         tracing::debug!(target: "codegen", "Found MacroArg::Ident IN \"{}\" Macro Invocation: \"{}\"!", macro_invoc.1.macro_name, iden);
 
@@ -111,10 +191,7 @@ pub fn bubble_arg_call(
                         {
                             tracing::info!(target: "codegen", "ARGCALL IS CONSTANT: {:?}", constant);
                             let push_bytes = match &constant.value {
-                                ConstVal::Literal(l) => {
-                                    let hex_literal: String = bytes32_to_string(l, false);
-                                    format!("{:02x}{hex_literal}", 95 + hex_literal.len() / 2)
-                                }
+                                ConstVal::Literal(l) => literal_gen(evm_version, l),
                                 ConstVal::FreeStoragePointer(fsp) => {
                                     // If this is reached in codegen stage,
                                     // `derive_storage_pointers`
@@ -124,6 +201,7 @@ pub fn bubble_arg_call(
                                         kind: CodegenErrorKind::StoragePointersNotDerived,
                                         span: AstSpan(vec![]),
                                         token: None,
+                                        call_stack: vec![],
                                     });
                                 }
                             };
@@ -132,6 +210,17 @@ pub fn bubble_arg_call(
                             bytes.push((starting_offset, Bytes(push_bytes)));
                         } else if let Ok(o) = Opcode::from_str(iden) {
                             tracing::debug!(target: "codegen", "Found Opcode: {}", o);
+                            if !evm_version.supports_opcode(&o) {
+                                return Err(CodegenError {
+                                    kind: CodegenErrorKind::UnsupportedOpcode {
+                                        opcode: o,
+                                        evm_version: evm_version.to_string(),
+                                    },
+                                    span: macro_invoc.1.span.clone(),
+                                    token: None,
+                                    call_stack: vec![],
+                                })
+                            }
                             let b = Bytes(o.to_string());
                             *offset += b.0.len() / 2;
                             bytes.push((starting_offset, b));
@@ -153,7 +242,16 @@ pub fn bubble_arg_call(
                     }
                 }
             } else {
-                tracing::warn!(target: "codegen", "\"{}\" FOUND IN MACRO DEF BUT NOT IN MACRO INVOCATION!", arg_name);
+                tracing::error!(target: "codegen", "\"{}\" FOUND IN MACRO DEF BUT NOT IN MACRO INVOCATION!", arg_name);
+                return Err(CodegenError {
+                    kind: CodegenErrorKind::MissingMacroArgument {
+                        macro_name: macro_def.name.clone(),
+                        param: arg_name.to_string(),
+                    },
+                    span: macro_invoc.1.span.clone(),
+                    token: None,
+                    call_stack: vec![],
+                })
             }
         } else {
             tracing::warn!(target: "codegen", "\"{}\" NOT IN ARG LIST", arg_name);
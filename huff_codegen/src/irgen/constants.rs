@@ -23,6 +23,7 @@ pub fn constant_gen(
             kind: CodegenErrorKind::MissingConstantDefinition(name.to_string()),
             span: ir_byte_span.clone(),
             token: None,
+            call_stack: vec![],
         });
     };
 
@@ -40,6 +41,7 @@ pub fn constant_gen(
                 kind: CodegenErrorKind::StoragePointersNotDerived,
                 span: constant.span.clone(),
                 token: None,
+                call_stack: vec![],
             });
         }
     };
@@ -0,0 +1,159 @@
+use huff_utils::prelude::*;
+
+use crate::{events, irgen::statements::codesize_push};
+
+/// How a macro frame relates to the frame it was started from
+#[derive(Debug)]
+pub enum FrameKind<'a> {
+    /// The macro bytecode generation was requested for
+    Root,
+    /// An inlined macro invocation, expanded into the bytecode of its parent
+    Invocation,
+    /// A macro sized for `__codesize` in its own scope. Holds the parent's scope, macro
+    /// invocations and circular codesize invocations, which are restored once it is generated.
+    Codesize {
+        /// The scope of the parent
+        scope: Vec<&'a MacroDefinition>,
+        /// The macro invocations of the parent
+        mis: Vec<(usize, MacroInvocation)>,
+        /// The circular codesize invocations of the parent
+        circular_codesize_invocations: CircularCodeSizeIndices,
+    },
+}
+
+/// A macro whose bytecode is being generated
+///
+/// Frames are kept on an explicit stack rather than generated recursively, so that the depth of
+/// nested macros is bounded by the heap instead of the native stack.
+#[derive(Debug)]
+pub struct MacroFrame<'a> {
+    /// The macro being generated
+    pub macro_def: &'a MacroDefinition,
+    /// The span the macro was entered from: its definition for the root frame, otherwise the
+    /// invocation or `__codesize` call
+    pub span: AstSpan,
+    /// How the frame relates to its parent
+    pub kind: FrameKind<'a>,
+    /// The intermediate bytecode left to generate
+    pub ir_bytes: std::vec::IntoIter<IRBytes<'a>>,
+    /// Whether statements are skipped because the constructor is being sized from within itself
+    pub recursing_constructor: bool,
+    /// The current bytecode offset
+    pub offset: usize,
+    /// The bytecode generated so far
+    pub bytes: Vec<(usize, Bytes)>,
+    /// Jumps waiting on their label
+    pub jump_table: JumpTable,
+    /// Offsets of the labels generated so far
+    pub label_indices: LabelIndices,
+    /// Table instances generated so far
+    pub table_instances: Jumps,
+    /// Offsets of the macros generated so far
+    pub macro_offsets: LabelIndices,
+    /// Byte lengths of the macros generated so far
+    pub macro_lengths: LabelIndices,
+    /// `__codeoffset` instances generated so far
+    pub codeoffset_instances: Jumps,
+    /// Provenance of the bytecode generated so far
+    pub source_spans: Provenance,
+    /// Tables referenced so far
+    pub utilized_tables: Vec<TableDefinition>,
+}
+
+impl<'a> MacroFrame<'a> {
+    /// Starts generating the bytecode of a macro at the given offset
+    pub fn new(
+        evm_version: &EVMVersion,
+        macro_def: &'a MacroDefinition,
+        span: AstSpan,
+        offset: usize,
+        recursing_constructor: bool,
+        kind: FrameKind<'a>,
+    ) -> Result<Self, CodegenError> {
+        events::emit(serde_json::json!({
+            "event": "macro_started",
+            "macro": macro_def.name,
+            "offset": offset,
+        }));
+
+        // Get intermediate bytecode representation of the macro definition
+        let ir_bytes = match macro_def.to_irbytecode(evm_version) {
+            Ok(ir_bytecode) => ir_bytecode.0,
+            Err(e) => return Err(e.within([(macro_def.name.clone(), span)])),
+        };
+
+        Ok(Self {
+            macro_def,
+            span,
+            kind,
+            ir_bytes: ir_bytes.into_iter(),
+            recursing_constructor,
+            offset,
+            bytes: Vec::default(),
+            jump_table: JumpTable::new(),
+            label_indices: LabelIndices::new(),
+            table_instances: Jumps::new(),
+            macro_offsets: LabelIndices::from([(macro_def.name.clone(), offset)]),
+            macro_lengths: LabelIndices::new(),
+            codeoffset_instances: Jumps::new(),
+            source_spans: Vec::new(),
+            utilized_tables: Vec::new(),
+        })
+    }
+
+    /// Lists the macros being generated on a stack of frames, outermost first
+    pub fn call_stack(frames: &[MacroFrame<'a>]) -> Vec<(String, AstSpan)> {
+        frames.iter().map(|frame| (frame.macro_def.name.clone(), frame.span.clone())).collect()
+    }
+
+    /// Appends the bytecode of an inlined macro invocation
+    pub fn extend(&mut self, mut res: BytecodeRes) {
+        // Set jump table values
+        tracing::debug!(target: "codegen", "Unmatched jumps: {:?}", res.unmatched_jumps.iter().map(|uj| uj.label.clone()).collect::<Vec<String>>());
+        for j in res.unmatched_jumps.iter_mut() {
+            let new_index = j.bytecode_index;
+            j.bytecode_index = 0;
+            self.jump_table.entry(new_index).or_default().push(j.clone());
+        }
+        self.table_instances.extend(res.table_instances);
+        self.label_indices.extend(res.label_indices);
+        // Offsets already recorded take precedence. The smaller map is merged into the larger
+        // one, as the offsets of a deep invocation chain accumulate at every level.
+        if res.macro_offsets.len() > self.macro_offsets.len() {
+            std::mem::swap(&mut self.macro_offsets, &mut res.macro_offsets);
+            self.macro_offsets.extend(res.macro_offsets);
+        } else {
+            for (name, start) in res.macro_offsets {
+                self.macro_offsets.entry(name).or_insert(start);
+            }
+        }
+        if res.macro_lengths.len() > self.macro_lengths.len() {
+            std::mem::swap(&mut self.macro_lengths, &mut res.macro_lengths);
+            self.macro_lengths.extend(res.macro_lengths);
+        } else {
+            for (name, len) in res.macro_lengths {
+                self.macro_lengths.entry(name).or_insert(len);
+            }
+        }
+        self.codeoffset_instances.extend(res.codeoffset_instances);
+        self.source_spans.extend(res.source_spans);
+
+        for table in res.utilized_tables {
+            if !self.utilized_tables.contains(&table) {
+                self.utilized_tables.push(table);
+            }
+        }
+
+        // Increase offset by byte length of the invoked macro
+        self.offset += res.bytes.iter().map(|(_, b)| b.0.len()).sum::<usize>() / 2;
+        // Add the macro's bytecode to the final result
+        self.bytes.extend(res.bytes);
+    }
+
+    /// Appends the push of a macro's size generated for `__codesize`
+    pub fn push_codesize(&mut self, res: &BytecodeRes) {
+        let push_bytes = codesize_push(res);
+        self.bytes.push((self.offset, Bytes(push_bytes.clone())));
+        self.offset += push_bytes.len() / 2;
+    }
+}
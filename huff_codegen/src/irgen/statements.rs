@@ -1,8 +1,21 @@
+use ethers_core::types::U256;
 use huff_utils::prelude::*;
 
-use crate::Codegen;
+/// The outcome of generating a Statement
+#[derive(Debug)]
+pub enum StatementBytecode<'a> {
+    /// The bytecode generated for the Statement
+    Bytes(Vec<(usize, Bytes)>),
+    /// An inlined macro invocation, whose bytecode is generated in place at the current offset
+    Invocation(&'a MacroDefinition, MacroInvocation),
+    /// A macro passed to `__codesize`, whose bytecode must be generated in its own scope first
+    Codesize(&'a MacroDefinition),
+}
 
 /// Generates the respective Bytecode for a given Statement
+///
+/// Statements that depend on the bytecode of another macro are not generated here. Instead, the
+/// macro is returned so the caller can generate it without growing the native stack.
 #[allow(clippy::too_many_arguments)]
 pub fn statement_gen<'a>(
     evm_version: &EVMVersion,
@@ -11,14 +24,15 @@ pub fn statement_gen<'a>(
     macro_def: &MacroDefinition,
     scope: &mut Vec<&'a MacroDefinition>,
     offset: &mut usize,
-    mis: &mut Vec<(usize, MacroInvocation)>,
+    mis: &mut [(usize, MacroInvocation)],
     jump_table: &mut JumpTable,
     label_indices: &mut LabelIndices,
     table_instances: &mut Jumps,
+    codeoffset_instances: &mut Jumps,
     utilized_tables: &mut Vec<TableDefinition>,
     circular_codesize_invocations: &mut CircularCodeSizeIndices,
     starting_offset: usize,
-) -> Result<Vec<(usize, Bytes)>, CodegenError> {
+) -> Result<StatementBytecode<'a>, CodegenError> {
     let mut bytes = vec![];
 
     tracing::debug!(target: "codegen", "Got Statement: {}", s.ty);
@@ -38,6 +52,7 @@ pub fn statement_gen<'a>(
                     kind: CodegenErrorKind::InvalidMacroInvocation(mi.macro_name.clone()),
                     span: mi.span.clone(),
                     token: None,
+                    call_stack: vec![],
                 });
             };
 
@@ -50,6 +65,7 @@ pub fn statement_gen<'a>(
                     kind: CodegenErrorKind::TestInvocation(ir_macro.name.clone()),
                     span: ir_macro.span.clone(),
                     token: None,
+                    call_stack: vec![],
                 });
             }
 
@@ -93,55 +109,36 @@ pub fn statement_gen<'a>(
                 // PUSH2 + 2 bytes + stack_swaps.len() + PUSH2 + 2 bytes + JUMP + JUMPDEST
                 *offset += stack_swaps.len() + 8;
             } else {
-                // Recurse into macro invocation
-                scope.push(ir_macro);
-                mis.push((*offset, mi.clone()));
-
-                let mut res: BytecodeRes = match Codegen::macro_to_bytecode(
-                    evm_version,
-                    ir_macro,
-                    contract,
-                    scope,
-                    *offset,
-                    mis,
-                    false,
-                    Some(circular_codesize_invocations),
-                ) {
-                    Ok(r) => r,
-                    Err(e) => {
+                // An inlined macro that is already being generated further up the invocation path
+                // would recurse forever. Macros only being sized for `__codesize` are in scope
+                // without an invocation, so the invocations since the earlier occurrence must
+                // account for every macro in scope after it.
+                if let Some(pos) = scope.iter().rposition(|def| def.name == ir_macro.name) {
+                    let entered = &scope[pos + 1..];
+                    let invoked = mis.len() >= entered.len() &&
+                        mis[mis.len() - entered.len()..]
+                            .iter()
+                            .zip(entered.iter())
+                            .all(|((_, mi), def)| mi.macro_name == def.name);
+                    if invoked {
+                        let mut cycle =
+                            scope[pos..].iter().map(|def| def.name.clone()).collect::<Vec<_>>();
+                        cycle.push(ir_macro.name.clone());
                         tracing::error!(
                             target: "codegen",
-                            "FAILED TO RECURSE INTO MACRO \"{}\"",
-                            ir_macro.name
+                            "RECURSIVE MACRO INVOCATION: {}",
+                            cycle.join(" -> ")
                         );
-                        return Err(e);
+                        return Err(CodegenError {
+                            kind: CodegenErrorKind::RecursiveMacroInvocation(cycle),
+                            span: mi.span.clone(),
+                            token: None,
+                            call_stack: vec![],
+                        });
                     }
-                };
-
-                // Set jump table values
-                tracing::debug!(target: "codegen", "Unmatched jumps: {:?}", res.unmatched_jumps.iter().map(|uj| uj.label.clone()).collect::<Vec<String>>());
-                for j in res.unmatched_jumps.iter_mut() {
-                    let new_index = j.bytecode_index;
-                    j.bytecode_index = 0;
-                    let mut new_jumps = if let Some(jumps) = jump_table.get(&new_index) {
-                        jumps.clone()
-                    } else {
-                        vec![]
-                    };
-                    new_jumps.push(j.clone());
-                    jump_table.insert(new_index, new_jumps);
                 }
-                table_instances.extend(res.table_instances);
-                label_indices.extend(res.label_indices);
 
-                let res_unique_tables =
-                    res.utilized_tables.iter().filter(|t| !utilized_tables.contains(t)).cloned().collect::<Vec<TableDefinition>>();
-                utilized_tables.extend(res_unique_tables);
-
-                // Increase offset by byte length of recursed macro
-                *offset += res.bytes.iter().map(|(_, b)| b.0.len()).sum::<usize>() / 2;
-                // Add the macro's bytecode to the final result
-                bytes = [bytes, res.bytes].concat()
+                return Ok(StatementBytecode::Invocation(ir_macro, mi.clone()))
             }
         }
         StatementType::Label(label) => {
@@ -168,29 +165,58 @@ pub fn statement_gen<'a>(
             tracing::info!(target: "codegen", "RECURSE BYTECODE GOT BUILTIN FUNCTION CALL: {:?}", bf);
             match bf.kind {
                 BuiltinFunctionKind::Codesize => {
-                    let ir_macro = if let Some(m) =
-                        contract.find_macro_by_name(bf.args[0].name.as_ref().unwrap())
-                    {
+                    // Get the name of the macro being passed to __codesize, `__codesize()` sizes
+                    // the macro being generated
+                    let codesize_arg = match bf.args.first() {
+                        Some(arg) => arg.name.as_ref().unwrap(),
+                        None => &macro_def.name,
+                    };
+
+                    let ir_macro = if let Some(m) = contract.find_macro_by_name(codesize_arg) {
                         m
                     } else {
                         tracing::error!(
                             target: "codegen",
                             "MISSING MACRO PASSED TO __codesize \"{}\"",
-                            bf.args[0].name.as_ref().unwrap()
+                            codesize_arg
                         );
                         return Err(CodegenError {
                             kind: CodegenErrorKind::MissingMacroDefinition(
-                                bf.args[0].name.as_ref().unwrap().to_string(), /* yuck */
+                                codesize_arg.to_string(),
                             ),
                             span: bf.span.clone(),
                             token: None,
+                            call_stack: vec![],
                         });
                     };
-
-                    // Get the name of the macro being passed to __codesize
-                    let codesize_arg = bf.args[0].name.as_ref().unwrap();
                     let is_previous_parent = scope.iter().any(|def| def.name == *codesize_arg);
 
+                    // Macros in scope that are neither the root macro nor invoked are only being
+                    // sized, so depending on one of their sizes again is a genuine cycle
+                    let is_sizing = is_previous_parent &&
+                        !macro_def.name.eq(codesize_arg) &&
+                        scope[0].name != *codesize_arg &&
+                        !mis.iter().any(|(_, mi)| mi.macro_name == *codesize_arg);
+                    if is_sizing {
+                        let mut cycle = scope
+                            .iter()
+                            .skip_while(|def| def.name != *codesize_arg)
+                            .map(|def| def.name.clone())
+                            .collect::<Vec<String>>();
+                        cycle.push(codesize_arg.to_string());
+                        tracing::error!(
+                            target: "codegen",
+                            "CIRCULAR __codesize REFERENCE: {}",
+                            cycle.join(" -> ")
+                        );
+                        return Err(CodegenError {
+                            kind: CodegenErrorKind::CircularCodesize(cycle),
+                            span: bf.span.clone(),
+                            token: None,
+                            call_stack: vec![],
+                        })
+                    }
+
                     // Special case:
                     // If the macro provided to __codesize is the current macro, we need to avoid a
                     // circular reference If this is the case we will store a
@@ -208,36 +234,10 @@ pub fn statement_gen<'a>(
                         *offset += 2;
                         bytes.push((starting_offset, Bytes("cccc".to_string())));
                     } else {
-                        // We will still need to recurse to get accurate values
-                        let res: BytecodeRes = match Codegen::macro_to_bytecode(
-                            evm_version,
-                            ir_macro,
-                            contract,
-                            scope,
-                            *offset,
-                            mis,
-                            ir_macro.name.eq("CONSTRUCTOR"),
-                            Some(circular_codesize_invocations),
-                        ) {
-                            Ok(r) => r,
-                            Err(e) => {
-                                tracing::error!(
-                                    target: "codegen",
-                                    "FAILED TO RECURSE INTO MACRO \"{}\"",
-                                    ir_macro.name
-                                );
-                                return Err(e);
-                            }
-                        };
-
-                        let size = format_even_bytes(format!(
-                            "{:02x}",
-                            (res.bytes.iter().map(|(_, b)| b.0.len()).sum::<usize>() / 2)
-                        ));
-                        let push_bytes = format!("{:02x}{size}", 95 + size.len() / 2);
-
-                        *offset += push_bytes.len() / 2;
-                        bytes.push((starting_offset, Bytes(push_bytes)));
+                        // We will still need to generate the macro to get accurate values. The
+                        // macro is sized in its own scope so that forward references resolve
+                        // without touching the state of the macro being generated.
+                        return Ok(StatementBytecode::Codesize(ir_macro))
                     }
                 }
                 BuiltinFunctionKind::Tablesize => {
@@ -252,16 +252,17 @@ pub fn statement_gen<'a>(
                             bf.args[0].name.as_ref().unwrap()
                         );
                         return Err(CodegenError {
-                            kind: CodegenErrorKind::InvalidMacroInvocation(
-                                bf.args[0].name.as_ref().unwrap().to_string(), /* yuck */
+                            kind: CodegenErrorKind::MissingTableDefinition(
+                                bf.args[0].name.as_ref().unwrap().to_string(),
                             ),
                             span: bf.span.clone(),
                             token: None,
+                            call_stack: vec![],
                         });
                     };
 
                     let size = bytes32_to_string(&ir_table.size, false);
-                    let push_bytes = format!("{:02x}{size}", 95 + size.len() / 2);
+                    let push_bytes = format_literal(size).expect("a word always fits a push");
 
                     if !utilized_tables.contains(&ir_table) {
                         utilized_tables.push(ir_table);
@@ -291,12 +292,42 @@ pub fn statement_gen<'a>(
                             "MISSING TABLE PASSED TO __tablestart \"{}\"",
                             bf.args[0].name.as_ref().unwrap()
                         );
+                        return Err(CodegenError {
+                            kind: CodegenErrorKind::MissingTableDefinition(
+                                bf.args[0].name.as_ref().unwrap().to_string(),
+                            ),
+                            span: bf.span.clone(),
+                            token: None,
+                            call_stack: vec![],
+                        });
+                    }
+                }
+                BuiltinFunctionKind::Codeoffset => {
+                    // Make sure the macro exists
+                    if contract.find_macro_by_name(bf.args[0].name.as_ref().unwrap()).is_some() {
+                        // The macro may be placed later in the bytecode, so the offset is filled
+                        // in `Codegen::gen_table_bytecode` once the layout is known
+                        codeoffset_instances.push(Jump {
+                            label: bf.args[0].name.as_ref().unwrap().to_owned(),
+                            bytecode_index: *offset,
+                            span: bf.span.clone(),
+                        });
+
+                        bytes.push((*offset, Bytes(format!("{}xxxx", Opcode::Push2))));
+                        *offset += 3;
+                    } else {
+                        tracing::error!(
+                            target: "codegen",
+                            "MISSING MACRO PASSED TO __codeoffset \"{}\"",
+                            bf.args[0].name.as_ref().unwrap()
+                        );
                         return Err(CodegenError {
                             kind: CodegenErrorKind::InvalidMacroInvocation(
                                 bf.args[0].name.as_ref().unwrap().to_string(),
                             ),
                             span: bf.span.clone(),
                             token: None,
+                            call_stack: vec![],
                         });
                     }
                 }
@@ -316,6 +347,7 @@ pub fn statement_gen<'a>(
                             ),
                             span: bf.span.clone(),
                             token: None,
+                            call_stack: vec![],
                         });
                     }
 
@@ -337,9 +369,10 @@ pub fn statement_gen<'a>(
                             format!("{}{}", Opcode::Push4, hex::encode(error.selector));
                         *offset += push_bytes.len() / 2;
                         bytes.push((starting_offset, Bytes(push_bytes)));
-                    } else if let Some(s) = &bf.args[0].name {
-                        let mut signature = [0u8; 4]; // Only keep first 4 bytes
-                        hash_bytes(&mut signature, s);
+                    } else if let Some(s) = bf.args[0].name.as_ref().filter(|s| s.contains('(')) {
+                        // A full signature doesn't need a matching declaration
+                        let signature = keccak256_selector(s)
+                            .map_err(|e| CodegenError { span: bf.span.clone(), ..e })?;
 
                         let push_bytes = format!("{}{}", Opcode::Push4, hex::encode(signature));
                         *offset += push_bytes.len() / 2;
@@ -356,6 +389,7 @@ pub fn statement_gen<'a>(
                             ),
                             span: bf.span.clone(),
                             token: None,
+                            call_stack: vec![],
                         });
                     }
                 }
@@ -375,6 +409,7 @@ pub fn statement_gen<'a>(
                             ),
                             span: bf.span.clone(),
                             token: None,
+                            call_stack: vec![],
                         });
                     }
 
@@ -387,9 +422,10 @@ pub fn statement_gen<'a>(
                         let push_bytes = format!("{}{hash}", Opcode::Push32);
                         *offset += push_bytes.len() / 2;
                         bytes.push((starting_offset, Bytes(push_bytes)));
-                    } else if let Some(s) = &bf.args[0].name {
-                        let mut hash = [0u8; 32];
-                        hash_bytes(&mut hash, s);
+                    } else if let Some(s) = bf.args[0].name.as_ref().filter(|s| s.contains('(')) {
+                        // A full signature doesn't need a matching declaration
+                        let hash = keccak256_hash(s)
+                            .map_err(|e| CodegenError { span: bf.span.clone(), ..e })?;
 
                         let push_bytes = format!("{}{}", Opcode::Push32, hex::encode(hash));
                         *offset += push_bytes.len() / 2;
@@ -406,6 +442,7 @@ pub fn statement_gen<'a>(
                             ),
                             span: bf.span.clone(),
                             token: None,
+                            call_stack: vec![],
                         });
                     }
                 }
@@ -423,6 +460,7 @@ pub fn statement_gen<'a>(
                             )),
                             span: bf.span.clone(),
                             token: None,
+                            call_stack: vec![],
                         });
                     }
 
@@ -431,15 +469,15 @@ pub fn statement_gen<'a>(
                         .iter()
                         .find(|e| bf.args[0].name.as_ref().unwrap().eq(&e.name))
                     {
-                        // Add 28 bytes to left-pad the 4 byte selector
-                        let selector =
-                            format!("{}{}", hex::encode(error.selector), "00".repeat(28));
-                        let push_bytes = format!("{}{selector}", Opcode::Push32);
+                        // Push the raw 4 byte selector, callers shift it into place
+                        let push_bytes =
+                            format!("{}{}", Opcode::Push4, hex::encode(error.selector));
                         *offset += push_bytes.len() / 2;
                         bytes.push((starting_offset, Bytes(push_bytes)));
-                    } else if let Some(s) = &bf.args[0].name {
-                        let mut signature = [0u8; 4]; // Only keep first 4 bytes
-                        hash_bytes(&mut signature, s);
+                    } else if let Some(s) = bf.args[0].name.as_ref().filter(|s| s.contains('(')) {
+                        // A full signature doesn't need a matching declaration
+                        let signature = keccak256_selector(s)
+                            .map_err(|e| CodegenError { span: bf.span.clone(), ..e })?;
 
                         let push_bytes = format!("{}{}", Opcode::Push4, hex::encode(signature));
                         *offset += push_bytes.len() / 2;
@@ -456,6 +494,7 @@ pub fn statement_gen<'a>(
                             ),
                             span: bf.span.clone(),
                             token: None,
+                            call_stack: vec![],
                         });
                     }
                 }
@@ -473,10 +512,27 @@ pub fn statement_gen<'a>(
                             )),
                             span: bf.span.clone(),
                             token: None,
+                            call_stack: vec![],
                         });
                     }
 
                     let hex = format_even_bytes(bf.args[0].name.as_ref().unwrap().clone());
+                    if hex.len() > 64 {
+                        tracing::error!(
+                            target: "codegen",
+                            "__RIGHTPAD ARGUMENT EXCEEDS 32 BYTES: {}",
+                            hex.len() / 2
+                        );
+                        return Err(CodegenError {
+                            kind: CodegenErrorKind::InvalidArguments(format!(
+                                "__RIGHTPAD argument must be at most 32 bytes, got {}",
+                                hex.len() / 2
+                            )),
+                            span: bf.span.clone(),
+                            token: None,
+                            call_stack: vec![],
+                        })
+                    }
                     let push_bytes =
                         format!("{}{hex}{}", Opcode::Push32, "0".repeat(64 - hex.len()));
                     *offset += push_bytes.len() / 2;
@@ -496,6 +552,7 @@ pub fn statement_gen<'a>(
                             )),
                             span: bf.span.clone(),
                             token: None,
+                            call_stack: vec![],
                         });
                     }
 
@@ -515,6 +572,7 @@ pub fn statement_gen<'a>(
                             ),
                             span: bf.span.clone(),
                             token: None,
+                            call_stack: vec![],
                         });
                     }
 
@@ -548,6 +606,7 @@ pub fn statement_gen<'a>(
                             )),
                             span: bf.span.clone(),
                             token: None,
+                            call_stack: vec![],
                         });
                     }
 
@@ -570,14 +629,157 @@ pub fn statement_gen<'a>(
                             kind: CodegenErrorKind::InvalidHex(verbatim_str.to_string()),
                             span: bf.span.clone(),
                             token: None,
+                            call_stack: vec![],
+                        });
+                    }
+
+                    // The bytes are spliced in as-is, so a dangling nibble can't be padded away.
+                    // Literal arguments are values without their leading zero bytes, exact leading
+                    // zeros need to be passed as a string.
+                    if verbatim_str.len() % 2 != 0 {
+                        tracing::error!(
+                            target: "codegen",
+                            "ODD LENGTH HEX STRING PASSED TO __VERBATIM: \"{}\"",
+                            verbatim_str
+                        );
+                        return Err(CodegenError {
+                            kind: CodegenErrorKind::InvalidHex(verbatim_str.to_string()),
+                            span: bf.span.clone(),
+                            token: None,
+                            call_stack: vec![],
                         });
                     }
 
                     tracing::debug!(target: "codegen", "INJECTING as verbatim: {}", verbatim_str);
-                    let hex = format_even_bytes(verbatim_str.clone());
-                    let push_bytes = hex.to_string();
-                    *offset += hex.len() / 2;
+                    *offset += verbatim_str.len() / 2;
+                    bytes.push((starting_offset, Bytes(verbatim_str.to_string())));
+                }
+                BuiltinFunctionKind::Shl | BuiltinFunctionKind::Shr => {
+                    let name = if bf.kind == BuiltinFunctionKind::Shl { "__SHL" } else { "__SHR" };
+                    if bf.args.len() != 2 {
+                        tracing::error!(
+                            target: "codegen",
+                            "Incorrect number of arguments passed to {}, should be 2: {}",
+                            name,
+                            bf.args.len()
+                        );
+                        return Err(CodegenError {
+                            kind: CodegenErrorKind::InvalidArguments(format!(
+                                "Incorrect number of arguments passed to {name}, should be 2: {}",
+                                bf.args.len()
+                            )),
+                            span: bf.span.clone(),
+                            token: None,
+                            call_stack: vec![],
+                        });
+                    }
+
+                    // Both operands must be literals
+                    let operands = bf
+                        .args
+                        .iter()
+                        .map(|arg| {
+                            let hex = arg.name.clone().unwrap_or_default();
+                            U256::from_str_radix(&hex, 16).map_err(|_| {
+                                tracing::error!(
+                                    target: "codegen",
+                                    "INVALID LITERAL PASSED TO {}: \"{}\"",
+                                    name,
+                                    hex
+                                );
+                                CodegenError {
+                                    kind: CodegenErrorKind::InvalidHex(hex.clone()),
+                                    span: arg.span.clone(),
+                                    token: None,
+                                    call_stack: vec![],
+                                }
+                            })
+                        })
+                        .collect::<Result<Vec<U256>, CodegenError>>()?;
+                    let (shift, value) = (operands[0], operands[1]);
+
+                    // Like the SHL and SHR opcodes, shifting by 256 bits or more results in zero
+                    let result = if shift >= U256::from(256) {
+                        U256::zero()
+                    } else if bf.kind == BuiltinFunctionKind::Shl {
+                        value << shift.as_usize()
+                    } else {
+                        value >> shift.as_usize()
+                    };
+                    tracing::debug!(target: "codegen", "FOLDED {}({}, {}) TO {}", name, shift, value, result);
+
+                    let mut literal = [0u8; 32];
+                    result.to_big_endian(&mut literal);
+                    let push_bytes = literal_gen(evm_version, &literal);
+                    *offset += push_bytes.len() / 2;
+                    bytes.push((starting_offset, Bytes(push_bytes)));
+                }
+                BuiltinFunctionKind::Invalid => {
+                    if !bf.args.is_empty() {
+                        tracing::error!(
+                            target: "codegen",
+                            "Incorrect number of arguments passed to __INVALID, should be 0: {}",
+                            bf.args.len()
+                        );
+                        return Err(CodegenError {
+                            kind: CodegenErrorKind::InvalidArguments(format!(
+                                "Incorrect number of arguments passed to __INVALID, should be 0: {}",
+                                bf.args.len()
+                            )),
+                            span: bf.span.clone(),
+                            token: None,
+                            call_stack: vec![],
+                        });
+                    }
 
+                    let push_bytes = Opcode::Invalid.to_string();
+                    *offset += push_bytes.len() / 2;
+                    bytes.push((starting_offset, Bytes(push_bytes)));
+                }
+                BuiltinFunctionKind::CalldataArg => {
+                    if bf.args.len() != 1 {
+                        tracing::error!(
+                            target: "codegen",
+                            "Incorrect number of arguments passed to __CALLDATA_ARG, should be 1: {}",
+                            bf.args.len()
+                        );
+                        return Err(CodegenError {
+                            kind: CodegenErrorKind::InvalidArguments(format!(
+                                "Incorrect number of arguments passed to __CALLDATA_ARG, should be 1: {}",
+                                bf.args.len()
+                            )),
+                            span: bf.span.clone(),
+                            token: None,
+                            call_stack: vec![],
+                        });
+                    }
+
+                    // The index must be a literal so the offset can be computed at compile time
+                    let hex = bf.args[0].name.clone().unwrap_or_default();
+                    let calldata_offset = U256::from_str_radix(&hex, 16)
+                        .ok()
+                        .and_then(|index| index.checked_mul(U256::from(32)))
+                        .and_then(|words| words.checked_add(U256::from(4)))
+                        .ok_or_else(|| {
+                            tracing::error!(
+                                target: "codegen",
+                                "INVALID INDEX PASSED TO __CALLDATA_ARG: \"{}\"",
+                                hex
+                            );
+                            CodegenError {
+                                kind: CodegenErrorKind::InvalidHex(hex.clone()),
+                                span: bf.args[0].span.clone(),
+                                token: None,
+                                call_stack: vec![],
+                            }
+                        })?;
+                    tracing::debug!(target: "codegen", "LOADING CALLDATA WORD AT OFFSET {}", calldata_offset);
+
+                    let mut literal = [0u8; 32];
+                    calldata_offset.to_big_endian(&mut literal);
+                    let push_bytes =
+                        format!("{}{}", literal_gen(evm_version, &literal), Opcode::Calldataload);
+                    *offset += push_bytes.len() / 2;
                     bytes.push((starting_offset, Bytes(push_bytes)));
                 }
             }
@@ -589,9 +791,16 @@ pub fn statement_gen<'a>(
                 kind: CodegenErrorKind::InvalidMacroStatement,
                 span: s.span.clone(),
                 token: None,
+                call_stack: vec![],
             });
         }
     }
 
-    Ok(bytes)
+    Ok(StatementBytecode::Bytes(bytes))
+}
+
+/// Generates the push of a macro's size for `__codesize` from its generated bytecode
+pub fn codesize_push(res: &BytecodeRes) -> String {
+    let size = res.bytes.iter().map(|(_, b)| b.0.len()).sum::<usize>() / 2;
+    format_literal(format!("{size:02x}")).expect("a size always fits a push")
 }
@@ -4,23 +4,55 @@
 #![forbid(unsafe_code)]
 #![forbid(where_clauses_object_safety)]
 
+use ethers_core::{
+    abi::{
+        param_type::Reader,
+        token::{LenientTokenizer, Tokenizer},
+        ParamType,
+    },
+    types::U256,
+};
+use huff_lexer::Lexer;
+use huff_parser::Parser;
 use huff_utils::{
     abi::*,
     artifact::*,
     ast::*,
     bytecode::*,
     bytes_util,
-    error::CodegenError,
+    error::{CodegenError, CodegenWarning, CodegenWarningKind, CompilerError, LexicalError},
     evm::Opcode,
     prelude::{format_even_bytes, pad_n_bytes, CodegenErrorKind, EVMVersion, FileSource, Span},
     types::EToken,
 };
 use regex::Regex;
-use std::{cmp::Ordering, collections::HashMap, fs, path::Path, sync::Arc};
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    fs,
+    ops::Range,
+    path::Path,
+    sync::Arc,
+};
 
 mod irgen;
 use crate::irgen::prelude::*;
 
+mod events;
+pub use events::EventSink;
+
+mod bootstrap;
+pub use bootstrap::BootstrapGenerator;
+
+mod metadata;
+pub use metadata::Metadata;
+
+mod cache;
+pub use cache::ParseCache;
+
+/// The maximum size of runtime bytecode that can be deployed, as defined by EIP-170
+pub const MAX_CONTRACT_SIZE: usize = 24576;
+
 /// ### Codegen
 ///
 /// Code Generation Manager responsible for generating bytecode from a
@@ -46,77 +78,1395 @@ pub struct Codegen {
     pub main_bytecode: Option<String>,
     /// Intermediate constructor bytecode store
     pub constructor_bytecode: Option<String>,
+    /// Warnings accumulated by the analysis passes
+    pub warnings: Vec<CodegenWarning>,
+    /// Whether warnings should fail the build
+    pub deny_warnings: bool,
+    /// Whether invoked test macros should be compiled
+    pub include_tests: bool,
+    /// Whether to lint jumps to computed destinations
+    pub lint_jumps: bool,
+    /// Whether to report address literals used as call targets
+    pub lint_addresses: bool,
+    /// Whether `INVALID` is only allowed through the explicit `__INVALID()` builtin
+    pub strict_invalid: bool,
+    /// Whether every declared macro parameter must be referenced in the macro body
+    pub strict_params: bool,
+    /// Whether entry point macros may declare a stack signature other than `takes(0) returns(0)`
+    pub lenient_entry_points: bool,
+    /// Whether to report macros with identical bodies
+    pub lint_duplicates: bool,
+    /// Whether to report labels that are never referenced
+    pub lint_labels: bool,
+    /// Shared prelude linked into every compiled contract
+    pub prelude: Option<Contract>,
+    /// Sink receiving progress events as JSON Lines
+    pub event_sink: Option<EventSink>,
+    /// Whether artifact bytecode is emitted as uppercase hex
+    pub hex_uppercase: bool,
+    /// Whether literals of explicit pushes are trimmed to their smallest push
+    pub trim_push_literals: bool,
+    /// Whether macro bodies are checked against their declared `takes` and `returns`
+    pub verify_stack: bool,
+    /// Generator replacing the default deployment bootstrap code
+    pub bootstrap: Option<BootstrapGenerator>,
+    /// Metadata trailer appended to the runtime bytecode
+    pub metadata: Option<Metadata>,
+    /// Cache of parsed source files
+    pub cache: Option<ParseCache>,
 }
 
-impl Codegen {
-    /// Public associated function to instantiate a new Codegen instance.
-    pub fn new() -> Self {
-        Self { ast: None, artifact: None, main_bytecode: None, constructor_bytecode: None }
+impl Codegen {
+    /// Public associated function to instantiate a new Codegen instance.
+    pub fn new() -> Self {
+        Self {
+            ast: None,
+            artifact: None,
+            main_bytecode: None,
+            constructor_bytecode: None,
+            warnings: vec![],
+            deny_warnings: false,
+            include_tests: false,
+            lint_jumps: false,
+            lint_addresses: false,
+            strict_invalid: false,
+            strict_params: false,
+            lenient_entry_points: false,
+            lint_duplicates: false,
+            lint_labels: false,
+            prelude: None,
+            event_sink: None,
+            hex_uppercase: false,
+            trim_push_literals: false,
+            verify_stack: false,
+            bootstrap: None,
+            metadata: None,
+            cache: None,
+        }
+    }
+
+    /// Parses a shared prelude of constants and macros to link into every compiled contract
+    ///
+    /// The prelude is linked into a contract with [`Codegen::link_prelude`], making its definitions
+    /// available to the contract source without an import.
+    pub fn with_prelude(mut self, source: String) -> Result<Self, CompilerError> {
+        let tokens = Lexer::new(&source)
+            .collect::<Result<Vec<_>, LexicalError>>()
+            .map_err(CompilerError::LexicalError)?;
+        let prelude = Parser::new(tokens, None).parse().map_err(CompilerError::ParserError)?;
+        self.prelude = Some(prelude);
+        Ok(self)
+    }
+
+    /// Links the prelude, if any, into a parsed contract
+    ///
+    /// Definitions colliding with the prelude are rejected like any other linked contract. Storage
+    /// pointers should be derived after linking, so prelude constants are assigned slots as well.
+    pub fn link_prelude(&self, contract: Contract) -> Result<Contract, CodegenError> {
+        match &self.prelude {
+            Some(prelude) => contract.link(prelude.clone()),
+            None => Ok(contract),
+        }
+    }
+
+    /// Attaches an event sink receiving progress events as JSON Lines
+    ///
+    /// Events are written for macros started and completed and tables generated while code
+    /// generation runs through [`Codegen::with_events`].
+    pub fn with_event_sink(mut self, sink: Box<dyn std::io::Write + Send>) -> Self {
+        self.event_sink = Some(EventSink::new(sink));
+        self
+    }
+
+    /// Replaces the default deployment bootstrap code with a custom generator
+    ///
+    /// The generator is called by [`Codegen::churn`] with the runtime and constructor bytecode
+    /// lengths in bytes, and its output is placed between the constructor and runtime bytecode.
+    /// Contracts with a custom bootstrap in their constructor never use the generator.
+    pub fn with_bootstrap(
+        mut self,
+        generator: impl Fn(usize, usize) -> String + Send + Sync + 'static,
+    ) -> Self {
+        self.bootstrap = Some(BootstrapGenerator::new(generator));
+        self
+    }
+
+    /// Appends a CBOR metadata trailer to the runtime bytecode generated by [`Codegen::churn`]
+    ///
+    /// Off by default, since the trailer is only needed by tooling expecting solc's layout.
+    pub fn with_metadata(mut self, metadata: Metadata) -> Self {
+        self.metadata = Some(metadata);
+        self
+    }
+
+    /// Caches the contracts parsed by [`Codegen::parse_file`]
+    pub fn with_cache(mut self, cache: ParseCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Runs code generation with the event sink, if any, attached
+    ///
+    /// Code generation runs through associated functions, so the sink is attached to the current
+    /// thread for the duration of `f`, e.g.
+    /// `cg.with_events(|| Codegen::generate_main_bytecode(&evm_version, &contract, None))`.
+    pub fn with_events<T>(&self, f: impl FnOnce() -> T) -> T {
+        match &self.event_sink {
+            Some(sink) => sink.attach(f),
+            None => f(),
+        }
+    }
+
+    /// Writes a warning to the warning channel.
+    ///
+    /// If `deny_warnings` is set, the warning is converted into a CodegenError instead.
+    pub fn warn(&mut self, warning: CodegenWarning) -> Result<(), CodegenError> {
+        if self.deny_warnings {
+            tracing::error!(target: "codegen", "DENIED WARNING: {}", warning.kind);
+            return Err(CodegenError {
+                kind: CodegenErrorKind::DeniedWarning(warning.kind),
+                span: warning.span,
+                token: None,
+                call_stack: vec![],
+            })
+        }
+        tracing::warn!(target: "codegen", "{}", warning.kind);
+        self.warnings.push(warning);
+        Ok(())
+    }
+
+    /// Runs the analysis passes over a Contract AST
+    ///
+    /// Unused macros, unused constants, unreachable labels, empty tables and stacks too deep to
+    /// reach are always written to the warning channel. The other lints are opt-in:
+    ///
+    /// * `lint_jumps` - Warns on computed jumps
+    /// * `lint_addresses` - Warns on call target addresses
+    /// * `lint_duplicates` - Warns on macros with identical bodies
+    /// * `lint_labels` - Warns on labels that are never referenced
+    /// * `strict_invalid` - Fails on an `INVALID` opcode not emitted through `__INVALID()`
+    /// * `strict_params` - Fails on a macro parameter that is never referenced
+    /// * `verify_stack` - Fails on a macro body that doesn't balance its declared stack signature
+    pub fn lint(&mut self, contract: &Contract) -> Result<(), CodegenError> {
+        // Collect the invoked macros and referenced constants of every macro
+        let mut invoked_macros = BTreeSet::new();
+        let mut referenced_constants = BTreeSet::new();
+        for macro_def in contract.macros.iter() {
+            visit_statements(&macro_def.statements, &mut |s| match &s.ty {
+                StatementType::Constant(name) => {
+                    referenced_constants.insert(name.clone());
+                }
+                StatementType::MacroInvocation(mi) => {
+                    invoked_macros.insert(mi.macro_name.clone());
+                    for arg in mi.args.iter() {
+                        match arg {
+                            MacroArg::Ident(name) => {
+                                referenced_constants.insert(name.clone());
+                            }
+                            MacroArg::Builtin(bf) if bf.kind == BuiltinFunctionKind::Codesize => {
+                                invoked_macros
+                                    .extend(bf.args.iter().filter_map(|a| a.name.clone()));
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                StatementType::BuiltinFunctionCall(bf)
+                    if bf.kind == BuiltinFunctionKind::Codesize =>
+                {
+                    invoked_macros.extend(bf.args.iter().filter_map(|a| a.name.clone()));
+                }
+                _ => {}
+            });
+        }
+
+        for m in contract.macros.iter().filter(|m| {
+            !m.test &&
+                !matches!(m.name.as_str(), "MAIN" | "CONSTRUCTOR") &&
+                !invoked_macros.contains(&m.name)
+        }) {
+            self.warn(CodegenWarning {
+                kind: CodegenWarningKind::UnusedMacro(m.name.clone()),
+                span: m.span.clone(),
+            })?;
+        }
+
+        let unused_constants = contract
+            .constants
+            .lock()
+            .map_err(|_| CodegenError::new(CodegenErrorKind::LockingError, AstSpan(vec![]), None))?
+            .iter()
+            .filter(|c| !referenced_constants.contains(&c.name))
+            .map(|c| (c.name.clone(), c.span.clone()))
+            .collect::<Vec<(String, AstSpan)>>();
+        for (name, span) in unused_constants {
+            self.warn(CodegenWarning { kind: CodegenWarningKind::UnusedConstant(name), span })?;
+        }
+
+        // A label is unreachable if it is never referenced and directly follows a terminating
+        // opcode, so it can't be reached by falling through either
+        let reference_counts = Codegen::label_reference_counts(contract);
+        let mut unreachable_labels = vec![];
+        for macro_def in contract.macros.iter() {
+            let mut previous: Option<&Statement> = None;
+            visit_statements(&macro_def.statements, &mut |s| {
+                if let StatementType::Label(label) = &s.ty {
+                    let after_terminator = previous.is_some_and(
+                        |p| matches!(&p.ty, StatementType::Opcode(o) if o.is_terminator()),
+                    );
+                    if after_terminator && reference_counts.get(&label.name) == Some(&0) {
+                        unreachable_labels.push((label.name.clone(), s.span.clone()));
+                    }
+                }
+                previous = Some(s);
+            });
+        }
+        for (name, span) in unreachable_labels {
+            self.warn(CodegenWarning { kind: CodegenWarningKind::UnreachableLabel(name), span })?;
+        }
+
+        // Sizing or locating a table without statements is almost always a mistake
+        let mut empty_tables = vec![];
+        for macro_def in contract.macros.iter() {
+            visit_statements(&macro_def.statements, &mut |s| {
+                let builtins = match &s.ty {
+                    StatementType::BuiltinFunctionCall(bf) => vec![bf],
+                    StatementType::MacroInvocation(mi) => mi
+                        .args
+                        .iter()
+                        .filter_map(|arg| match arg {
+                            MacroArg::Builtin(bf) => Some(bf),
+                            _ => None,
+                        })
+                        .collect(),
+                    _ => vec![],
+                };
+                for bf in builtins {
+                    let table = table_target(bf).and_then(|name| contract.find_table_by_name(name));
+                    if let Some(table) = table.filter(|t| t.statements.is_empty()) {
+                        empty_tables.push((table.name, bf.span.clone()));
+                    }
+                }
+            });
+        }
+        for (name, span) in empty_tables {
+            self.warn(CodegenWarning { kind: CodegenWarningKind::EmptyTable(name), span })?;
+        }
+
+        self.lint_stack_depth(contract)?;
+
+        if self.lint_jumps {
+            self.lint_computed_jumps(contract)?;
+        }
+
+        if self.lint_addresses {
+            self.lint_call_targets(contract)?;
+        }
+
+        if self.lint_duplicates {
+            self.lint_duplicate_macros(contract)?;
+        }
+
+        if self.lint_labels {
+            self.lint_unreferenced_labels(contract)?;
+        }
+
+        if self.strict_invalid {
+            Codegen::check_explicit_invalid(contract)?;
+        }
+
+        if self.strict_params {
+            Codegen::check_macro_parameters(contract)?;
+        }
+
+        if self.verify_stack {
+            self.verify_stack_balance(contract)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes every 20-byte literal used as the target of a call to the warning channel, so
+    /// hardcoded addresses can be reviewed
+    ///
+    /// A literal, or a constant with a literal value, is a call target if it is pushed right before
+    /// the gas argument of a `CALL`, `CALLCODE`, `DELEGATECALL` or `STATICCALL`.
+    fn lint_call_targets(&mut self, contract: &Contract) -> Result<(), CodegenError> {
+        let constants = contract
+            .constants
+            .lock()
+            .map_err(|_| CodegenError::new(CodegenErrorKind::LockingError, AstSpan(vec![]), None))?
+            .clone();
+        let literal_value = |s: &Statement| match &s.ty {
+            StatementType::Literal(l) => Some(*l),
+            StatementType::Constant(name) => {
+                constants.iter().find(|c| c.name == *name).and_then(|c| match c.value {
+                    ConstVal::Literal(l) => Some(l),
+                    ConstVal::FreeStoragePointer(_) => None,
+                })
+            }
+            _ => None,
+        };
+
+        let mut call_targets = vec![];
+        for macro_def in contract.macros.iter() {
+            let mut stream: Vec<&Statement> = vec![];
+            visit_statements(&macro_def.statements, &mut |s| stream.push(s));
+            for window in stream.windows(3) {
+                let is_call = matches!(
+                    window[2].ty,
+                    StatementType::Opcode(
+                        Opcode::Call | Opcode::Callcode | Opcode::Delegatecall | Opcode::Staticcall
+                    )
+                );
+                let gas_push = matches!(window[1].ty, StatementType::Opcode(Opcode::Gas)) ||
+                    literal_value(window[1]).is_some();
+                let address = literal_value(window[0])
+                    .filter(|l| l[..12].iter().all(|b| *b == 0) && l[12] != 0);
+                if let (true, true, Some(address)) = (is_call, gas_push, address) {
+                    call_targets.push((address, window[0].span.clone()));
+                }
+            }
+        }
+
+        for (address, span) in call_targets {
+            let address = format!("0x{}", hex::encode(&address[12..]));
+            self.warn(CodegenWarning { kind: CodegenWarningKind::CallTarget(address), span })?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes every label that is never referenced to the warning channel
+    ///
+    /// The `JUMPDEST` of such a label is a wasted byte unless execution falls through it. Labels
+    /// already reported as unreachable aren't reported again.
+    fn lint_unreferenced_labels(&mut self, contract: &Contract) -> Result<(), CodegenError> {
+        let reference_counts = Codegen::label_reference_counts(contract);
+        let mut unreferenced_labels = vec![];
+        for macro_def in contract.macros.iter() {
+            visit_statements(&macro_def.statements, &mut |s| {
+                if let StatementType::Label(label) = &s.ty {
+                    if reference_counts.get(&label.name) == Some(&0) {
+                        unreferenced_labels.push((label.name.clone(), s.span.clone()));
+                    }
+                }
+            });
+        }
+
+        for (name, span) in unreferenced_labels {
+            let unreachable = CodegenWarningKind::UnreachableLabel(name.clone());
+            if self.warnings.iter().any(|w| w.kind == unreachable) {
+                continue
+            }
+            self.warn(CodegenWarning { kind: CodegenWarningKind::UnreferencedLabel(name), span })?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes every `JUMP` or `JUMPI` that is not immediately preceded by a constant push to the
+    /// warning channel
+    ///
+    /// Statements are walked in the order they are emitted, so a jump at the start of a label
+    /// follows the label's `JUMPDEST` and a jump after a macro invocation is always flagged.
+    fn lint_computed_jumps(&mut self, contract: &Contract) -> Result<(), CodegenError> {
+        let mut computed_jumps = vec![];
+        for macro_def in contract.macros.iter() {
+            let mut previous: Option<&Statement> = None;
+            visit_statements(&macro_def.statements, &mut |s| {
+                let is_jump = matches!(s.ty, StatementType::Opcode(Opcode::Jump | Opcode::Jumpi));
+                let constant_push = previous.is_some_and(|p| {
+                    matches!(
+                        p.ty,
+                        StatementType::Literal(_) |
+                            StatementType::Constant(_) |
+                            StatementType::ArgCall(_) |
+                            StatementType::LabelCall(_) |
+                            StatementType::BuiltinFunctionCall(_) |
+                            StatementType::Opcode(Opcode::Push0)
+                    )
+                });
+                if is_jump && !constant_push {
+                    computed_jumps.push((macro_def.name.clone(), s.span.clone()));
+                }
+                previous = Some(s);
+            });
+        }
+
+        for (name, span) in computed_jumps {
+            self.warn(CodegenWarning { kind: CodegenWarningKind::ComputedJump(name), span })?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes every pair of macros with identical intermediate bytecode to the warning channel, as
+    /// candidates for consolidation
+    ///
+    /// Macros are compared by their `to_irbytecode` output with spans ignored. Each macro is
+    /// reported against the first macro defined with the same body, and empty macros are skipped.
+    fn lint_duplicate_macros(&mut self, contract: &Contract) -> Result<(), CodegenError> {
+        let mut bodies: Vec<(&MacroDefinition, Vec<String>)> = vec![];
+        let mut duplicates = vec![];
+        for macro_def in contract.macros.iter().filter(|m| !m.statements.is_empty()) {
+            let body = macro_def
+                .to_irbytecode(&EVMVersion::default())?
+                .0
+                .iter()
+                .map(|irb| ir_key(&irb.ty))
+                .collect::<Vec<String>>();
+            match bodies.iter().find(|(_, b)| *b == body) {
+                Some((original, _)) => duplicates.push((
+                    original.name.clone(),
+                    macro_def.name.clone(),
+                    macro_def.span.clone(),
+                )),
+                None => bodies.push((macro_def, body)),
+            }
+        }
+
+        for (original, duplicate, span) in duplicates {
+            self.warn(CodegenWarning {
+                kind: CodegenWarningKind::DuplicateMacroBody(original, duplicate),
+                span,
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// Errors on the first `INVALID` opcode written out in a macro body
+    ///
+    /// Only the `__INVALID()` builtin states the intent to emit `INVALID`, so the opcode itself is
+    /// treated as incidental, e.g. a leftover from a mistyped instruction.
+    fn check_explicit_invalid(contract: &Contract) -> Result<(), CodegenError> {
+        for macro_def in contract.macros.iter() {
+            let mut incidental = None;
+            visit_statements(&macro_def.statements, &mut |s| {
+                if incidental.is_none() && s.ty == StatementType::Opcode(Opcode::Invalid) {
+                    incidental = Some(s.span.clone());
+                }
+            });
+            if let Some(span) = incidental {
+                tracing::error!(target: "codegen", "INCIDENTAL INVALID OPCODE IN MACRO \"{}\"", macro_def.name);
+                return Err(CodegenError {
+                    kind: CodegenErrorKind::IncidentalInvalid(macro_def.name.clone()),
+                    span,
+                    token: None,
+                    call_stack: vec![],
+                })
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Errors on the first declared macro parameter that is never referenced
+    ///
+    /// A parameter is referenced by an `<arg>` call in the macro body, either as a statement or
+    /// as an argument passed on to an invoked macro.
+    fn check_macro_parameters(contract: &Contract) -> Result<(), CodegenError> {
+        for macro_def in contract.macros.iter() {
+            let mut referenced = BTreeSet::new();
+            visit_statements(&macro_def.statements, &mut |s| match &s.ty {
+                StatementType::ArgCall(name) => {
+                    referenced.insert(name.as_str());
+                }
+                StatementType::MacroInvocation(mi) => {
+                    referenced.extend(mi.args.iter().filter_map(|arg| match arg {
+                        MacroArg::ArgCall(name) => Some(name.as_str()),
+                        _ => None,
+                    }));
+                }
+                _ => {}
+            });
+
+            let unused = macro_def.parameters.iter().find_map(|p| {
+                p.name.as_deref().filter(|name| !referenced.contains(name)).map(|name| (name, p))
+            });
+            if let Some((name, param)) = unused {
+                tracing::error!(target: "codegen", "UNUSED PARAMETER \"{}\" IN MACRO \"{}\"", name, macro_def.name);
+                return Err(CodegenError {
+                    kind: CodegenErrorKind::UnusedMacroParameter {
+                        macro_name: macro_def.name.clone(),
+                        param: name.to_string(),
+                    },
+                    span: param.span.clone(),
+                    token: None,
+                    call_stack: vec![],
+                })
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes every macro whose tracked stack grows beyond the 16 items DUP and SWAP can reach
+    /// to the warning channel
+    ///
+    /// The tracker is branch-free: it starts at the macro's `takes`, applies the stack effect of
+    /// each statement, counts nested invocations by their declared `takes` and `returns`, and stops
+    /// at the first label or statement whose effect it can't know. A macro is reported once, at
+    /// the first DUP or SWAP executed while the stack holds more than 16 items.
+    fn lint_stack_depth(&mut self, contract: &Contract) -> Result<(), CodegenError> {
+        let mut too_deep = vec![];
+        for macro_def in contract.macros.iter() {
+            let mut height = macro_def.takes;
+            for s in macro_def.statements.iter() {
+                let (inputs, outputs) = match &s.ty {
+                    StatementType::Literal(_) |
+                    StatementType::Constant(_) |
+                    StatementType::LabelCall(_) => (0, 1),
+                    StatementType::BuiltinFunctionCall(bf)
+                        if bf.kind == BuiltinFunctionKind::Invalid =>
+                    {
+                        (0, 0)
+                    }
+                    StatementType::BuiltinFunctionCall(bf)
+                        if !matches!(
+                            bf.kind,
+                            BuiltinFunctionKind::Verbatim | BuiltinFunctionKind::DynConstructorArg
+                        ) =>
+                    {
+                        (0, 1)
+                    }
+                    StatementType::Opcode(o) => {
+                        // The deepest item a DUP or SWAP touches, counting the top as 1. Only
+                        // one reaching as far as the EVM allows while more items lie below it is
+                        // digging for something it can't reach.
+                        let depth = if o.is_dup() {
+                            Some((o.to_u8() - Opcode::Dup1.to_u8() + 1) as usize)
+                        } else if o.is_swap() {
+                            Some((o.to_u8() - Opcode::Swap1.to_u8() + 2) as usize)
+                        } else {
+                            None
+                        };
+                        if depth.is_some_and(|d| d >= 16 && height > d) {
+                            too_deep.push((macro_def.name.clone(), height, s.span.clone()));
+                            break
+                        }
+                        stack_effect(o)
+                    }
+                    StatementType::MacroInvocation(mi) => {
+                        match contract.macros.iter().find(|m| m.name == mi.macro_name) {
+                            Some(m) => (m.takes, m.returns),
+                            None => break,
+                        }
+                    }
+                    _ => break,
+                };
+                height = height.saturating_sub(inputs) + outputs;
+            }
+        }
+
+        for (name, height, span) in too_deep {
+            self.warn(CodegenWarning {
+                kind: CodegenWarningKind::StackTooDeep(name, height),
+                span,
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// Checks the net stack effect of every macro body against its declared `takes` and `returns`
+    ///
+    /// Only straight-line macros are verified, with invoked macros accounted for by their declared
+    /// signature. A macro that ends in a terminating opcode is balanced regardless of what it
+    /// leaves on the stack. Macros containing labels, jumps or statements with an unknown stack
+    /// effect are written to the warning channel instead.
+    pub fn verify_stack_balance(&mut self, contract: &Contract) -> Result<(), CodegenError> {
+        let mut unverified = vec![];
+        'macros: for macro_def in contract.macros.iter() {
+            let mut height = macro_def.takes as isize;
+            for s in macro_def.statements.iter() {
+                let (inputs, outputs) = match &s.ty {
+                    StatementType::Literal(_) |
+                    StatementType::Constant(_) |
+                    StatementType::LabelCall(_) => (0, 1),
+                    StatementType::Opcode(Opcode::Jump | Opcode::Jumpi) => {
+                        unverified.push((macro_def.name.clone(), macro_def.span.clone()));
+                        continue 'macros
+                    }
+                    StatementType::Opcode(o) if o.is_terminator() => continue 'macros,
+                    StatementType::Opcode(o) => stack_effect(o),
+                    StatementType::BuiltinFunctionCall(bf)
+                        if bf.kind == BuiltinFunctionKind::Invalid =>
+                    {
+                        continue 'macros
+                    }
+                    StatementType::BuiltinFunctionCall(bf)
+                        if !matches!(
+                            bf.kind,
+                            BuiltinFunctionKind::Verbatim | BuiltinFunctionKind::DynConstructorArg
+                        ) =>
+                    {
+                        (0, 1)
+                    }
+                    StatementType::MacroInvocation(mi) => {
+                        match contract.macros.iter().find(|m| m.name == mi.macro_name) {
+                            Some(m) => (m.takes, m.returns),
+                            None => {
+                                unverified.push((macro_def.name.clone(), macro_def.span.clone()));
+                                continue 'macros
+                            }
+                        }
+                    }
+                    _ => {
+                        unverified.push((macro_def.name.clone(), macro_def.span.clone()));
+                        continue 'macros
+                    }
+                };
+                if height < inputs as isize {
+                    height -= inputs as isize;
+                    break
+                }
+                height += outputs as isize - inputs as isize;
+            }
+
+            if height != macro_def.returns as isize {
+                tracing::error!(
+                    target: "codegen",
+                    "MACRO \"{}\" DECLARES {} RETURNED STACK ITEMS BUT LEAVES {}",
+                    macro_def.name,
+                    macro_def.returns,
+                    height
+                );
+                return Err(CodegenError {
+                    kind: CodegenErrorKind::StackImbalance {
+                        macro_name: macro_def.name.clone(),
+                        expected: macro_def.returns,
+                        got: height,
+                    },
+                    span: macro_def.span.clone(),
+                    token: None,
+                    call_stack: vec![],
+                })
+            }
+        }
+
+        for (name, span) in unverified {
+            self.warn(CodegenWarning { kind: CodegenWarningKind::UnverifiedStack(name), span })?;
+        }
+
+        Ok(())
+    }
+
+    /// Generates main bytecode from a Contract AST
+    pub fn generate_main_bytecode(
+        evm_version: &EVMVersion,
+        contract: &Contract,
+        alternative_main: Option<String>,
+    ) -> Result<String, CodegenError> {
+        let bytecode_res = Codegen::main_bytecode_res(evm_version, contract, alternative_main)?;
+
+        tracing::debug!(target: "codegen", "Generated main bytecode. Appending table bytecode...");
+
+        // Generate the fully baked bytecode
+        Codegen::gen_table_bytecode(bytecode_res)
+    }
+
+    /// Generates main bytecode from a Contract AST along with the provenance of its instructions
+    ///
+    /// Each entry of the provenance holds the offset of an instruction in the runtime bytecode,
+    /// the name of the macro it was generated in and the span of the source it was generated
+    /// from. Table bytecode appended after the code isn't annotated.
+    pub fn generate_main_bytecode_with_stack_comments(
+        evm_version: &EVMVersion,
+        contract: &Contract,
+        alternative_main: Option<String>,
+    ) -> Result<(String, Provenance), CodegenError> {
+        let bytecode_res = Codegen::main_bytecode_res(evm_version, contract, alternative_main)?;
+        let provenance = Codegen::instruction_provenance(&bytecode_res)?;
+        Ok((Codegen::gen_table_bytecode(bytecode_res)?, provenance))
+    }
+
+    /// Generates a source map for the main bytecode of a Contract AST
+    ///
+    /// Maps the offset of every instruction in the runtime bytecode to the span of the source it
+    /// was generated from. Instructions of invoked macros map to their span in the invoked
+    /// macro's body rather than to the invocation. The source map is also stored on the Codegen
+    /// artifact.
+    pub fn generate_source_map(
+        &mut self,
+        evm_version: &EVMVersion,
+        contract: &Contract,
+        alternative_main: Option<String>,
+    ) -> Result<SourceMap, CodegenError> {
+        let bytecode_res = Codegen::main_bytecode_res(evm_version, contract, alternative_main)?;
+        let source_map = Codegen::instruction_provenance(&bytecode_res)?
+            .into_iter()
+            .map(|(offset, _, span)| (offset, span))
+            .collect::<SourceMap>();
+
+        if let Some(artifact) = &mut self.artifact {
+            artifact.source_map = Some(source_map.clone());
+        } else {
+            self.artifact =
+                Some(Artifact { source_map: Some(source_map.clone()), ..Default::default() });
+        }
+        Ok(source_map)
+    }
+
+    /// Generates a map of every macro in the main bytecode of a Contract AST to its starting
+    /// offset and byte length in the runtime bytecode
+    ///
+    /// A macro invoked more than once maps to its first instance. The range of an outlined
+    /// function excludes its leading JUMPDEST and trailing return jump, and the range of the main
+    /// macro excludes the functions and tables appended after it.
+    pub fn generate_macro_map(
+        evm_version: &EVMVersion,
+        contract: &Contract,
+        alternative_main: Option<String>,
+    ) -> Result<MacroMap, CodegenError> {
+        let bytecode_res = Codegen::main_bytecode_res(evm_version, contract, alternative_main)?;
+        Ok(bytecode_res
+            .macro_offsets
+            .iter()
+            .filter_map(|(name, start)| {
+                bytecode_res.macro_lengths.get(name).map(|len| (name.clone(), (*start, *len)))
+            })
+            .collect())
+    }
+
+    /// Annotates every instruction of a bytecode result with its enclosing macro and source span
+    fn instruction_provenance(bytecode_res: &BytecodeRes) -> Result<Provenance, CodegenError> {
+        // Later spans at the same offset belong to the code they were inlined into
+        let spans = bytecode_res
+            .source_spans
+            .iter()
+            .map(|(offset, name, span)| (*offset, (name, span)))
+            .collect::<BTreeMap<_, _>>();
+
+        let start = bytecode_res.bytes.first().map(|(o, _)| *o).unwrap_or_default();
+        let code = bytecode_res.bytes.iter().map(|(_, b)| b.0.as_str()).collect::<String>();
+        let bytes = Codegen::decode_placeholders(&code)?;
+        Ok(Codegen::split_code(&bytes)?
+            .into_iter()
+            .filter_map(|(pc, _, _)| {
+                spans
+                    .range(..=start + pc)
+                    .next_back()
+                    .map(|(_, (name, span))| (start + pc, name.to_string(), (*span).clone()))
+            })
+            .collect())
+    }
+
+    /// Generates the main macro's bytecode result, placed past any leading tables
+    fn main_bytecode_res(
+        evm_version: &EVMVersion,
+        contract: &Contract,
+        alternative_main: Option<String>,
+    ) -> Result<BytecodeRes, CodegenError> {
+        // If an alternative main is provided, then use it as the compilation target
+        let main_macro = alternative_main.unwrap_or_else(|| String::from("MAIN"));
+
+        // Find the main macro
+        let m_macro = Codegen::get_macro_by_name(&main_macro, contract)?;
+        Codegen::validate_entry_point(m_macro)?;
+
+        // For each MacroInvocation Statement, recurse into bytecode
+        let gen_at = |offset| {
+            Codegen::macro_to_bytecode(
+                evm_version,
+                m_macro,
+                contract,
+                &mut vec![m_macro],
+                offset,
+                &mut Vec::default(),
+                false,
+                None,
+            )
+        };
+        let mut bytecode_res: BytecodeRes = gen_at(0)?;
+
+        // If any utilized tables are placed before the code, regenerate the code past them
+        let leading_size = Codegen::leading_tables_size(&bytecode_res.utilized_tables)?;
+        if leading_size > 0 {
+            bytecode_res = gen_at(leading_size)?;
+        }
+
+        Ok(bytecode_res)
+    }
+
+    /// Generates constructor bytecode from a Contract AST
+    pub fn generate_constructor_bytecode(
+        evm_version: &EVMVersion,
+        contract: &Contract,
+        alternative_constructor: Option<String>,
+    ) -> Result<(String, bool), CodegenError> {
+        // If an alternative constructor macro is provided, then use it as the compilation target
+        let constructor_macro =
+            alternative_constructor.unwrap_or_else(|| String::from("CONSTRUCTOR"));
+
+        // Find the constructor macro
+        let c_macro = Codegen::get_macro_by_name(&constructor_macro, contract)?;
+        Codegen::validate_entry_point(c_macro)?;
+
+        // For each MacroInvocation Statement, recurse into bytecode
+        let gen_at = |offset| {
+            Codegen::macro_to_bytecode(
+                evm_version,
+                c_macro,
+                contract,
+                &mut vec![c_macro],
+                offset,
+                &mut Vec::default(),
+                false,
+                None,
+            )
+        };
+        let mut bytecode_res: BytecodeRes = gen_at(0)?;
+
+        // If any utilized tables are placed before the code, regenerate the code past them
+        let leading_size = Codegen::leading_tables_size(&bytecode_res.utilized_tables)?;
+        if leading_size > 0 {
+            bytecode_res = gen_at(leading_size)?;
+        }
+
+        // Check if the constructor performs its own code generation
+        let has_custom_bootstrap = bytecode_res.bytes.iter().any(|bytes| bytes.1 .0 == *"f3");
+
+        tracing::info!(target: "codegen", "Constructor is self-generating: {}", has_custom_bootstrap);
+
+        let bytecode = Codegen::gen_table_bytecode(bytecode_res)?;
+
+        Ok((bytecode, has_custom_bootstrap))
+    }
+
+    /// Returns the storage slots written to by a macro
+    ///
+    /// Walks the macro's generated opcode stream (including any macros it invokes) and records
+    /// the slot of every `SSTORE` that is immediately preceded by a push of a statically known
+    /// slot. This analysis is best-effort: slots computed at runtime are not captured.
+    ///
+    /// The slots are returned sorted and deduplicated.
+    pub fn written_slots(
+        evm_version: &EVMVersion,
+        macro_name: &str,
+        contract: &Contract,
+    ) -> Result<Vec<[u8; 32]>, CodegenError> {
+        let macro_def = Codegen::get_macro_by_name(macro_name, contract)?;
+        let bytecode_res: BytecodeRes = Codegen::macro_to_bytecode(
+            evm_version,
+            macro_def,
+            contract,
+            &mut vec![macro_def],
+            0,
+            &mut Vec::default(),
+            false,
+            None,
+        )?;
+        let bytecode = bytecode_res.bytes.iter().map(|(_, b)| b.0.as_str()).collect::<String>();
+
+        let bytes = Codegen::decode_placeholders(&bytecode)?;
+
+        let mut slots = BTreeSet::new();
+        let mut last_push: Option<[u8; 32]> = None;
+        for (pc, op, data) in Codegen::split_code(&bytes)? {
+            match Opcode::from_u8(op) {
+                Some(o) if o == Opcode::Push0 || o.is_value_push() => {
+                    // Placeholders can never be a known slot
+                    let placeholder = bytecode[2 * pc..2 * (pc + 1 + data.len())].contains('x');
+                    last_push = (!placeholder).then(|| {
+                        let mut slot = [0u8; 32];
+                        slot[32 - data.len()..].copy_from_slice(data);
+                        slot
+                    });
+                }
+                Some(Opcode::Sstore) => {
+                    if let Some(slot) = last_push.take() {
+                        tracing::debug!(target: "codegen", "FOUND SSTORE TO SLOT: {}", hex::encode(slot));
+                        slots.insert(slot);
+                    }
+                }
+                _ => last_push = None,
+            }
+        }
+
+        Ok(slots.into_iter().collect())
+    }
+
+    /// Applies the `include_tests` flag to a Contract AST
+    ///
+    /// Test macros may not be invoked during code generation. If `include_tests` is set, invoked
+    /// tests are compiled inline like regular macros. Otherwise, their invocations are removed so
+    /// that the tests are excluded from the production bytecode.
+    pub fn resolve_test_invocations(&self, contract: &mut Contract) {
+        if self.include_tests {
+            contract.macros.iter_mut().for_each(|m| m.test = false);
+            return
+        }
+
+        let tests = contract
+            .macros
+            .iter()
+            .filter(|m| m.test)
+            .map(|m| m.name.clone())
+            .collect::<BTreeSet<_>>();
+        for macro_def in contract.macros.iter_mut() {
+            remove_invocations(&mut macro_def.statements, &tests);
+        }
+    }
+
+    /// Applies the `trim_push_literals` flag to a Contract AST
+    ///
+    /// Explicit pushes like `push3 0x0000ff` keep their width by default, since authors may rely
+    /// on exact byte widths. If `trim_push_literals` is set, the explicit push opcode is dropped so
+    /// the literal is pushed with its leading zero bytes stripped, i.e. as `PUSH1 0xff`.
+    pub fn resolve_push_literals(&self, contract: &mut Contract) {
+        if !self.trim_push_literals {
+            return
+        }
+
+        for macro_def in contract.macros.iter_mut() {
+            remove_push_overrides(&mut macro_def.statements);
+        }
+    }
+
+    /// Checks that no `view` decorated macro changes state
+    ///
+    /// Walks the opcodes emitted for each view macro (including any macros it invokes) and rejects
+    /// any [state changing](Opcode::is_state_changing) opcode. A `CALL` or `CALLCODE` is only
+    /// allowed if its value argument is pushed as a literal zero directly before the address and
+    /// gas arguments. This heuristic is conservative, so calls with a computed value are rejected.
+    pub fn check_view_macros(
+        evm_version: &EVMVersion,
+        contract: &Contract,
+    ) -> Result<(), CodegenError> {
+        for macro_def in contract.macros.iter().filter(|m| m.is_view()) {
+            let bytecode_res: BytecodeRes = Codegen::macro_to_bytecode(
+                evm_version,
+                macro_def,
+                contract,
+                &mut vec![macro_def],
+                0,
+                &mut Vec::default(),
+                false,
+                None,
+            )?;
+            let bytecode = bytecode_res.bytes.iter().map(|(_, b)| b.0.as_str()).collect::<String>();
+
+            // Split the bytecode into opcodes and their push data
+            let bytes = Codegen::decode_placeholders(&bytecode)?;
+            let instructions = Codegen::split_code(&bytes)?;
+
+            for (i, (_, op, _)) in instructions.iter().enumerate() {
+                let opcode = match Opcode::from_u8(*op) {
+                    Some(o) => o,
+                    None => continue,
+                };
+                let value_call = matches!(opcode, Opcode::Call | Opcode::Callcode) &&
+                    !i.checked_sub(3).is_some_and(|v| {
+                        let (pc, value_op, value_data) = instructions[v];
+                        let placeholder =
+                            bytecode[2 * pc..2 * (pc + 1 + value_data.len())].contains('x');
+                        Opcode::from_u8(value_op).is_some_and(|o| o.is_push()) &&
+                            !placeholder &&
+                            value_data.iter().all(|b| *b == 0)
+                    });
+                if opcode.is_state_changing() || value_call {
+                    tracing::error!(
+                        target: "codegen",
+                        "VIEW MACRO \"{}\" CHANGES STATE WITH OPCODE: {:?}",
+                        macro_def.name,
+                        opcode
+                    );
+                    return Err(CodegenError {
+                        kind: CodegenErrorKind::StateChangeInView(macro_def.name.clone(), opcode),
+                        span: macro_def.span.clone(),
+                        token: None,
+                        call_stack: vec![],
+                    })
+                }
+            }
+        }
+
+        Ok(())
     }
 
-    /// Generates main bytecode from a Contract AST
-    pub fn generate_main_bytecode(
-        evm_version: &EVMVersion,
-        contract: &Contract,
-        alternative_main: Option<String>,
-    ) -> Result<String, CodegenError> {
-        // If an alternative main is provided, then use it as the compilation target
-        let main_macro = alternative_main.unwrap_or_else(|| String::from("MAIN"));
+    /// Counts the references to every label defined in a Contract AST
+    ///
+    /// A reference is any jump to the label from a macro body, the label passed as a macro
+    /// argument, or an entry in a jump table. References are counted per definition, so a label
+    /// referenced from a macro invoked several times is only counted once per reference site.
+    /// Labels that are never referenced are reported with a count of zero.
+    pub fn label_reference_counts(contract: &Contract) -> HashMap<String, usize> {
+        let mut counts = HashMap::new();
+        for macro_def in contract.macros.iter() {
+            visit_statements(&macro_def.statements, &mut |s| {
+                if let StatementType::Label(label) = &s.ty {
+                    counts.insert(label.name.clone(), 0);
+                }
+            });
+        }
 
-        // Find the main macro
-        let m_macro = Codegen::get_macro_by_name(&main_macro, contract)?;
+        let mut reference = |name: &String| {
+            if let Some(count) = counts.get_mut(name) {
+                *count += 1;
+            }
+        };
+        for macro_def in contract.macros.iter() {
+            visit_statements(&macro_def.statements, &mut |s| match &s.ty {
+                StatementType::LabelCall(name) => reference(name),
+                StatementType::MacroInvocation(mi) => {
+                    mi.args.iter().for_each(|arg| {
+                        if let MacroArg::Ident(name) = arg {
+                            reference(name);
+                        }
+                    });
+                }
+                _ => {}
+            });
+        }
+        for table in contract.tables.iter() {
+            table.statements.iter().for_each(|s| {
+                if let StatementType::LabelCall(name) = &s.ty {
+                    reference(name);
+                }
+            });
+        }
 
-        // For each MacroInvocation Statement, recurse into bytecode
-        let bytecode_res: BytecodeRes = Codegen::macro_to_bytecode(
-            evm_version,
-            m_macro,
-            contract,
-            &mut vec![m_macro],
-            0,
-            &mut Vec::default(),
-            false,
-            None,
-        )?;
+        counts
+    }
 
-        tracing::debug!(target: "codegen", "Generated main bytecode. Appending table bytecode...");
+    /// Lists the macros defined in a Contract AST that are never reached from `MAIN` or
+    /// `CONSTRUCTOR`
+    ///
+    /// A macro is reached when it is invoked, or passed to `__codesize`, from a reachable macro.
+    /// Test macros are run on their own and are never reported. Macros are returned in
+    /// definition order.
+    pub fn unreachable_macros(contract: &Contract) -> Vec<String> {
+        let mut reached: BTreeSet<&str> = BTreeSet::new();
+        let mut pending: Vec<&str> = vec!["MAIN", "CONSTRUCTOR"];
+        while let Some(name) = pending.pop() {
+            let Some(macro_def) = contract.find_macro_by_name(name) else { continue };
+            if !reached.insert(macro_def.name.as_str()) {
+                continue
+            }
+            visit_statements(&macro_def.statements, &mut |s| match &s.ty {
+                StatementType::MacroInvocation(mi) => {
+                    pending.push(&mi.macro_name);
+                    mi.args.iter().for_each(|arg| {
+                        if let MacroArg::Builtin(b) = arg {
+                            pending.extend(codesize_target(b));
+                        }
+                    });
+                }
+                StatementType::BuiltinFunctionCall(b) => pending.extend(codesize_target(b)),
+                _ => {}
+            });
+        }
 
-        // Generate the fully baked bytecode
-        Codegen::gen_table_bytecode(bytecode_res)
+        contract
+            .macros
+            .iter()
+            .filter(|m| !m.test && !reached.contains(m.name.as_str()))
+            .map(|m| m.name.clone())
+            .collect()
     }
 
-    /// Generates constructor bytecode from a Contract AST
-    pub fn generate_constructor_bytecode(
+    /// Checks that every name referenced in a Contract AST resolves, without generating bytecode
+    ///
+    /// Unlike code generation, which stops at the first error, every error found is returned:
+    /// duplicate definitions, invocations of and `__codesize` calls on missing macros, table
+    /// builtins on missing tables, references to missing constants, and jumps to labels that are
+    /// never defined. Every macro is checked,
+    /// including those that are never invoked. An empty result means the references resolve,
+    /// not that code generation succeeds.
+    pub fn validate(contract: &Contract) -> Vec<CodegenError> {
+        let mut errors = contract.duplicate_definitions();
+
+        let mut labels = BTreeSet::new();
+        for macro_def in contract.macros.iter() {
+            visit_statements(&macro_def.statements, &mut |s| {
+                if let StatementType::Label(label) = &s.ty {
+                    labels.insert(label.name.as_str());
+                }
+            });
+        }
+        let constants = match contract.constants.lock() {
+            Ok(constants) => constants.iter().map(|c| c.name.clone()).collect::<BTreeSet<_>>(),
+            Err(_) => {
+                errors.push(CodegenError::new(
+                    CodegenErrorKind::LockingError,
+                    AstSpan(vec![]),
+                    None,
+                ));
+                return errors
+            }
+        };
+
+        let error = |kind: CodegenErrorKind, span: &AstSpan| CodegenError {
+            kind,
+            span: span.clone(),
+            token: None,
+            call_stack: vec![],
+        };
+        let missing_target = |b: &BuiltinFunctionCall| {
+            let missing_macro = codesize_target(b)
+                .filter(|name| contract.find_macro_by_name(name).is_none())
+                .map(|name| CodegenErrorKind::MissingMacroDefinition(name.to_string()));
+            let missing_table = table_target(b)
+                .filter(|name| contract.find_table_by_name(name).is_none())
+                .map(|name| CodegenErrorKind::MissingTableDefinition(name.to_string()));
+            missing_macro.or(missing_table).map(|kind| error(kind, &b.span))
+        };
+        for macro_def in contract.macros.iter() {
+            visit_statements(&macro_def.statements, &mut |s| match &s.ty {
+                StatementType::MacroInvocation(mi) => {
+                    if contract.find_macro_by_name(&mi.macro_name).is_none() {
+                        errors.push(error(
+                            CodegenErrorKind::InvalidMacroInvocation(mi.macro_name.clone()),
+                            &mi.span,
+                        ));
+                    }
+                    mi.args.iter().for_each(|arg| {
+                        if let MacroArg::Builtin(b) = arg {
+                            errors.extend(missing_target(b));
+                        }
+                    });
+                }
+                StatementType::BuiltinFunctionCall(b) => errors.extend(missing_target(b)),
+                StatementType::Constant(name) if !constants.contains(name) => errors.push(error(
+                    CodegenErrorKind::MissingConstantDefinition(name.clone()),
+                    &s.span,
+                )),
+                StatementType::LabelCall(name) if !labels.contains(name.as_str()) => {
+                    errors.push(error(CodegenErrorKind::UnmatchedJumpLabel, &s.span))
+                }
+                _ => {}
+            });
+        }
+        for table in contract.tables.iter() {
+            table.statements.iter().for_each(|s| {
+                if let StatementType::LabelCall(name) = &s.ty {
+                    if !labels.contains(name.as_str()) {
+                        errors.push(error(CodegenErrorKind::UnmatchedJumpLabel, &s.span));
+                    }
+                }
+            });
+        }
+
+        errors
+    }
+
+    /// Generates a selector dispatcher for every function in a Contract AST
+    ///
+    /// The dispatcher is returned as Huff source that loads the selector from calldata and, for
+    /// each function, compares it against the function's selector and jumps to a handler label
+    /// named after the function. Execution falls through past the dispatcher if no selector
+    /// matches, so the caller can append their own fallback logic.
+    ///
+    /// Handler labels must be defined in the `MAIN` macro or a macro it invokes. Overloaded
+    /// functions are rejected, as they would share a handler label.
+    pub fn generate_dispatcher(contract: &Contract) -> Result<String, CodegenError> {
+        // Collect the labels defined in the main macro and every macro reachable from it
+        let mut labels = BTreeSet::new();
+        let mut visited = BTreeSet::new();
+        let mut pending = vec![Codegen::get_macro_by_name("MAIN", contract)?];
+        while let Some(macro_def) = pending.pop() {
+            if !visited.insert(macro_def.name.as_str()) {
+                continue
+            }
+            visit_statements(&macro_def.statements, &mut |s| match &s.ty {
+                StatementType::Label(label) => {
+                    labels.insert(label.name.as_str());
+                }
+                StatementType::MacroInvocation(mi) => {
+                    pending.extend(contract.macros.iter().find(|m| m.name == mi.macro_name));
+                }
+                _ => {}
+            });
+        }
+
+        let mut names = BTreeSet::new();
+        let mut dispatcher = vec![String::from("0x00 calldataload 0xe0 shr")];
+        for function in contract.functions.iter().filter(|f| f.name != "CONSTRUCTOR") {
+            let error = |kind| CodegenError {
+                kind,
+                span: function.span.clone(),
+                token: None,
+                call_stack: vec![],
+            };
+            if !names.insert(function.name.as_str()) {
+                tracing::error!(
+                    target: "codegen",
+                    "OVERLOADED FUNCTION \"{}\" SHARES A DISPATCH HANDLER LABEL",
+                    function.name
+                );
+                return Err(error(CodegenErrorKind::OverloadedDispatchFunction(
+                    function.name.clone(),
+                )))
+            }
+            if !labels.contains(function.name.as_str()) {
+                tracing::error!(
+                    target: "codegen",
+                    "MISSING DISPATCH HANDLER LABEL FOR FUNCTION \"{}\"",
+                    function.name
+                );
+                return Err(error(CodegenErrorKind::MissingDispatchHandler(function.name.clone())))
+            }
+            dispatcher.push(format!(
+                "dup1 0x{} eq {} jumpi",
+                hex::encode(function.signature),
+                function.name
+            ));
+        }
+        Ok(dispatcher.join("\n"))
+    }
+
+    /// Generates a machine-readable compile report for a Contract AST
+    ///
+    /// Runs the full code generation pipeline once and bundles the runtime and init code sizes,
+    /// the tables and labels defined in the contract, any unused macros and constants, and the
+    /// resulting warnings into a single JSON value.
+    pub fn report(
         evm_version: &EVMVersion,
         contract: &Contract,
-        alternative_constructor: Option<String>,
-    ) -> Result<(String, bool), CodegenError> {
-        // If an alternative constructor macro is provided, then use it as the compilation target
-        let constructor_macro =
-            alternative_constructor.unwrap_or_else(|| String::from("CONSTRUCTOR"));
+    ) -> Result<serde_json::Value, CodegenError> {
+        let main_bytecode = Codegen::generate_main_bytecode(evm_version, contract, None)?;
+        let (constructor_bytecode, has_custom_bootstrap) =
+            match Codegen::generate_constructor_bytecode(evm_version, contract, None) {
+                Ok(res) => res,
+                Err(e)
+                    if e.kind ==
+                        CodegenErrorKind::MissingMacroDefinition("CONSTRUCTOR".to_string()) =>
+                {
+                    (String::default(), false)
+                }
+                Err(e) => return Err(e),
+            };
+        let artifact = Codegen::new().churn(
+            Arc::new(FileSource::default()),
+            vec![],
+            &main_bytecode,
+            &constructor_bytecode,
+            has_custom_bootstrap,
+        )?;
+        let runtime_size = artifact.runtime.len() / 2;
+        let init_size = artifact.bytecode.len() / 2;
+
+        let tables = contract
+            .tables
+            .iter()
+            .map(|t| {
+                serde_json::json!({
+                    "name": t.name,
+                    "kind": format!("{:?}", t.kind),
+                    "size": bytes_util::hex_to_usize(&bytes_util::bytes32_to_string(&t.size, false))
+                        .unwrap_or_default(),
+                })
+            })
+            .collect::<Vec<serde_json::Value>>();
 
-        // Find the constructor macro
-        let c_macro = Codegen::get_macro_by_name(&constructor_macro, contract)?;
+        let mut label_count = 0;
+        for macro_def in contract.macros.iter() {
+            visit_statements(&macro_def.statements, &mut |s| {
+                if let StatementType::Label(_) = s.ty {
+                    label_count += 1;
+                }
+            });
+        }
 
-        // For each MacroInvocation Statement, recurse into bytecode
-        let bytecode_res: BytecodeRes = Codegen::macro_to_bytecode(
-            evm_version,
-            c_macro,
-            contract,
-            &mut vec![c_macro],
-            0,
-            &mut Vec::default(),
-            false,
-            None,
-        )?;
+        let mut cg = Codegen::new();
+        if runtime_size > MAX_CONTRACT_SIZE {
+            cg.warn(CodegenWarning {
+                kind: CodegenWarningKind::ContractSizeExceeded(runtime_size, MAX_CONTRACT_SIZE),
+                span: AstSpan(vec![Span { start: 0, end: 0, file: None }]),
+            })?;
+        }
+        cg.lint(contract)?;
+
+        let unused_macros = cg
+            .warnings
+            .iter()
+            .filter_map(|w| match &w.kind {
+                CodegenWarningKind::UnusedMacro(name) => Some(name.clone()),
+                _ => None,
+            })
+            .collect::<Vec<String>>();
+        let unused_constants = cg
+            .warnings
+            .iter()
+            .filter_map(|w| match &w.kind {
+                CodegenWarningKind::UnusedConstant(name) => Some(name.clone()),
+                _ => None,
+            })
+            .collect::<Vec<String>>();
+        let warnings = cg.warnings.iter().map(|w| w.kind.to_string()).collect::<Vec<String>>();
+
+        Ok(serde_json::json!({
+            "runtime_size": runtime_size,
+            "init_size": init_size,
+            "table_count": tables.len(),
+            "tables": tables,
+            "label_count": label_count,
+            "unused_macros": unused_macros,
+            "unused_constants": unused_constants,
+            "warnings": warnings,
+        }))
+    }
 
-        // Check if the constructor performs its own code generation
-        let has_custom_bootstrap = bytecode_res.bytes.iter().any(|bytes| bytes.1 .0 == *"f3");
+    /// Helper function to check that a macro compiled as an entry point takes no parameters,
+    /// since nothing can invoke it with arguments
+    pub(crate) fn validate_entry_point(macro_def: &MacroDefinition) -> Result<(), CodegenError> {
+        if macro_def.parameters.is_empty() {
+            return Ok(())
+        }
+        tracing::error!(target: "codegen", "ENTRY POINT \"{}\" TAKES PARAMETERS!", macro_def.name);
+        Err(CodegenError {
+            kind: CodegenErrorKind::ParameterizedEntryPoint(macro_def.name.clone()),
+            span: macro_def.span.clone(),
+            token: None,
+            call_stack: vec![],
+        })
+    }
 
-        tracing::info!(target: "codegen", "Constructor is self-generating: {}", has_custom_bootstrap);
+    /// Checks that the main and constructor macros declare `takes(0) returns(0)`
+    ///
+    /// Nothing is on the stack when an entry point starts executing and anything it leaves is
+    /// discarded, so any other signature is most likely a mistake. Skipped if
+    /// `lenient_entry_points` is set. Missing entry points are left to bytecode generation to
+    /// report.
+    pub fn check_entry_signatures(
+        &self,
+        contract: &Contract,
+        alternative_main: Option<&str>,
+        alternative_constructor: Option<&str>,
+    ) -> Result<(), CodegenError> {
+        if self.lenient_entry_points {
+            return Ok(())
+        }
 
-        let bytecode = Codegen::gen_table_bytecode(bytecode_res)?;
+        let entry_points =
+            [alternative_main.unwrap_or("MAIN"), alternative_constructor.unwrap_or("CONSTRUCTOR")];
+        for macro_def in contract.macros.iter().filter(|m| entry_points.contains(&m.name.as_str()))
+        {
+            if macro_def.takes == 0 && macro_def.returns == 0 {
+                continue
+            }
+            tracing::error!(
+                target: "codegen",
+                "ENTRY POINT \"{}\" DECLARES TAKES({}) RETURNS({})",
+                macro_def.name,
+                macro_def.takes,
+                macro_def.returns
+            );
+            return Err(CodegenError {
+                kind: CodegenErrorKind::InvalidEntryMacroSignature {
+                    macro_name: macro_def.name.clone(),
+                    takes: macro_def.takes,
+                    returns: macro_def.returns,
+                },
+                span: macro_def.span.clone(),
+                token: None,
+                call_stack: vec![],
+            })
+        }
 
-        Ok((bytecode, has_custom_bootstrap))
+        Ok(())
     }
 
     /// Helper function to find a macro or generate a CodegenError
@@ -132,11 +1482,13 @@ impl Codegen {
                 kind: CodegenErrorKind::MissingMacroDefinition(name.to_string()),
                 span: AstSpan(vec![Span { start: 0, end: 0, file: None }]),
                 token: None,
+                call_stack: vec![],
             })
         }
     }
 
-    /// Appends table bytecode to the end of the BytecodeRes output.
+    /// Lays out table bytecode around the BytecodeRes output, either before or after the code
+    /// depending on each table's placement.
     /// Fills table JUMPDEST placeholders.
     pub fn gen_table_bytecode(res: BytecodeRes) -> Result<String, CodegenError> {
         if !res.unmatched_jumps.is_empty() {
@@ -154,87 +1506,49 @@ impl Codegen {
                         .collect::<Vec<Span>>(),
                 ),
                 token: None,
+                call_stack: vec![],
             });
         }
 
         tracing::info!(target: "codegen", "GENERATING JUMPTABLE BYTECODE");
 
-        let mut bytecode = res.bytes.into_iter().map(|(_, b)| b.0).collect::<String>();
+        let mut bytecode = String::new();
         let mut table_offsets: HashMap<String, usize> = HashMap::new(); // table name -> bytecode offset
-        let mut table_offset = bytecode.len() / 2;
-
-        res.utilized_tables.iter().try_for_each(|jt| {
+        let mut table_offset = 0;
+        // emitted table code -> bytecode offset, so byte-identical tables share a single copy
+        let mut emitted_tables: HashMap<String, usize> = HashMap::new();
+
+        // Tables placed before the code are laid out first, so the code must have been generated
+        // at an offset past them (see `leading_tables_size`)
+        let (leading, trailing): (Vec<&TableDefinition>, Vec<&TableDefinition>) =
+            res.utilized_tables.iter().partition(|jt| jt.placement == TablePlacement::Before);
+        for jt in leading {
+            let table_code = Codegen::gen_table(jt, &res.label_indices)?;
             table_offsets.insert(jt.name.to_string(), table_offset);
-            let size = match bytes_util::hex_to_usize(bytes_util::bytes32_to_string(&jt.size, false).as_str()) {
-                Ok(s) => s,
-                Err(e) => {
-                    tracing::error!(target: "codegen", "Errored converting bytes32 to str. Bytes {:?} with error: {:?}", jt.size, e);
-                    return Err(CodegenError {
-                        kind: CodegenErrorKind::UsizeConversion(format!("{:?}", jt.size)),
-                        span: jt.span.clone(),
-                        token: None
-                    })
-                }
-            };
-            table_offset += size;
-
-            tracing::info!(target: "codegen", "GENERATING BYTECODE FOR TABLE: \"{}\"", jt.name);
-
-            let mut table_code = String::new();
-            jt
-                .statements
-                .iter()
-                .try_for_each(|s| {
-                    match &s.ty {
-                        StatementType::LabelCall(label) => {
-                            let offset = match res.label_indices.get(label) {
-                                Some(l) => l,
-                                None => {
-                                    tracing::error!(
-                                    target: "codegen",
-                                    "Definition not found for Jump Table Label: \"{}\"",
-                                    label
-                                );
-                                    return Err(CodegenError {
-                                        kind: CodegenErrorKind::UnmatchedJumpLabel,
-                                        span: s.span.clone(),
-                                        token: None,
-                                    });
-                                }
-                            };
-                            let hex = format_even_bytes(format!("{offset:02x}"));
-
-                            table_code = format!("{table_code}{}", pad_n_bytes(
-                                hex.as_str(),
-                                if matches!(jt.kind, TableKind::JumpTablePacked) { 0x02 } else { 0x20 },
-                            ));
-                        }
-                        StatementType::Code(code) => {
-                            // Check if code length is even
-                            if code.len() % 2 != 0 {
-                                return Err(CodegenError {
-                                    kind: CodegenErrorKind::InvalidCodeLength(code.len()),
-                                    span: s.span.clone(),
-                                    token: None,
-                                });
-                            }
+            emitted_tables.entry(table_code.clone()).or_insert(table_offset);
+            table_offset += Codegen::table_size(jt)?;
+            bytecode = format!("{bytecode}{table_code}");
+        }
 
-                            table_code = format!("{table_code}{code}");
-                        }
-                        _ => {
-                            return Err(CodegenError {
-                                kind: CodegenErrorKind::InvalidMacroStatement,
-                                span: jt.span.clone(),
-                                token: None
-                            })
-                        }
-                    }
-                    Ok(())
-                })?;
-            tracing::info!(target: "codegen", "SUCCESSFULLY GENERATED BYTECODE FOR TABLE: \"{}\"", jt.name);
+        let code = res.bytes.into_iter().map(|(_, b)| b.0).collect::<String>();
+        let code_range = table_offset..table_offset + code.len() / 2;
+        table_offset += code.len() / 2;
+        bytecode = format!("{bytecode}{code}");
+
+        // Leading tables can't be deduplicated since the code was already generated past them, but
+        // trailing tables that match any previously emitted table reuse its offset
+        for jt in trailing {
+            let table_code = Codegen::gen_table(jt, &res.label_indices)?;
+            if let Some(o) = emitted_tables.get(&table_code) {
+                tracing::info!(target: "codegen", "REUSING IDENTICAL TABLE BYTECODE FOR \"{}\"", jt.name);
+                table_offsets.insert(jt.name.to_string(), *o);
+                continue
+            }
+            table_offsets.insert(jt.name.to_string(), table_offset);
+            emitted_tables.insert(table_code.clone(), table_offset);
+            table_offset += Codegen::table_size(jt)?;
             bytecode = format!("{bytecode}{table_code}");
-            Ok(())
-        })?;
+        }
 
         res.table_instances.iter().for_each(|jump| {
             if let Some(o) = table_offsets.get(&jump.label) {
@@ -253,9 +1567,215 @@ impl Codegen {
             }
         });
 
+        for instance in res.codeoffset_instances.iter() {
+            let o = match res.macro_offsets.get(&instance.label) {
+                Some(o) => o,
+                None => {
+                    tracing::error!(
+                        target: "codegen",
+                        "Macro \"{}\" passed to __codeoffset is not in the bytecode",
+                        instance.label
+                    );
+                    return Err(CodegenError {
+                        kind: CodegenErrorKind::MissingCodeOffset(instance.label.clone()),
+                        span: instance.span.clone(),
+                        token: None,
+                        call_stack: vec![],
+                    });
+                }
+            };
+            // The offset is pushed with a PUSH2
+            if *o > 0xffff {
+                tracing::error!(
+                    target: "codegen",
+                    "Offset {:#x} of macro \"{}\" passed to __codeoffset doesn't fit in 2 bytes",
+                    o,
+                    instance.label
+                );
+                return Err(CodegenError {
+                    kind: CodegenErrorKind::CodeOffsetOverflow(instance.label.clone(), *o),
+                    span: instance.span.clone(),
+                    token: None,
+                    call_stack: vec![],
+                })
+            }
+            let before = &bytecode[0..instance.bytecode_index * 2 + 2];
+            let after = &bytecode[instance.bytecode_index * 2 + 6..];
+
+            bytecode = format!("{before}{o:04x}{after}");
+            tracing::info!(target: "codegen", "FILLED CODE OFFSET FOR MACRO \"{}\"", instance.label);
+        }
+
+        // Every label entry of the emitted jump tables is a jump target
+        let table_entries = res
+            .utilized_tables
+            .iter()
+            .filter_map(|jt| {
+                let size = match jt.kind {
+                    TableKind::JumpTablePacked => 0x02,
+                    TableKind::JumpTable => 0x20,
+                    TableKind::CodeTable => return None,
+                };
+                let start = *table_offsets.get(&jt.name)?;
+                Some((0..jt.statements.len()).map(move |i| (start + i * size, size)))
+            })
+            .flatten()
+            .collect::<Vec<(usize, usize)>>();
+        Codegen::check_jump_destinations(&bytecode, code_range, &table_entries)?;
+
         Ok(bytecode)
     }
 
+    /// Checks that every jump target known at compile time is a `JUMPDEST` in the code
+    ///
+    /// The targets are the literals pushed right before a `JUMP` or `JUMPI`, and the entries of
+    /// jump tables, given as their offset and size in bytes. Only `JUMPDEST`s at an instruction
+    /// boundary within `code` count, so a target inside push data or a table is rejected.
+    pub fn check_jump_destinations(
+        bytecode: &str,
+        code: Range<usize>,
+        table_entries: &[(usize, usize)],
+    ) -> Result<(), CodegenError> {
+        // `__CODECOPY_DYN_ARG` placeholders are only filled in by `churn` with whole instructions,
+        // so they can be read as `STOP`s without shifting the instruction boundaries
+        let bytes = Codegen::decode_placeholders(bytecode)?;
+        let invalid = |offset: usize| {
+            tracing::error!(target: "codegen", "JUMP TARGET 0x{:02x} IS NOT A JUMPDEST", offset);
+            CodegenError {
+                kind: CodegenErrorKind::InvalidJumpDestination { offset },
+                span: AstSpan(vec![]),
+                token: None,
+                call_stack: vec![],
+            }
+        };
+        // Values too large for a usize saturate, since they can't be a jumpdest anyway
+        let value = |data: &[u8]| {
+            data.iter().fold(0usize, |acc, b| {
+                acc.checked_mul(0x100).map_or(usize::MAX, |v| v | *b as usize)
+            })
+        };
+
+        // Walk the code one instruction at a time, collecting the jumpdests and pushed targets
+        let mut jumpdests = BTreeSet::new();
+        let mut targets = vec![];
+        let mut pushed = None;
+        let code = code.start.min(bytes.len())..code.end.min(bytes.len());
+        for (pc, op, data) in Codegen::split_code(&bytes[code.clone()])? {
+            let opcode = Opcode::from_u8(op);
+            match opcode {
+                Some(Opcode::Jumpdest) => {
+                    jumpdests.insert(code.start + pc);
+                }
+                Some(Opcode::Jump | Opcode::Jumpi) => targets.extend(pushed),
+                _ => {}
+            }
+            pushed =
+                opcode.filter(|o| *o == Opcode::Push0 || o.is_value_push()).map(|_| value(data));
+        }
+
+        let table_targets = table_entries
+            .iter()
+            .map(|(offset, size)| bytes.get(*offset..offset + size).map_or(usize::MAX, value));
+        match targets.into_iter().chain(table_targets).find(|t| !jumpdests.contains(t)) {
+            Some(t) => Err(invalid(t)),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the combined size of the tables that are placed before the code
+    ///
+    /// Code using these tables must be generated starting at this offset so that its label
+    /// offsets and jump destinations account for the leading tables.
+    pub fn leading_tables_size(tables: &[TableDefinition]) -> Result<usize, CodegenError> {
+        tables
+            .iter()
+            .filter(|jt| jt.placement == TablePlacement::Before)
+            .try_fold(0, |acc, jt| Ok(acc + Codegen::table_size(jt)?))
+    }
+
+    /// Helper associated function to convert a table's size to a usize
+    fn table_size(jt: &TableDefinition) -> Result<usize, CodegenError> {
+        bytes_util::hex_to_usize(bytes_util::bytes32_to_string(&jt.size, false).as_str()).map_err(|e| {
+            tracing::error!(target: "codegen", "Errored converting bytes32 to str. Bytes {:?} with error: {:?}", jt.size, e);
+            CodegenError {
+                kind: CodegenErrorKind::InvalidTableSize(jt.name.clone()),
+                span: jt.span.clone(),
+                token: None,
+                call_stack: vec![],
+            }
+        })
+    }
+
+    /// Helper associated function to generate the bytecode of a single table
+    fn gen_table(
+        jt: &TableDefinition,
+        label_indices: &LabelIndices,
+    ) -> Result<String, CodegenError> {
+        tracing::info!(target: "codegen", "GENERATING BYTECODE FOR TABLE: \"{}\"", jt.name);
+
+        let mut table_code = String::new();
+        jt.statements.iter().try_for_each(|s| {
+            match &s.ty {
+                StatementType::LabelCall(label) => {
+                    let offset = match label_indices.get(label) {
+                        Some(l) => l,
+                        None => {
+                            tracing::error!(
+                                target: "codegen",
+                                "Definition not found for Jump Table Label: \"{}\"",
+                                label
+                            );
+                            return Err(CodegenError {
+                                kind: CodegenErrorKind::UnmatchedJumpLabel,
+                                span: s.span.clone(),
+                                token: None,
+                                call_stack: vec![],
+                            });
+                        }
+                    };
+                    let hex = format_even_bytes(format!("{offset:02x}"));
+
+                    table_code = format!(
+                        "{table_code}{}",
+                        pad_n_bytes(
+                            hex.as_str(),
+                            if matches!(jt.kind, TableKind::JumpTablePacked) { 0x02 } else { 0x20 },
+                        )
+                    );
+                }
+                StatementType::Code(code) => {
+                    // Check if code length is even
+                    if code.len() % 2 != 0 {
+                        return Err(CodegenError {
+                            kind: CodegenErrorKind::InvalidCodeLength(code.len()),
+                            span: s.span.clone(),
+                            token: None,
+                            call_stack: vec![],
+                        });
+                    }
+
+                    table_code = format!("{table_code}{code}");
+                }
+                _ => {
+                    return Err(CodegenError {
+                        kind: CodegenErrorKind::InvalidMacroStatement,
+                        span: jt.span.clone(),
+                        token: None,
+                        call_stack: vec![],
+                    })
+                }
+            }
+            Ok(())
+        })?;
+        tracing::info!(target: "codegen", "SUCCESSFULLY GENERATED BYTECODE FOR TABLE: \"{}\"", jt.name);
+        events::emit(serde_json::json!({
+            "event": "table_generated",
+            "table": jt.name,
+            "size": table_code.len() / 2,
+        }));
+        Ok(table_code)
+    }
+
     /// Recurses a MacroDefinition to generate Bytecode
     ///
     /// ## Overview
@@ -279,79 +1799,104 @@ impl Codegen {
     #[allow(clippy::too_many_arguments)]
     pub fn macro_to_bytecode<'a>(
         evm_version: &EVMVersion,
-        macro_def: &'a MacroDefinition,
+        macro_def: &'a MacroDefinition,
+        contract: &'a Contract,
+        scope: &mut Vec<&'a MacroDefinition>,
+        offset: usize,
+        mis: &mut Vec<(usize, MacroInvocation)>,
+        recursing_constructor: bool,
+        circular_codesize_invocations: Option<&mut CircularCodeSizeIndices>,
+    ) -> Result<BytecodeRes, CodegenError> {
+        let mut ccsi = CircularCodeSizeIndices::new();
+        let circular_codesize_invocations = circular_codesize_invocations.unwrap_or(&mut ccsi);
+
+        // Macros that must be generated before the current one can continue are pushed as new
+        // frames instead of being recursed into, so nesting depth is bounded by the heap
+        let mut frames = vec![MacroFrame::new(
+            evm_version,
+            macro_def,
+            macro_def.span.clone(),
+            offset,
+            recursing_constructor,
+            FrameKind::Root,
+        )?];
+
+        while let Some(frame) = frames.last_mut() {
+            // Loop through all intermediate bytecode representations generated from the AST
+            let res = match frame.ir_bytes.next() {
+                Some(ir_byte) => match Codegen::ir_byte_to_bytecode(
+                    evm_version,
+                    ir_byte,
+                    frame,
+                    contract,
+                    scope,
+                    mis,
+                    circular_codesize_invocations,
+                ) {
+                    Ok(child) => {
+                        frames.extend(child);
+                        continue
+                    }
+                    Err(e) => Err(e),
+                },
+                None => Codegen::finish_frame(
+                    evm_version,
+                    frame,
+                    contract,
+                    scope,
+                    mis,
+                    circular_codesize_invocations,
+                ),
+            };
+
+            let res = match res {
+                Ok(res) => res,
+                Err(e) => {
+                    if let [_, .., frame] = frames.as_slice() {
+                        tracing::error!(
+                            target: "codegen",
+                            "FAILED TO RECURSE INTO MACRO \"{}\"",
+                            frame.macro_def.name
+                        );
+                    }
+                    return Err(e.within(MacroFrame::call_stack(&frames)))
+                }
+            };
+
+            let finished = frames.pop().map(|frame| frame.kind);
+            let Some(parent) = frames.last_mut() else { return Ok(res) };
+            match finished {
+                Some(FrameKind::Codesize {
+                    scope: parent_scope,
+                    mis: parent_mis,
+                    circular_codesize_invocations: parent_ccsi,
+                }) => {
+                    *scope = parent_scope;
+                    *mis = parent_mis;
+                    *circular_codesize_invocations = parent_ccsi;
+                    parent.push_codesize(&res);
+                }
+                _ => parent.extend(res),
+            }
+        }
+
+        unreachable!("the root frame always returns")
+    }
+
+    /// Completes the bytecode of a frame once all of its intermediate bytes are generated
+    fn finish_frame<'a>(
+        evm_version: &EVMVersion,
+        frame: &mut MacroFrame<'a>,
         contract: &'a Contract,
         scope: &mut Vec<&'a MacroDefinition>,
-        mut offset: usize,
         mis: &mut Vec<(usize, MacroInvocation)>,
-        recursing_constructor: bool,
-        circular_codesize_invocations: Option<&mut CircularCodeSizeIndices>,
+        circular_codesize_invocations: &mut CircularCodeSizeIndices,
     ) -> Result<BytecodeRes, CodegenError> {
-        // Get intermediate bytecode representation of the macro definition
-        let mut bytes: Vec<(usize, Bytes)> = Vec::default();
-        let ir_bytes = macro_def.to_irbytecode(evm_version)?.0;
-
-        // Define outer loop variables
-        let mut jump_table = JumpTable::new();
-        let mut label_indices = LabelIndices::new();
-        let mut table_instances = Jumps::new();
-        let mut utilized_tables: Vec<TableDefinition> = Vec::new();
-        let mut ccsi = CircularCodeSizeIndices::new();
-        let circular_codesize_invocations = circular_codesize_invocations.unwrap_or(&mut ccsi);
+        let mut bytes = std::mem::take(&mut frame.bytes);
 
-        // Loop through all intermediate bytecode representations generated from the AST
-        for ir_byte in ir_bytes.iter() {
-            let starting_offset = offset;
-            match &ir_byte.ty {
-                IRByteType::Bytes(b) => {
-                    offset += b.0.len() / 2;
-                    bytes.push((starting_offset, b.to_owned()));
-                }
-                IRByteType::Constant(name) => {
-                    let push_bytes = constant_gen(evm_version, name, contract, ir_byte.span)?;
-                    offset += push_bytes.len() / 2;
-                    tracing::debug!(target: "codegen", "OFFSET: {}, PUSH BYTES: {:?}", offset, push_bytes);
-                    bytes.push((starting_offset, Bytes(push_bytes)));
-                }
-                IRByteType::Statement(s) => {
-                    // if we have a codesize call for the constructor here, from within the
-                    // constructor, we skip
-                    if recursing_constructor {
-                        continue;
-                    }
-                    let mut push_bytes = statement_gen(
-                        evm_version,
-                        s,
-                        contract,
-                        macro_def,
-                        scope,
-                        &mut offset,
-                        mis,
-                        &mut jump_table,
-                        &mut label_indices,
-                        &mut table_instances,
-                        &mut utilized_tables,
-                        circular_codesize_invocations,
-                        starting_offset,
-                    )?;
-                    bytes.append(&mut push_bytes);
-                }
-                IRByteType::ArgCall(arg_name) => {
-                    // Bubble up arg call by looking through the previous scopes.
-                    // Once the arg value is found, add it to `bytes`
-                    bubble_arg_call(
-                        arg_name,
-                        &mut bytes,
-                        macro_def,
-                        contract,
-                        scope,
-                        &mut offset,
-                        mis,
-                        &mut jump_table,
-                    )?
-                }
-            }
-        }
+        // Record the length of the macro itself, excluding any functions appended below
+        let macro_len = bytes.iter().map(|(_, b)| b.0.len()).sum::<usize>() / 2;
+        frame.macro_lengths.insert(frame.macro_def.name.clone(), macro_len);
 
         // We're done, let's pop off the macro invocation
         if mis.pop().is_none() {
@@ -365,11 +1910,15 @@ impl Codegen {
                 evm_version,
                 contract,
                 scope,
-                &mut offset,
+                &mut frame.offset,
                 mis,
-                &mut jump_table,
-                &mut label_indices,
-                &mut table_instances,
+                &mut frame.jump_table,
+                &mut frame.label_indices,
+                &mut frame.table_instances,
+                &mut frame.macro_offsets,
+                &mut frame.macro_lengths,
+                &mut frame.codeoffset_instances,
+                &mut frame.source_spans,
                 bytes,
             )?;
         } else {
@@ -379,18 +1928,148 @@ impl Codegen {
         }
 
         // Fill JUMPDEST placeholders
-        let (bytes, unmatched_jumps) = Codegen::fill_unmatched(bytes, &jump_table, &label_indices)?;
+        let (bytes, unmatched_jumps) =
+            Codegen::fill_unmatched(bytes, &frame.jump_table, &frame.label_indices)?;
 
         // Fill in circular codesize invocations
-        // Workout how to increase the offset the correct amount within here if it is longer than 2
-        // bytes
+        // Workout how to increase the offset the correct amount within here if it is longer
+        // than 2 bytes
         let bytes = Codegen::fill_circular_codesize_invocations(
             bytes,
             circular_codesize_invocations,
-            &macro_def.name,
+            &frame.macro_def.name,
         )?;
 
-        Ok(BytecodeRes { bytes, label_indices, unmatched_jumps, table_instances, utilized_tables })
+        events::emit(serde_json::json!({
+            "event": "macro_completed",
+            "macro": frame.macro_def.name,
+            "offset": frame.offset,
+        }));
+        Ok(BytecodeRes {
+            bytes,
+            label_indices: std::mem::take(&mut frame.label_indices),
+            unmatched_jumps,
+            table_instances: std::mem::take(&mut frame.table_instances),
+            utilized_tables: std::mem::take(&mut frame.utilized_tables),
+            macro_offsets: std::mem::take(&mut frame.macro_offsets),
+            macro_lengths: std::mem::take(&mut frame.macro_lengths),
+            codeoffset_instances: std::mem::take(&mut frame.codeoffset_instances),
+            source_spans: std::mem::take(&mut frame.source_spans),
+        })
+    }
+
+    /// Generates a single intermediate byte into the current frame
+    ///
+    /// Returns the frame of the macro that must be generated before the current frame can
+    /// continue, if any.
+    fn ir_byte_to_bytecode<'a>(
+        evm_version: &EVMVersion,
+        ir_byte: IRBytes<'a>,
+        frame: &mut MacroFrame<'a>,
+        contract: &'a Contract,
+        scope: &mut Vec<&'a MacroDefinition>,
+        mis: &mut Vec<(usize, MacroInvocation)>,
+        circular_codesize_invocations: &mut CircularCodeSizeIndices,
+    ) -> Result<Option<MacroFrame<'a>>, CodegenError> {
+        let starting_offset = frame.offset;
+        frame.source_spans.push((
+            starting_offset,
+            frame.macro_def.name.clone(),
+            ir_byte.span.clone(),
+        ));
+        match &ir_byte.ty {
+            IRByteType::Bytes(b) => {
+                frame.offset += b.0.len() / 2;
+                frame.bytes.push((starting_offset, b.to_owned()));
+            }
+            IRByteType::Constant(name) => {
+                let push_bytes = constant_gen(evm_version, name, contract, ir_byte.span)?;
+                frame.offset += push_bytes.len() / 2;
+                tracing::debug!(target: "codegen", "OFFSET: {}, PUSH BYTES: {:?}", frame.offset, push_bytes);
+                frame.bytes.push((starting_offset, Bytes(push_bytes)));
+            }
+            IRByteType::Statement(s) => {
+                // if we have a codesize call for the constructor here, from within the
+                // constructor, we skip
+                if frame.recursing_constructor {
+                    return Ok(None)
+                }
+                let statement_bytecode = statement_gen(
+                    evm_version,
+                    s,
+                    contract,
+                    frame.macro_def,
+                    scope,
+                    &mut frame.offset,
+                    mis,
+                    &mut frame.jump_table,
+                    &mut frame.label_indices,
+                    &mut frame.table_instances,
+                    &mut frame.codeoffset_instances,
+                    &mut frame.utilized_tables,
+                    circular_codesize_invocations,
+                    starting_offset,
+                )?;
+                match statement_bytecode {
+                    StatementBytecode::Bytes(mut push_bytes) => frame.bytes.append(&mut push_bytes),
+                    StatementBytecode::Invocation(ir_macro, mi) => {
+                        // Generate the invoked macro in place
+                        let span = mi.span.clone();
+                        scope.push(ir_macro);
+                        mis.push((frame.offset, mi));
+                        return Ok(Some(MacroFrame::new(
+                            evm_version,
+                            ir_macro,
+                            span,
+                            frame.offset,
+                            false,
+                            FrameKind::Invocation,
+                        )?))
+                    }
+                    StatementBytecode::Codesize(ir_macro) => {
+                        // Size the macro in its own scope, restoring ours once it is generated
+                        let mut sizing_scope = scope.clone();
+                        sizing_scope.push(ir_macro);
+                        let kind = FrameKind::Codesize {
+                            scope: std::mem::replace(scope, sizing_scope),
+                            mis: mis.clone(),
+                            circular_codesize_invocations: std::mem::take(
+                                circular_codesize_invocations,
+                            ),
+                        };
+                        return Ok(Some(MacroFrame::new(
+                            evm_version,
+                            ir_macro,
+                            s.span.clone(),
+                            frame.offset,
+                            ir_macro.name.eq("CONSTRUCTOR"),
+                            kind,
+                        )?))
+                    }
+                }
+            }
+            IRByteType::ArgCall(arg_name) => {
+                // Bubble up arg call by looking through the previous scopes.
+                // Once the arg value is found, add it to `bytes`
+                bubble_arg_call(
+                    evm_version,
+                    arg_name,
+                    &mut frame.bytes,
+                    frame.macro_def,
+                    contract,
+                    scope,
+                    &mut frame.offset,
+                    mis,
+                    &mut frame.jump_table,
+                    &mut frame.label_indices,
+                    &mut frame.table_instances,
+                    &mut frame.codeoffset_instances,
+                    &mut frame.utilized_tables,
+                    circular_codesize_invocations,
+                )?
+            }
+        }
+        Ok(None)
     }
 
     /// Helper associated function to fill unmatched jump dests.
@@ -496,8 +2175,8 @@ impl Codegen {
         // Codesize will increase by 1 byte for every codesize that exists
         let extended_length = length + (offset_increase * num_invocations);
 
-        let push_size = format_even_bytes(format!("{extended_length:02x}"));
-        let push_bytes = format!("{:02x}{push_size}", 95 + push_size.len() / 2);
+        let push_bytes = bytes_util::format_literal(format!("{extended_length:02x}"))
+            .expect("a size always fits a push");
 
         // Track the number of bytes added if there is an offset increase with codesize
         let mut running_increase = 0;
@@ -551,6 +2230,10 @@ impl Codegen {
         jump_table: &mut JumpTable,
         label_indices: &mut LabelIndices,
         table_instances: &mut Jumps,
+        macro_offsets: &mut LabelIndices,
+        macro_lengths: &mut LabelIndices,
+        codeoffset_instances: &mut Jumps,
+        source_spans: &mut Provenance,
         mut bytes: Vec<(usize, Bytes)>,
     ) -> Result<Vec<(usize, Bytes)>, CodegenError> {
         for macro_def in contract.macros.iter().filter(|m| m.outlined) {
@@ -582,6 +2265,15 @@ impl Codegen {
             }
             table_instances.extend(res.table_instances);
             label_indices.extend(res.label_indices);
+            for (name, start) in res.macro_offsets {
+                macro_offsets.entry(name).or_insert(start);
+            }
+            for (name, len) in res.macro_lengths {
+                macro_lengths.entry(name).or_insert(len);
+            }
+            codeoffset_instances.extend(res.codeoffset_instances);
+            source_spans.push((*offset, macro_def.name.clone(), macro_def.span.clone()));
+            source_spans.extend(res.source_spans);
 
             let macro_code_len = res.bytes.iter().map(|(_, b)| b.0.len()).sum::<usize>() / 2;
 
@@ -596,6 +2288,11 @@ impl Codegen {
                 *offset + macro_code_len + 1,
                 Bytes(format!("{}{}", stack_swaps.join(""), Opcode::Jump)),
             ));
+            source_spans.push((
+                *offset + macro_code_len + 1,
+                macro_def.name.clone(),
+                macro_def.span.clone(),
+            ));
             bytes = [bytes, res.bytes].concat();
             // Add the jumpdest to the beginning of the outlined macro.
             label_indices.insert(format!("goto_{}", macro_def.name.clone()), *offset);
@@ -605,8 +2302,124 @@ impl Codegen {
         Ok(bytes)
     }
 
+    /// Lexes and parses a file along with its dependencies
+    ///
+    /// With a cache attached, a file whose contents and dependencies are unchanged since it was
+    /// last parsed with the same defines returns the cached contract, and new tokens are
+    /// persisted if the cache has a path. Returns the contract and whether it was cached. The
+    /// contract doesn't share its constants with the cached one, so deriving storage pointers
+    /// leaves the cache untouched.
+    pub fn parse_file(
+        &mut self,
+        file: Arc<FileSource>,
+        defines: &HashSet<String>,
+    ) -> Result<(Contract, bool), CompilerError> {
+        let key = ParseCache::key(&file, defines);
+        if let Some(contract) = self.cache.as_ref().and_then(|c| c.contracts.get(&key)) {
+            tracing::info!(target: "codegen", "PARSE CACHE HIT FOR \"{}\"", file.path);
+            return Ok((cache::detached(contract), true))
+        }
+
+        let tokens = match self.cache.as_ref().and_then(|c| c.tokens.get(&key)) {
+            Some(tokens) => tokens.clone(),
+            None => {
+                let flattened = FileSource::fully_flatten(Arc::clone(&file));
+                Lexer::new(&flattened.0)
+                    .with_defines(defines.clone())
+                    .collect::<Result<Vec<_>, LexicalError>>()
+                    .map_err(CompilerError::LexicalError)?
+            }
+        };
+        let contract = Parser::new(tokens.clone(), Some(file.path.clone()))
+            .parse()
+            .map_err(CompilerError::ParserError)?;
+
+        if let Some(cache) = self.cache.as_mut() {
+            cache.contracts.insert(key.clone(), cache::detached(&contract));
+            if cache.tokens.insert(key, tokens).is_none() {
+                cache.save().map_err(CompilerError::CodegenError)?;
+            }
+        }
+        Ok((contract, false))
+    }
+
+    /// Compiles a contract source into an artifact
+    ///
+    /// Runs the whole pipeline with default settings: lexing, parsing, storage pointer
+    /// derivation, the checks and lints the compiler runs, main and constructor bytecode
+    /// generation, and ABI generation. The
+    /// source can't import other files. A missing `CONSTRUCTOR` macro is only an error when
+    /// constructor arguments are given. Constructor arguments are checked against the ABI's
+    /// declared constructor, see [encode_constructor_args](Codegen::encode_constructor_args).
+    /// Errors of each stage are returned unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The contract source code
+    /// * `constructor_args` - Constructor arguments to encode and append to the deployed bytecode
+    pub fn compile_str(
+        source: &str,
+        constructor_args: Vec<String>,
+    ) -> Result<Artifact, CompilerError> {
+        let tokens = Lexer::new(source)
+            .collect::<Result<Vec<_>, LexicalError>>()
+            .map_err(CompilerError::LexicalError)?;
+        let mut contract = Parser::new(tokens, None).parse().map_err(CompilerError::ParserError)?;
+        contract.derive_storage_pointers();
+        contract.check_duplicate_definitions().map_err(CompilerError::CodegenError)?;
+        contract.check_storage_slots().map_err(CompilerError::CodegenError)?;
+
+        let evm_version = EVMVersion::default();
+        let mut cg = Codegen::new();
+        cg.lint(&contract).map_err(CompilerError::CodegenError)?;
+        cg.check_entry_signatures(&contract, None, None).map_err(CompilerError::CodegenError)?;
+        Codegen::check_view_macros(&evm_version, &contract).map_err(CompilerError::CodegenError)?;
+        cg.resolve_test_invocations(&mut contract);
+        cg.resolve_push_literals(&mut contract);
+
+        let main_bytecode = Codegen::generate_main_bytecode(&evm_version, &contract, None)
+            .map_err(CompilerError::CodegenError)?;
+        let (constructor_bytecode, has_custom_bootstrap) =
+            match Codegen::generate_constructor_bytecode(&evm_version, &contract, None) {
+                Ok(res) => res,
+                Err(e)
+                    if e.kind ==
+                        CodegenErrorKind::MissingMacroDefinition("CONSTRUCTOR".to_string()) &&
+                        constructor_args.is_empty() =>
+                {
+                    (String::default(), false)
+                }
+                Err(e) => return Err(CompilerError::CodegenError(e)),
+            };
+
+        let constructor = Constructor::declared(&contract);
+        let encoded_inputs = Codegen::encode_constructor_args(
+            constructor.as_ref().map(|c| c.inputs.as_slice()),
+            constructor_args,
+        )
+        .map_err(CompilerError::CodegenError)?;
+
+        let file = Arc::new(FileSource { source: Some(source.to_string()), ..Default::default() });
+        let mut artifact = cg
+            .churn(
+                file,
+                encoded_inputs,
+                &main_bytecode,
+                &constructor_bytecode,
+                has_custom_bootstrap,
+            )
+            .map_err(CompilerError::CodegenError)?;
+        artifact.abi = Some(cg.abi_gen(contract, None).map_err(CompilerError::CodegenError)?);
+        Ok(artifact)
+    }
+
     /// Generate a codegen artifact
     ///
+    /// Unless the constructor provides its own bootstrap, the bootstrap code set with
+    /// [`Codegen::with_bootstrap`], or the default bootstrap code, is placed between the
+    /// constructor and runtime bytecode. The metadata trailer set with [`Codegen::with_metadata`],
+    /// if any, is appended to the runtime bytecode.
+    ///
     /// # Arguments
     ///
     /// * `args` - A vector of Tokens representing constructor arguments
@@ -620,6 +2433,21 @@ impl Codegen {
         constructor_bytecode: &str,
         has_custom_bootstrap: bool,
     ) -> Result<Artifact, CodegenError> {
+        let main_bytecode = match &self.metadata {
+            Some(metadata) => format!("{main_bytecode}{}", metadata.encode()?),
+            None => main_bytecode.to_string(),
+        };
+        let contract_length = main_bytecode.len() / 2;
+        let constructor_length = constructor_bytecode.len() / 2;
+
+        // Runtime bytecode over the EIP-170 limit can never be deployed
+        if contract_length > MAX_CONTRACT_SIZE {
+            self.warn(CodegenWarning {
+                kind: CodegenWarningKind::ContractSizeExceeded(contract_length, MAX_CONTRACT_SIZE),
+                span: AstSpan(vec![Span { start: 0, end: 0, file: None }]),
+            })?;
+        }
+
         let artifact: &mut Artifact = if let Some(art) = &mut self.artifact {
             art
         } else {
@@ -627,11 +2455,8 @@ impl Codegen {
             self.artifact.as_mut().unwrap()
         };
 
-        // Move `main_bytecode` to the heap so that it can be modified if need be.
-        let mut main_bytecode = String::from(main_bytecode);
-
-        let contract_length = main_bytecode.len() / 2;
-        let constructor_length = constructor_bytecode.len() / 2;
+        // `main_bytecode` may be modified when filling dynamic argument placeholders
+        let mut main_bytecode = main_bytecode;
 
         // Sort constructor arguments so that statically sized args are inserted last.
         args.sort_by(|a, b| {
@@ -706,6 +2531,7 @@ impl Codegen {
                 kind: CodegenErrorKind::InvalidDynArgIndex,
                 span: AstSpan(vec![Span { start: 0, end: 0, file: None }]),
                 token: None,
+                call_stack: vec![],
             });
         }
 
@@ -732,26 +2558,460 @@ impl Codegen {
             )
         };
 
+        // The default bootstrap code pushes both sizes with at most a PUSH2
+        if !has_custom_bootstrap && self.bootstrap.is_none() {
+            if let Some(size) = [contract_length, bootstrap_code_size + constructor_length]
+                .into_iter()
+                .find(|size| *size > 0xffff)
+            {
+                tracing::error!(target: "codegen", "CODE SIZE TOO LARGE FOR BOOTSTRAP: {}", size);
+                return Err(CodegenError {
+                    kind: CodegenErrorKind::BootstrapSizeOverflow(size),
+                    span: AstSpan(vec![Span { start: 0, end: 0, file: None }]),
+                    token: None,
+                    call_stack: vec![],
+                });
+            }
+        }
+
         let bootstrap_code = if has_custom_bootstrap {
             String::default()
+        } else if let Some(bootstrap) = &self.bootstrap {
+            bootstrap.generate(contract_length, constructor_length)
         } else {
             format!("{contract_size}80{contract_code_offset}3d393df3")
         };
 
         // Generate the final bytecode
         let constructor_code = format!("{constructor_bytecode}{bootstrap_code}");
-        artifact.bytecode =
-            format!("{constructor_code}{main_bytecode}{constructor_args}").to_lowercase();
-        artifact.runtime = main_bytecode.to_lowercase();
+        let bytecode = format!("{constructor_code}{main_bytecode}{constructor_args}");
+        if self.hex_uppercase {
+            artifact.bytecode = bytecode.to_uppercase();
+            artifact.runtime = main_bytecode.to_uppercase();
+        } else {
+            artifact.bytecode = bytecode.to_lowercase();
+            artifact.runtime = main_bytecode.to_lowercase();
+        }
         artifact.file = file;
         Ok(artifact.clone())
     }
 
+    /// Generate a runtime-only codegen artifact
+    ///
+    /// Unlike [churn](Codegen::churn), no constructor or bootstrap code is placed in front of
+    /// the runtime bytecode, so the artifact's `bytecode` is left empty.
+    ///
+    /// # Arguments
+    ///
+    /// * `main_bytecode` - The compiled MAIN Macro bytecode
+    pub fn runtime_artifact(&mut self, file: Arc<FileSource>, main_bytecode: &str) -> Artifact {
+        let runtime = if self.hex_uppercase {
+            main_bytecode.to_uppercase()
+        } else {
+            main_bytecode.to_lowercase()
+        };
+        let artifact = Artifact { file, ..Artifact::from_runtime(&runtime) };
+        self.artifact = Some(artifact.clone());
+        artifact
+    }
+
+    /// Encode constructor arguments with explicit types as ethers_core::abi::token::Token
+    ///
+    /// Unlike [encode_constructor_args](Codegen::encode_constructor_args), the argument types are
+    /// given alongside their values. Each argument is a `(value, type)` pair where the type is a
+    /// solidity type, e.g. `("0x646dB8ffC21e7ddc2B6327448dd9Fa560Df41087", "address")`.
+    pub fn encode_constructor_args_typed(
+        args: Vec<(String, String)>,
+    ) -> Result<Vec<ethers_core::abi::token::Token>, CodegenError> {
+        args.iter()
+            .map(|(value, ty)| {
+                let param_type = Reader::read(ty).map_err(|_| {
+                    tracing::error!(target: "codegen", "INVALID CONSTRUCTOR ARGUMENT TYPE: \"{}\"", ty);
+                    CodegenError {
+                        kind: CodegenErrorKind::InvalidArguments(format!(
+                            "Invalid constructor argument type \"{ty}\""
+                        )),
+                        span: AstSpan(vec![]),
+                        token: None,
+                        call_stack: vec![],
+                    }
+                })?;
+                // The lenient tokenizer reads integers as decimal, so `0x` integers are parsed
+                // here, and only addresses and bytes take bare hex
+                let token = match (&param_type, value.strip_prefix("0x")) {
+                    (ParamType::Uint(_), Some(hex)) => {
+                        U256::from_str_radix(hex, 16).map(ethers_core::abi::token::Token::Uint).ok()
+                    }
+                    (ParamType::Int(_), Some(hex)) => {
+                        U256::from_str_radix(hex, 16).map(ethers_core::abi::token::Token::Int).ok()
+                    }
+                    (ParamType::Address | ParamType::Bytes | ParamType::FixedBytes(_), Some(hex)) => {
+                        LenientTokenizer::tokenize(&param_type, hex).ok()
+                    }
+                    _ => LenientTokenizer::tokenize(&param_type, value).ok(),
+                };
+                // The tokenizer reads every integer as 256 bits wide, so the declared width is
+                // checked here, with negative integers in two's complement
+                let fits = |t: &ethers_core::abi::token::Token| match (&param_type, t) {
+                    (ParamType::Uint(n), ethers_core::abi::token::Token::Uint(v)) => v.bits() <= *n,
+                    (ParamType::Int(n), ethers_core::abi::token::Token::Int(v)) => {
+                        let limit = U256::one() << (n - 1);
+                        if v.bit(255) {
+                            (!*v).overflowing_add(U256::one()).0 <= limit
+                        } else {
+                            *v < limit
+                        }
+                    }
+                    _ => true,
+                };
+                token.filter(fits).ok_or_else(|| {
+                    tracing::error!(target: "codegen", "INVALID CONSTRUCTOR ARGUMENT \"{}\" FOR TYPE \"{}\"", value, ty);
+                    CodegenError {
+                        kind: CodegenErrorKind::InvalidArguments(format!(
+                            "Invalid constructor argument \"{value}\" for type \"{ty}\""
+                        )),
+                        span: AstSpan(vec![]),
+                        token: None,
+                        call_stack: vec![],
+                    }
+                })
+            })
+            .collect()
+    }
+
     /// Encode constructor arguments as ethers_core::abi::token::Token
-    pub fn encode_constructor_args(args: Vec<String>) -> Vec<ethers_core::abi::token::Token> {
-        let tokens: Vec<ethers_core::abi::token::Token> =
-            args.iter().map(|tok| EToken::try_from(tok.clone()).unwrap().0).collect();
-        tokens
+    ///
+    /// Given the constructor's declared parameters, e.g. from
+    /// [Constructor::declared](Constructor::declared), each argument is tokenized as the type of
+    /// the parameter at the same position. A value that doesn't fit its type, or a number of
+    /// arguments that doesn't match the number of parameters, results in a CodegenError.
+    /// Contracts without a declared constructor have no parameters to check against, so the
+    /// argument types are guessed from their values instead.
+    pub fn encode_constructor_args(
+        inputs: Option<&[FunctionParam]>,
+        args: Vec<String>,
+    ) -> Result<Vec<ethers_core::abi::token::Token>, CodegenError> {
+        let Some(inputs) = inputs else {
+            return args
+                .into_iter()
+                .map(|value| {
+                    EToken::try_from(value.clone()).map(|tok| tok.0).map_err(|_| {
+                        tracing::error!(target: "codegen", "INVALID CONSTRUCTOR ARGUMENT \"{}\"", value);
+                        CodegenError {
+                            kind: CodegenErrorKind::InvalidArguments(format!(
+                                "Invalid constructor argument \"{value}\""
+                            )),
+                            span: AstSpan(vec![]),
+                            token: None,
+                            call_stack: vec![],
+                        }
+                    })
+                })
+                .collect()
+        };
+
+        if inputs.len() != args.len() {
+            tracing::error!(target: "codegen", "EXPECTED {} CONSTRUCTOR ARGUMENTS, GOT {}", inputs.len(), args.len());
+            return Err(CodegenError {
+                kind: CodegenErrorKind::InvalidArguments(format!(
+                    "Expected {} constructor arguments, got {}",
+                    inputs.len(),
+                    args.len()
+                )),
+                span: AstSpan(vec![]),
+                token: None,
+                call_stack: vec![],
+            })
+        }
+
+        inputs
+            .iter()
+            .zip(args)
+            .enumerate()
+            .map(|(index, (param, value))| {
+                let expected = param.kind.to_string();
+                Codegen::encode_constructor_args_typed(vec![(value.clone(), expected.clone())])
+                    .map(|mut tokens| tokens.remove(0))
+                    .map_err(|_| CodegenError {
+                        kind: CodegenErrorKind::InvalidConstructorArgument {
+                            index,
+                            expected,
+                            got: value,
+                        },
+                        span: AstSpan(vec![]),
+                        token: None,
+                        call_stack: vec![],
+                    })
+            })
+            .collect()
+    }
+
+    /// Reassembles raw bytecode
+    ///
+    /// Disassembles the bytecode into opcodes and their push data before assembling them back
+    /// into a normalized, lowercase hex string. Valid bytecode round-trips to the same bytecode,
+    /// so any difference with the input indicates corrupt input. Push data that runs past the
+    /// end of the bytecode results in a CodegenError.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytecode` - The hex encoded bytecode, with or without a `0x` prefix
+    pub fn reassemble(bytecode: &str) -> Result<String, CodegenError> {
+        let bytes = Codegen::decode_bytecode(bytecode)?;
+        let ops = Codegen::split_ops(&bytes)?;
+        Ok(ops.iter().map(|(_, op, data)| format!("{op:02x}{}", hex::encode(data))).collect())
+    }
+
+    /// Disassembles hex encoded bytecode into its instructions
+    ///
+    /// Yields the offset and opcode of every instruction, along with the immediate data of
+    /// `PUSH1` through `PUSH32`. Push data that runs past the end of the bytecode and bytes that
+    /// do not correspond to a known opcode result in a CodegenError holding their offset.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytecode` - The hex encoded bytecode, with or without a `0x` prefix
+    #[allow(clippy::type_complexity)]
+    pub fn disassemble(
+        bytecode: &str,
+    ) -> Result<Vec<(usize, Opcode, Option<Vec<u8>>)>, CodegenError> {
+        let bytes = Codegen::decode_bytecode(bytecode)?;
+        Codegen::split_ops(&bytes)?
+            .into_iter()
+            .map(|(pc, op, data)| {
+                let opcode = Opcode::from_u8(op).ok_or_else(|| {
+                    tracing::error!(target: "codegen", "UNKNOWN OPCODE {:#04x} AT PC {}", op, pc);
+                    CodegenError {
+                        kind: CodegenErrorKind::UnknownOpcode(pc, op),
+                        span: AstSpan(vec![]),
+                        token: None,
+                        call_stack: vec![],
+                    }
+                })?;
+                let data = opcode.is_value_push().then(|| data.to_vec());
+                Ok((pc, opcode, data))
+            })
+            .collect()
+    }
+
+    /// Counts the occurrences of each opcode in hex encoded bytecode
+    ///
+    /// PUSH immediate data is skipped rather than counted, and bytes that do not correspond to a
+    /// known opcode are ignored.
+    pub fn opcode_histogram(bytecode: &str) -> Result<HashMap<Opcode, usize>, CodegenError> {
+        let bytes = Codegen::decode_bytecode(bytecode)?;
+        let mut histogram = HashMap::new();
+        for (_, op, _) in Codegen::split_ops(&bytes)? {
+            if let Some(opcode) = Opcode::from_u8(op) {
+                *histogram.entry(opcode).or_insert(0) += 1;
+            }
+        }
+        Ok(histogram)
+    }
+
+    /// Estimates the static gas cost of hex encoded bytecode
+    ///
+    /// Sums `Opcode::min_gas` over every disassembled instruction as if each one ran exactly
+    /// once, so jumps, loops and early exits are ignored. Dynamic costs such as memory expansion,
+    /// cold storage and account access, and value transfers are not included, which makes the
+    /// result a lower bound for straight-line code. Unknown opcodes result in a CodegenError.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytecode` - The hex encoded bytecode, with or without a `0x` prefix
+    pub fn estimate_gas(bytecode: &str) -> Result<u64, CodegenError> {
+        Ok(Codegen::disassemble(bytecode)?.iter().map(|(_, opcode, _)| opcode.min_gas()).sum())
+    }
+
+    /// Diffs the runtime bytecode of two Contract ASTs at the instruction level
+    ///
+    /// Compiles the `MAIN` macro of both contracts and diffs their disassembled instruction
+    /// listings rather than the raw hex, so a changed literal shows up as e.g.
+    /// `PUSH1 0x01 became PUSH1 0x02 at offset 0x04`. Removed instructions carry their offset in
+    /// the old bytecode, all other instructions their offset in the new bytecode.
+    pub fn diff_opcodes(
+        evm_version: &EVMVersion,
+        old: &Contract,
+        new: &Contract,
+    ) -> Result<Vec<InstructionDiff>, CodegenError> {
+        let old = Codegen::instructions(&Codegen::generate_main_bytecode(evm_version, old, None)?)?;
+        let new = Codegen::instructions(&Codegen::generate_main_bytecode(evm_version, new, None)?)?;
+
+        // Skip the common prefix and suffix before diffing the remaining instructions
+        let prefix = old.iter().zip(new.iter()).take_while(|(o, n)| o.text == n.text).count();
+        let suffix = old[prefix..]
+            .iter()
+            .rev()
+            .zip(new[prefix..].iter().rev())
+            .take_while(|(o, n)| o.text == n.text)
+            .count();
+        let old_mid = &old[prefix..old.len() - suffix];
+        let new_mid = &new[prefix..new.len() - suffix];
+
+        // Longest common subsequence lengths of every pair of suffixes
+        let mut lcs = vec![vec![0usize; new_mid.len() + 1]; old_mid.len() + 1];
+        for i in (0..old_mid.len()).rev() {
+            for j in (0..new_mid.len()).rev() {
+                lcs[i][j] = if old_mid[i].text == new_mid[j].text {
+                    lcs[i + 1][j + 1] + 1
+                } else {
+                    lcs[i + 1][j].max(lcs[i][j + 1])
+                };
+            }
+        }
+
+        let mut diff =
+            new[..prefix].iter().cloned().map(InstructionDiff::Unchanged).collect::<Vec<_>>();
+        let (mut removed, mut added) = (vec![], vec![]);
+        let (mut i, mut j) = (0, 0);
+        while i < old_mid.len() || j < new_mid.len() {
+            if i < old_mid.len() && j < new_mid.len() && old_mid[i].text == new_mid[j].text {
+                pair_changes(&mut diff, &mut removed, &mut added);
+                diff.push(InstructionDiff::Unchanged(new_mid[j].clone()));
+                i += 1;
+                j += 1;
+            } else if j < new_mid.len() && (i == old_mid.len() || lcs[i][j + 1] >= lcs[i + 1][j]) {
+                added.push(new_mid[j].clone());
+                j += 1;
+            } else {
+                removed.push(old_mid[i].clone());
+                i += 1;
+            }
+        }
+        pair_changes(&mut diff, &mut removed, &mut added);
+        diff.extend(new[new.len() - suffix..].iter().cloned().map(InstructionDiff::Unchanged));
+
+        Ok(diff)
+    }
+
+    /// Finds the first instruction at which two hex encoded bytecodes differ
+    ///
+    /// Both bytecodes are disassembled and compared instruction by instruction, so a changed push
+    /// value is reported along with the instructions before and after the change. Returns `None`
+    /// if the bytecodes hold the same instructions.
+    ///
+    /// # Arguments
+    ///
+    /// * `old` - The hex encoded old bytecode, with or without a `0x` prefix
+    /// * `new` - The hex encoded new bytecode, with or without a `0x` prefix
+    pub fn diff_bytecode(old: &str, new: &str) -> Result<Option<BytecodeDiff>, CodegenError> {
+        let old = Codegen::instructions(old)?;
+        let new = Codegen::instructions(new)?;
+
+        let common = old.iter().zip(new.iter()).take_while(|(o, n)| o.text == n.text).count();
+        let offset = match old.get(common).or(new.get(common)) {
+            Some(instruction) => instruction.offset,
+            None => return Ok(None),
+        };
+        Ok(Some(BytecodeDiff {
+            offset,
+            old: old.get(common).cloned(),
+            new: new.get(common).cloned(),
+        }))
+    }
+
+    /// Helper associated function to disassemble bytecode into an instruction listing
+    fn instructions(bytecode: &str) -> Result<Vec<Instruction>, CodegenError> {
+        let bytes = Codegen::decode_bytecode(bytecode)?;
+        Ok(Codegen::split_ops(&bytes)?
+            .into_iter()
+            .map(|(offset, op, data)| {
+                let mnemonic = Opcode::from_u8(op)
+                    .map(|o| o.to_mnemonic().to_string())
+                    .unwrap_or_else(|| format!("UNKNOWN({op:#04x})"));
+                let text = if data.is_empty() {
+                    mnemonic
+                } else {
+                    format!("{mnemonic} 0x{}", hex::encode(data))
+                };
+                Instruction { offset, text }
+            })
+            .collect())
+    }
+
+    /// Helper associated function to decode hex encoded bytecode
+    fn decode_bytecode(bytecode: &str) -> Result<Vec<u8>, CodegenError> {
+        let code = bytecode.strip_prefix("0x").unwrap_or(bytecode);
+        hex::decode(code).map_err(|_| {
+            tracing::error!(target: "codegen", "INVALID HEX STRING PASSED AS BYTECODE: \"{}\"", bytecode);
+            CodegenError {
+                kind: CodegenErrorKind::InvalidHex(bytecode.to_string()),
+                span: AstSpan(vec![]),
+                token: None,
+                call_stack: vec![],
+            }
+        })
+    }
+
+    /// Helper associated function to split bytecode into its offsets, opcodes and push data
+    #[allow(clippy::type_complexity)]
+    fn split_ops(bytes: &[u8]) -> Result<Vec<(usize, u8, &[u8])>, CodegenError> {
+        let mut ops: Vec<(usize, u8, &[u8])> = Vec::new();
+        let mut pc = 0;
+        while pc < bytes.len() {
+            let op = bytes[pc];
+            let data_len =
+                Opcode::from_u8(op).and_then(|o| o.push_size()).unwrap_or_default() as usize;
+            if pc + 1 + data_len > bytes.len() {
+                tracing::error!(target: "codegen", "TRUNCATED PUSH DATA AT PC {}", pc);
+                return Err(CodegenError {
+                    kind: CodegenErrorKind::TruncatedPushData(pc),
+                    span: AstSpan(vec![]),
+                    token: None,
+                    call_stack: vec![],
+                })
+            }
+            ops.push((pc, op, &bytes[pc + 1..pc + 1 + data_len]));
+            pc += 1 + data_len;
+        }
+        Ok(ops)
+    }
+
+    /// Helper associated function to split code like [split_ops](Codegen::split_ops), dropping a
+    /// push whose data runs past the end of the code
+    #[allow(clippy::type_complexity)]
+    fn split_code(bytes: &[u8]) -> Result<Vec<(usize, u8, &[u8])>, CodegenError> {
+        match Codegen::split_ops(bytes) {
+            Err(CodegenError { kind: CodegenErrorKind::TruncatedPushData(pc), .. }) => {
+                Codegen::split_ops(&bytes[..pc])
+            }
+            ops => ops,
+        }
+    }
+
+    /// Helper associated function to decode generated bytecode, reading the `x` placeholders that
+    /// are filled in later as zeros
+    fn decode_placeholders(bytecode: &str) -> Result<Vec<u8>, CodegenError> {
+        hex::decode(bytecode.replace('x', "0")).map_err(|e| CodegenError {
+            kind: CodegenErrorKind::InvalidHex(e.to_string()),
+            span: AstSpan(vec![]),
+            token: None,
+            call_stack: vec![],
+        })
+    }
+
+    /// Computes the address of a contract deployed with CREATE
+    ///
+    /// The address is the last 20 bytes of `keccak256(rlp([deployer, nonce]))`.
+    ///
+    /// # Arguments
+    ///
+    /// * `deployer` - The address of the deploying account
+    /// * `nonce` - The nonce of the deploying account at the time of deployment
+    pub fn create_address(deployer: [u8; 20], nonce: u64) -> [u8; 20] {
+        ethers_core::utils::get_contract_address(deployer, nonce).to_fixed_bytes()
+    }
+
+    /// Generates the EIP-7702 delegation designator for a target address
+    ///
+    /// The designator is the `0xef0100` prefix followed by the 20 byte address, which is enforced
+    /// by the argument type. The result is hex encoded without a `0x` prefix.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - The address of the account to delegate to
+    pub fn delegation_designator(address: [u8; 20]) -> String {
+        format!("ef0100{}", hex::encode(address))
     }
 
     /// Export
@@ -761,47 +3021,76 @@ impl Codegen {
     /// # Arguments
     ///
     /// * `out` - Output location to write the serialized json artifact to.
-    pub fn export(output: String, art: &Artifact) -> Result<(), CodegenError> {
-        let serialized_artifact = serde_json::to_string_pretty(art).unwrap();
+    /// * `art` - The artifact to serialize.
+    /// * `pretty` - Whether to pretty-print the json.
+    pub fn export(output: String, art: &Artifact, pretty: bool) -> Result<(), CodegenError> {
+        let serialized_artifact =
+            if pretty { serde_json::to_string_pretty(art) } else { serde_json::to_string(art) }
+                .map_err(|e| {
+                    tracing::error!(target: "codegen", "FAILED TO SERIALIZE ARTIFACT: {}", e);
+                    file_error(CodegenErrorKind::SerdeJson(e.to_string()), output.clone())
+                })?;
         // Try to create the parent directory
         let file_path = Path::new(&output);
         if let Some(p) = file_path.parent() {
-            if let Err(e) = fs::create_dir_all(p) {
-                return Err(CodegenError {
-                    kind: CodegenErrorKind::IOError(e.to_string()),
-                    span: AstSpan(vec![Span {
-                        start: 0,
-                        end: 0,
-                        file: Some(Arc::new(FileSource {
-                            id: uuid::Uuid::new_v4(),
-                            path: output,
-                            source: None,
-                            access: None,
-                            dependencies: None,
-                        })),
-                    }]),
-                    token: None,
-                });
-            }
+            fs::create_dir_all(p).map_err(|e| {
+                file_error(CodegenErrorKind::IOError(e.to_string()), output.clone())
+            })?;
         }
-        if let Err(e) = fs::write(file_path, serialized_artifact) {
-            return Err(CodegenError {
-                kind: CodegenErrorKind::IOError(e.to_string()),
-                span: AstSpan(vec![Span {
-                    start: 0,
-                    end: 0,
-                    file: Some(Arc::new(FileSource {
-                        id: uuid::Uuid::new_v4(),
-                        path: output,
-                        source: None,
-                        access: None,
-                        dependencies: None,
-                    })),
-                }]),
-                token: None,
-            });
+        fs::write(file_path, serialized_artifact)
+            .map_err(|e| file_error(CodegenErrorKind::IOError(e.to_string()), output.clone()))
+    }
+
+    /// Load Artifact
+    ///
+    /// Reads back a Codegen Artifact written by [`Codegen::export`]. The source map isn't
+    /// exported, so it is always `None` on the loaded artifact.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Location of the serialized json artifact.
+    pub fn load_artifact(path: String) -> Result<Artifact, CodegenError> {
+        let serialized_artifact = fs::read_to_string(&path)
+            .map_err(|e| file_error(CodegenErrorKind::IOError(e.to_string()), path.clone()))?;
+        serde_json::from_str(&serialized_artifact).map_err(|e| {
+            tracing::error!(target: "codegen", "FAILED TO DESERIALIZE ARTIFACT: {}", e);
+            file_error(CodegenErrorKind::SerdeJson(e.to_string()), path)
+        })
+    }
+
+    /// Export Binary
+    ///
+    /// Writes the raw bytes of a hex encoded bytecode string out to the specified file.
+    /// The bytecode is validated as an even-length hex string before anything is written.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - Output location to write the raw bytecode to.
+    /// * `bytecode` - The hex encoded bytecode, optionally prefixed with `0x`.
+    pub fn export_binary(output: String, bytecode: &str) -> Result<(), CodegenError> {
+        let bytes = Codegen::decode_bytecode(bytecode)?;
+        let io_error = |e: std::io::Error, path: String| CodegenError {
+            kind: CodegenErrorKind::IOError(e.to_string()),
+            span: AstSpan(vec![Span {
+                start: 0,
+                end: 0,
+                file: Some(Arc::new(FileSource {
+                    id: uuid::Uuid::new_v4(),
+                    path,
+                    source: None,
+                    access: None,
+                    dependencies: None,
+                })),
+            }]),
+            token: None,
+            call_stack: vec![],
+        };
+        // Try to create the parent directory
+        let file_path = Path::new(&output);
+        if let Some(p) = file_path.parent() {
+            fs::create_dir_all(p).map_err(|e| io_error(e, output.clone()))?;
         }
-        Ok(())
+        fs::write(file_path, bytes).map_err(|e| io_error(e, output.clone()))
     }
 
     /// Abi Generation
@@ -831,10 +3120,145 @@ impl Codegen {
         // If an output's specified, write the artifact out
         if let Some(o) = output {
             // Error message is sent to tracing in `export` if an error occurs
-            Codegen::export(o, art)?;
+            Codegen::export(o, art, true)?;
         }
 
         // Return the abi
         Ok(abi)
     }
 }
+
+/// Visits every statement in a list of statements, including statements nested in labels
+fn visit_statements<'a>(statements: &'a [Statement], f: &mut impl FnMut(&'a Statement)) {
+    for statement in statements {
+        f(statement);
+        if let StatementType::Label(label) = &statement.ty {
+            visit_statements(&label.inner, f);
+        }
+    }
+}
+
+/// An error pointing at the file it occured while reading or writing
+fn file_error(kind: CodegenErrorKind, path: String) -> CodegenError {
+    CodegenError {
+        kind,
+        span: AstSpan(vec![Span {
+            start: 0,
+            end: 0,
+            file: Some(Arc::new(FileSource {
+                id: uuid::Uuid::new_v4(),
+                path,
+                source: None,
+                access: None,
+                dependencies: None,
+            })),
+        }]),
+        token: None,
+        call_stack: vec![],
+    }
+}
+
+/// The macro a `__codesize` call measures, if the builtin is one
+fn codesize_target(builtin: &BuiltinFunctionCall) -> Option<&str> {
+    match builtin.kind {
+        BuiltinFunctionKind::Codesize => builtin.args.first()?.name.as_deref(),
+        _ => None,
+    }
+}
+
+/// The table a `__tablesize` or `__tablestart` call refers to, if the builtin is one
+fn table_target(builtin: &BuiltinFunctionCall) -> Option<&str> {
+    match builtin.kind {
+        BuiltinFunctionKind::Tablesize | BuiltinFunctionKind::Tablestart => {
+            builtin.args.first()?.name.as_deref()
+        }
+        _ => None,
+    }
+}
+
+/// Removes every invocation of the given macros, including invocations nested in labels
+fn remove_invocations(statements: &mut Vec<Statement>, macros: &BTreeSet<String>) {
+    statements.retain(
+        |s| !matches!(&s.ty, StatementType::MacroInvocation(mi) if macros.contains(&mi.macro_name)),
+    );
+    for statement in statements.iter_mut() {
+        if let StatementType::Label(label) = &mut statement.ty {
+            remove_invocations(&mut label.inner, macros);
+        }
+    }
+}
+
+/// Removes every explicit value push that is followed by a literal, including in labels
+fn remove_push_overrides(statements: &mut Vec<Statement>) {
+    let mut i = 0;
+    while i + 1 < statements.len() {
+        if matches!(&statements[i].ty, StatementType::Opcode(o) if o.is_value_push()) &&
+            matches!(&statements[i + 1].ty, StatementType::Literal(_))
+        {
+            statements.remove(i);
+        }
+        i += 1;
+    }
+    for statement in statements.iter_mut() {
+        if let StatementType::Label(label) = &mut statement.ty {
+            remove_push_overrides(&mut label.inner);
+        }
+    }
+}
+
+/// Pairs up runs of removed and added instructions as changes, and appends them to a diff
+fn pair_changes(
+    diff: &mut Vec<InstructionDiff>,
+    removed: &mut Vec<Instruction>,
+    added: &mut Vec<Instruction>,
+) {
+    let paired = removed.len().min(added.len());
+    let mut removed = removed.drain(..);
+    let mut added = added.drain(..);
+    for (old, new) in removed.by_ref().zip(added.by_ref()).take(paired) {
+        diff.push(InstructionDiff::Changed { old, new });
+    }
+    diff.extend(removed.map(InstructionDiff::Removed));
+    diff.extend(added.map(InstructionDiff::Added));
+}
+
+/// Returns a span-free key for an intermediate bytecode item, for comparing macro bodies
+fn ir_key(ty: &IRByteType) -> String {
+    let builtin_key = |bf: &BuiltinFunctionCall| {
+        let args = bf.args.iter().map(|a| a.name.clone().unwrap_or_default()).collect::<Vec<_>>();
+        format!("{:?}({})", bf.kind, args.join(","))
+    };
+    match ty {
+        IRByteType::Bytes(b) => b.0.clone(),
+        IRByteType::Constant(name) => format!("[{name}]"),
+        IRByteType::ArgCall(name) => format!("<{name}>"),
+        IRByteType::Statement(s) => match &s.ty {
+            StatementType::Label(l) => format!("{}:", l.name),
+            StatementType::MacroInvocation(mi) => {
+                let args = mi
+                    .args
+                    .iter()
+                    .map(|arg| match arg {
+                        MacroArg::Literal(l) => hex::encode(l),
+                        MacroArg::Ident(name) => name.clone(),
+                        MacroArg::ArgCall(name) => format!("<{name}>"),
+                        MacroArg::Builtin(bf) => builtin_key(bf),
+                    })
+                    .collect::<Vec<String>>();
+                format!("{}({})", mi.macro_name, args.join(","))
+            }
+            StatementType::BuiltinFunctionCall(bf) => builtin_key(bf),
+            sty => sty.to_string(),
+        },
+    }
+}
+
+/// Returns the number of stack items an opcode statement consumes and produces
+///
+/// Value pushes produce nothing here, since the pushed value is a statement of its own.
+fn stack_effect(opcode: &Opcode) -> (usize, usize) {
+    if opcode.is_value_push() {
+        return (0, 0)
+    }
+    (opcode.stack_inputs() as usize, opcode.stack_outputs() as usize)
+}
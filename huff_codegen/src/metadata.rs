@@ -0,0 +1,62 @@
+use huff_utils::prelude::*;
+
+/// A CBOR encoded metadata trailer appended to the runtime bytecode
+///
+/// Like the trailer emitted by solc, the metadata is encoded as a CBOR map from text keys to
+/// byte strings, followed by the length of the encoded map as 2 big-endian bytes. An entry is
+/// only encoded if it was added, so `Metadata::ipfs(hash)` omits the `solc` version.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Metadata {
+    /// The entries of the map, in encoding order
+    pub entries: Vec<(String, Vec<u8>)>,
+}
+
+impl Metadata {
+    /// Creates metadata holding only the `ipfs` hash of the contract metadata
+    pub fn ipfs(hash: &[u8]) -> Self {
+        Self::default().with_entry("ipfs", hash)
+    }
+
+    /// Adds an entry to the map
+    pub fn with_entry(mut self, key: &str, value: &[u8]) -> Self {
+        self.entries.push((key.to_string(), value.to_vec()));
+        self
+    }
+
+    /// Encodes the trailer as hex, including the 2 byte length suffix
+    pub fn encode(&self) -> Result<String, CodegenError> {
+        let mut cbor = cbor_head(5, self.entries.len());
+        for (key, value) in self.entries.iter() {
+            cbor.extend(cbor_head(3, key.len()));
+            cbor.extend(key.as_bytes());
+            cbor.extend(cbor_head(2, value.len()));
+            cbor.extend(value);
+        }
+
+        let length = u16::try_from(cbor.len()).map_err(|_| {
+            tracing::error!(target: "codegen", "METADATA TRAILER TOO LARGE: {} BYTES", cbor.len());
+            CodegenError {
+                kind: CodegenErrorKind::InvalidArguments(format!(
+                    "Metadata trailer of {} bytes doesn't fit its 2 byte length suffix",
+                    cbor.len()
+                )),
+                span: AstSpan(vec![]),
+                token: None,
+                call_stack: vec![],
+            }
+        })?;
+        cbor.extend(length.to_be_bytes());
+        Ok(hex::encode(cbor))
+    }
+}
+
+/// Encodes the head of a CBOR data item with the given major type and length
+fn cbor_head(major: u8, len: usize) -> Vec<u8> {
+    let major = major << 5;
+    match len {
+        0..=23 => vec![major | len as u8],
+        24..=0xff => vec![major | 24, len as u8],
+        0x100..=0xffff => [vec![major | 25], (len as u16).to_be_bytes().to_vec()].concat(),
+        _ => [vec![major | 26], (len as u32).to_be_bytes().to_vec()].concat(),
+    }
+}
@@ -28,6 +28,8 @@ fn constructs_valid_abi() {
         functions: vec![],
         events: vec![],
         tables: vec![],
+        receive: false,
+        fallback: None,
     };
 
     // Generate the abi from the contract
@@ -41,7 +43,8 @@ fn constructs_valid_abi() {
             events: BTreeMap::new(),
             errors: BTreeMap::new(),
             receive: false,
-            fallback: false
+            fallback: false,
+            fallback_payable: false
         }
     );
 }
@@ -68,6 +71,8 @@ fn missing_constructor_fails() {
         functions: vec![],
         events: vec![],
         tables: vec![],
+        receive: false,
+        fallback: None,
     };
 
     // Generate the abi from the contract
@@ -1,8 +1,8 @@
 use std::sync::Arc;
 
 use ethers_core::abi::Token;
-use huff_codegen::Codegen;
-use huff_utils::prelude::FileSource;
+use huff_codegen::{Codegen, Metadata, MAX_CONTRACT_SIZE};
+use huff_utils::prelude::{CodegenErrorKind, CodegenWarningKind, FileSource};
 
 #[test]
 fn churns_into_bytecode() {
@@ -87,3 +87,132 @@ fn churns_constructor_args() {
     assert!(churn_res.is_ok());
     assert_ne!(churn_res.unwrap().bytecode, "336000556101ac806100116000396000f360003560E01c8063a9059cbb1461004857806340c10f19146100de57806370a082311461014e57806318160ddd1461016b578063095ea7b314610177578063dd62ed3e1461018e575b600435336024358160016000526000602001526040600020548082116100d8578190038260016000526000602001526040600020558281906001600052600060200152604060002054018360016000526000602001526040600020556000527fDDF252AD1BE2C89B69C2B068FC378DAA952BA7F163C4A11628F55A4DF523B3EF60206000a3600160005260206000f35b60006000fd5b60005433146100ed5760006000fd5b600435600060243582819060016000526000602001526040600020540183600160005260006020015260406000205580600254016002556000527fDDF252AD1BE2C89B69C2B068FC378DAA952BA7F163C4A11628F55A4DF523B3EF60206000a35b600435600160005260006020015260406000205460005260206000f35b60025460005260206000f35b602435600435336000526000602001526040600020555b60243560043560005260006020015260406000205460005260206000f3".to_lowercase());
 }
+
+#[test]
+fn churns_uppercase_hex() {
+    let main_bytecode = "60aa6000fd";
+
+    // Lowercase is the default casing
+    let mut cg = Codegen::new();
+    let artifact =
+        cg.churn(Arc::new(FileSource::default()), vec![], main_bytecode, "", false).unwrap();
+    assert_eq!(artifact.bytecode, "60058060093d393df360aa6000fd");
+    assert_eq!(artifact.runtime, "60aa6000fd");
+
+    // Uppercase when the option is set
+    let mut cg = Codegen { hex_uppercase: true, ..Codegen::new() };
+    let artifact = cg
+        .churn(
+            Arc::new(FileSource::default()),
+            vec![Token::Uint(0xabu64.into())],
+            main_bytecode,
+            "",
+            false,
+        )
+        .unwrap();
+    assert_eq!(artifact.bytecode, format!("60058060093D393DF360AA6000FD{}AB", "0".repeat(62)));
+    assert_eq!(artifact.runtime, "60AA6000FD");
+}
+
+#[test]
+fn churn_warns_on_oversized_runtime() {
+    // One byte over the EIP-170 limit
+    let main_bytecode = "00".repeat(MAX_CONTRACT_SIZE + 1);
+
+    let mut cg = Codegen::new();
+    let artifact =
+        cg.churn(Arc::new(FileSource::default()), vec![], &main_bytecode, "", false).unwrap();
+    assert_eq!(artifact.runtime.len() / 2, MAX_CONTRACT_SIZE + 1);
+    assert_eq!(
+        cg.warnings.iter().map(|w| w.kind.clone()).collect::<Vec<_>>(),
+        vec![CodegenWarningKind::ContractSizeExceeded(MAX_CONTRACT_SIZE + 1, MAX_CONTRACT_SIZE)]
+    );
+
+    // Denying warnings turns the overage into an error
+    let mut cg = Codegen::new();
+    cg.deny_warnings = true;
+    let err =
+        cg.churn(Arc::new(FileSource::default()), vec![], &main_bytecode, "", false).unwrap_err();
+    assert_eq!(
+        err.kind,
+        CodegenErrorKind::DeniedWarning(CodegenWarningKind::ContractSizeExceeded(
+            MAX_CONTRACT_SIZE + 1,
+            MAX_CONTRACT_SIZE
+        ))
+    );
+}
+
+#[test]
+fn churn_rejects_sizes_too_large_for_bootstrap() {
+    let main_bytecode = "00".repeat(0x10000);
+
+    let mut cg = Codegen::new();
+    let err =
+        cg.churn(Arc::new(FileSource::default()), vec![], &main_bytecode, "", false).unwrap_err();
+    assert_eq!(err.kind, CodegenErrorKind::BootstrapSizeOverflow(0x10000));
+
+    // A custom bootstrap doesn't push the sizes
+    let mut cg = Codegen::new();
+    assert!(cg.churn(Arc::new(FileSource::default()), vec![], &main_bytecode, "", true).is_ok());
+}
+
+#[test]
+fn churns_with_bootstrap_generator() {
+    // A bootstrap pushing both sizes with a PUSH3, for contracts near the size limit
+    let mut cg = Codegen::new().with_bootstrap(|contract_length, constructor_length| {
+        format!("62{contract_length:06x}8062{:06x}3d393df3", constructor_length + 13)
+    });
+
+    let main_bytecode = "6001600216";
+    let constructor_bytecode = "33600055";
+    let artifact = cg
+        .churn(Arc::new(FileSource::default()), vec![], main_bytecode, constructor_bytecode, false)
+        .unwrap();
+    assert_eq!(artifact.bytecode, "336000556200000580620000113d393df36001600216");
+    assert_eq!(artifact.runtime, main_bytecode);
+}
+
+#[test]
+fn runtime_artifact_skips_bootstrap() {
+    let mut cg = Codegen::new();
+    let main_bytecode = "5F35600101";
+
+    let artifact = cg.runtime_artifact(Arc::new(FileSource::default()), main_bytecode);
+    assert_eq!(artifact.runtime, main_bytecode.to_lowercase());
+    assert!(artifact.bytecode.is_empty());
+    assert_eq!(cg.artifact, Some(artifact));
+
+    let artifact = huff_utils::prelude::Artifact::from_runtime(main_bytecode);
+    assert_eq!(artifact.runtime, main_bytecode);
+    assert!(artifact.bytecode.is_empty());
+}
+
+#[test]
+fn churns_metadata_trailer() {
+    let main_bytecode = "5f355f5260205ff3";
+    let hash = [[0x12, 0x20].as_slice(), &[0xab; 32]].concat();
+
+    // The trailer is off by default
+    let mut cg = Codegen::new();
+    let plain =
+        cg.churn(Arc::new(FileSource::default()), vec![], main_bytecode, "", false).unwrap();
+    assert_eq!(plain.runtime, main_bytecode);
+
+    let mut cg = Codegen::new().with_metadata(Metadata::ipfs(&hash));
+    let artifact =
+        cg.churn(Arc::new(FileSource::default()), vec![], main_bytecode, "", false).unwrap();
+
+    // {"ipfs": h'1220abab..'} followed by its 42 byte length
+    let trailer = format!("a1646970667358221220{}002a", "ab".repeat(32));
+    assert_eq!(artifact.runtime, format!("{main_bytecode}{trailer}"));
+    assert_eq!(artifact.runtime.len(), main_bytecode.len() + 2 * 44);
+
+    // The bootstrap copies the runtime bytecode including the trailer
+    assert_eq!(artifact.bytecode, format!("60348060093d393df3{}", artifact.runtime));
+}
+
+#[test]
+fn encodes_metadata_entries() {
+    let metadata = Metadata::default().with_entry("solc", &[0, 8, 26]);
+    assert_eq!(metadata.encode().unwrap(), "a164736f6c634300081a000a");
+}
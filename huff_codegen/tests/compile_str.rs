@@ -0,0 +1,186 @@
+mod common;
+
+use common::parse;
+use huff_codegen::Codegen;
+use huff_utils::prelude::*;
+
+const ERC20: &str = r#"
+    #define function totalSupply() view returns (uint256)
+    #define function balanceOf(address) view returns (uint256)
+    #define function transfer(address,uint256) nonpayable returns ()
+
+    #define event Transfer(address indexed, address indexed, uint256)
+
+    #define constant TOTAL_SUPPLY = FREE_STORAGE_POINTER()
+    #define constant BALANCES = FREE_STORAGE_POINTER()
+
+    #define macro CONSTRUCTOR() = takes(0) returns(0) {
+        0x04 calldataload
+        dup1 [TOTAL_SUPPLY] sstore
+        caller [BALANCES] mstore 0x20 mstore
+        0x40 0x00 sha3 sstore
+    }
+
+    #define macro BALANCE_SLOT() = takes(1) returns(1) {
+        [BALANCES] 0x20 mstore 0x00 mstore
+        0x40 0x00 sha3
+    }
+
+    #define macro TOTAL_SUPPLY() = takes(0) returns(0) {
+        [TOTAL_SUPPLY] sload
+        0x00 mstore 0x20 0x00 return
+    }
+
+    #define macro BALANCE_OF() = takes(0) returns(0) {
+        0x04 calldataload BALANCE_SLOT() sload
+        0x00 mstore 0x20 0x00 return
+    }
+
+    #define macro TRANSFER() = takes(0) returns(0) {
+        0x24 calldataload
+        dup1 caller BALANCE_SLOT() dup1 sload
+        dup3 dup2 lt insufficient jumpi
+        sub swap1 sstore
+        0x04 calldataload BALANCE_SLOT() dup1 sload
+        add swap1 sstore
+        stop
+
+        insufficient:
+            0x00 dup1 revert
+    }
+
+    #define macro MAIN() = takes(0) returns(0) {
+        0x00 calldataload 0xE0 shr
+        dup1 __FUNC_SIG(totalSupply) eq total_supply jumpi
+        dup1 __FUNC_SIG(balanceOf) eq balance_of jumpi
+        dup1 __FUNC_SIG(transfer) eq transfer jumpi
+        0x00 dup1 revert
+
+        total_supply:
+            TOTAL_SUPPLY()
+        balance_of:
+            BALANCE_OF()
+        transfer:
+            TRANSFER()
+    }
+"#;
+
+#[test]
+fn test_compile_str_erc20() {
+    let artifact = Codegen::compile_str(ERC20, vec!["1000".to_string()]).unwrap();
+
+    // The pipeline agrees with driving each stage separately
+    let contract = parse(ERC20);
+    let runtime = Codegen::generate_main_bytecode(&EVMVersion::default(), &contract, None).unwrap();
+    let (constructor, _) =
+        Codegen::generate_constructor_bytecode(&EVMVersion::default(), &contract, None).unwrap();
+    assert_eq!(artifact.runtime, runtime);
+    assert!(artifact.bytecode.starts_with(&constructor));
+    assert!(artifact.bytecode.contains(&runtime));
+
+    // The constructor argument is appended to the deployed bytecode
+    assert!(artifact.bytecode.ends_with(&format!("{:064x}", 1000)));
+
+    let abi = artifact.abi.unwrap();
+    assert_eq!(abi.functions.len(), 3);
+    assert!(abi.functions.contains_key("balanceOf"));
+    assert!(abi.events.contains_key("Transfer"));
+    assert_eq!(artifact.file.source.as_deref(), Some(ERC20));
+}
+
+#[test]
+fn test_compile_str_without_constructor() {
+    let source = "#define macro MAIN() = takes(0) returns(0) { 0x01 }";
+    let artifact = Codegen::compile_str(source, vec![]).unwrap();
+    assert_eq!(artifact.runtime, "6001");
+
+    // Constructor arguments require a constructor
+    let err = Codegen::compile_str(source, vec!["1".to_string()]).unwrap_err();
+    assert!(matches!(
+        err,
+        CompilerError::CodegenError(CodegenError {
+            kind: CodegenErrorKind::MissingMacroDefinition(name),
+            ..
+        }) if name == "CONSTRUCTOR"
+    ));
+}
+
+#[test]
+fn test_compile_str_checks_constructor_args() {
+    let source = r#"
+        #define function constructor(uint256) nonpayable returns ()
+        #define macro CONSTRUCTOR() = takes(0) returns(0) {}
+        #define macro MAIN() = takes(0) returns(0) { 0x01 }
+    "#;
+    let artifact = Codegen::compile_str(source, vec!["0x64".to_string()]).unwrap();
+    assert!(artifact.bytecode.ends_with(&format!("{:064x}", 100)));
+
+    // Arguments are checked against the declared constructor instead of panicking
+    let err = Codegen::compile_str(source, vec!["hello".to_string()]).unwrap_err();
+    assert!(matches!(
+        err,
+        CompilerError::CodegenError(CodegenError {
+            kind: CodegenErrorKind::InvalidConstructorArgument { index: 0, .. },
+            ..
+        })
+    ));
+    let err = Codegen::compile_str(source, vec![]).unwrap_err();
+    assert!(matches!(
+        err,
+        CompilerError::CodegenError(CodegenError {
+            kind: CodegenErrorKind::InvalidArguments(_),
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_compile_str_surfaces_stage_errors() {
+    let lexical = Codegen::compile_str("#define macro MAIN() = takes(0) returns(0) { ~ }", vec![]);
+    assert!(matches!(lexical, Err(CompilerError::LexicalError(_))));
+
+    let parser = Codegen::compile_str("#define macro MAIN() = takes(0) returns(0) {", vec![]);
+    assert!(matches!(parser, Err(CompilerError::ParserError(_))));
+
+    let codegen = Codegen::compile_str("#define macro OTHER() = takes(0) returns(0) {}", vec![]);
+    assert!(matches!(
+        codegen,
+        Err(CompilerError::CodegenError(CodegenError {
+            kind: CodegenErrorKind::MissingMacroDefinition(_),
+            ..
+        }))
+    ));
+}
+
+#[test]
+fn test_compile_str_runs_compiler_checks() {
+    let err = Codegen::compile_str(
+        "#define macro MAIN() = takes(2) returns(0) { add 0x00 mstore }",
+        vec![],
+    )
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        CompilerError::CodegenError(CodegenError {
+            kind: CodegenErrorKind::InvalidEntryMacroSignature { takes: 2, .. },
+            ..
+        })
+    ));
+
+    let err = Codegen::compile_str(
+        r#"
+        #define constant A = 0x01
+        #define constant A = 0x02
+        #define macro MAIN() = takes(0) returns(0) { [A] }
+    "#,
+        vec![],
+    )
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        CompilerError::CodegenError(CodegenError {
+            kind: CodegenErrorKind::DuplicateConstantDefinition { .. },
+            ..
+        })
+    ));
+}
@@ -3,7 +3,11 @@ use ethers_core::{
     types::*,
 };
 use huff_codegen::Codegen;
-use huff_utils::bytes_util::*;
+use huff_utils::{
+    abi::{FunctionParam, FunctionParamType},
+    bytes_util::*,
+    error::CodegenErrorKind,
+};
 
 #[test]
 fn encode_simple_constructor_args() {
@@ -26,7 +30,7 @@ fn encode_simple_constructor_args() {
     .map(|s| s.to_string())
     .collect();
 
-    let results = Codegen::encode_constructor_args(args);
+    let results = Codegen::encode_constructor_args(None, args).unwrap();
     assert_eq!(results[0], Token::String("Hello".to_string()));
     assert_eq!(results[1], Token::Uint(U256::from_dec_str("10000").unwrap()));
     assert_eq!(results[2], Token::Bool(false));
@@ -34,6 +38,10 @@ fn encode_simple_constructor_args() {
     assert_eq!(results[4], Token::FixedBytes(expected_bytes32));
     assert_eq!(results[5], "-10".parse::<i128>().unwrap().into_token());
     assert_eq!(results[6], "+55".parse::<i128>().unwrap().into_token());
+
+    // Values that can't be encoded are an error rather than a panic
+    let err = Codegen::encode_constructor_args(None, vec!["-ten".to_string()]).unwrap_err();
+    assert!(matches!(err.kind, CodegenErrorKind::InvalidArguments(_)));
 }
 
 #[test]
@@ -56,7 +64,7 @@ fn encode_array_constructor_args() {
     .map(|s| s.to_string())
     .collect();
 
-    let results = Codegen::encode_constructor_args(args);
+    let results = Codegen::encode_constructor_args(None, args).unwrap();
 
     assert_eq!(
         results[0],
@@ -105,7 +113,7 @@ fn encode_missing_brackets_array_constructor_args() {
     .map(|s| s.to_string())
     .collect();
 
-    let results = Codegen::encode_constructor_args(args);
+    let results = Codegen::encode_constructor_args(None, args).unwrap();
 
     assert_eq!(
         results[0],
@@ -135,3 +143,169 @@ fn encode_missing_brackets_array_constructor_args() {
     assert_eq!(results[4], expected_array);
     assert_eq!(results[5], expected_array);
 }
+
+#[test]
+fn encode_typed_constructor_args() {
+    let value = "0x646dB8ffC21e7ddc2B6327448dd9Fa560Df41087".to_string();
+    let raw: [u8; 20] = [
+        100, 109, 184, 255, 194, 30, 125, 220, 43, 99, 39, 68, 141, 217, 250, 86, 13, 244, 16, 135,
+    ];
+
+    let results = Codegen::encode_constructor_args_typed(vec![
+        (value.clone(), "address".to_string()),
+        (value, "bytes20".to_string()),
+        ("Hello".to_string(), "string".to_string()),
+        ("100".to_string(), "uint256".to_string()),
+    ])
+    .unwrap();
+
+    assert_eq!(results[0], Token::Address(H160::from(raw)));
+    assert_eq!(results[1], Token::FixedBytes(raw.to_vec()));
+    assert_eq!(results[2], Token::String("Hello".to_string()));
+    assert_eq!(results[3], Token::Uint(U256::from(100)));
+
+    // Addresses are left padded while fixed bytes are right padded
+    let address = ethers_core::abi::encode(&[results[0].clone()]);
+    let bytes20 = ethers_core::abi::encode(&[results[1].clone()]);
+    assert_ne!(address, bytes20);
+    assert_eq!(address, [vec![0u8; 12], raw.to_vec()].concat());
+    assert_eq!(bytes20, [raw.to_vec(), vec![0u8; 12]].concat());
+}
+
+#[test]
+fn encode_typed_hex_integer_constructor_args() {
+    let results = Codegen::encode_constructor_args_typed(vec![
+        ("0x10".to_string(), "uint256".to_string()),
+        ("0xff".to_string(), "uint8".to_string()),
+        ("10".to_string(), "uint256".to_string()),
+        ("0xff".to_string(), "int256".to_string()),
+    ])
+    .unwrap();
+
+    // `0x` integers are hex, bare integers are decimal
+    assert_eq!(results[0], Token::Uint(U256::from(16)));
+    assert_eq!(results[1], Token::Uint(U256::from(255)));
+    assert_eq!(results[2], Token::Uint(U256::from(10)));
+    assert_eq!(results[3], Token::Int(U256::from(255)));
+
+    assert!(Codegen::encode_constructor_args_typed(vec![(
+        "0xzz".to_string(),
+        "uint256".to_string()
+    )])
+    .is_err());
+}
+
+#[test]
+fn encode_typed_integer_constructor_args_widths() {
+    let results = Codegen::encode_constructor_args_typed(vec![
+        ("-128".to_string(), "int8".to_string()),
+        ("127".to_string(), "int8".to_string()),
+        ("-1".to_string(), "int256".to_string()),
+    ])
+    .unwrap();
+    assert_eq!(results[0], (-128i128).into_token());
+    assert_eq!(results[1], Token::Int(U256::from(127)));
+    assert_eq!(results[2], Token::Int(U256::MAX));
+
+    // Values that don't fit the declared width are rejected
+    for (value, ty) in [
+        ("256", "uint8"),
+        ("511", "uint8"),
+        ("0x1ff", "uint8"),
+        ("128", "int8"),
+        ("-129", "int8"),
+        ("0xff", "int8"),
+    ] {
+        assert!(
+            Codegen::encode_constructor_args_typed(vec![(value.to_string(), ty.to_string())])
+                .is_err(),
+            "{value} should not fit {ty}"
+        );
+    }
+
+    let inputs = vec![FunctionParam {
+        name: "n".to_string(),
+        kind: FunctionParamType::Uint(8),
+        internal_type: None,
+    }];
+    let err = Codegen::encode_constructor_args(Some(&inputs), vec!["511".to_string()]).unwrap_err();
+    assert_eq!(
+        err.kind,
+        CodegenErrorKind::InvalidConstructorArgument {
+            index: 0,
+            expected: "uint8".to_string(),
+            got: "511".to_string(),
+        }
+    );
+}
+
+#[test]
+fn encode_typed_constructor_args_errors() {
+    assert!(Codegen::encode_constructor_args_typed(vec![(
+        "0x01".to_string(),
+        "bytesX".to_string()
+    )])
+    .is_err());
+    assert!(Codegen::encode_constructor_args_typed(vec![(
+        "0x01".to_string(),
+        "address".to_string()
+    )])
+    .is_err());
+}
+
+#[test]
+fn encode_checked_constructor_args() {
+    let inputs = vec![
+        FunctionParam {
+            name: "owner".to_string(),
+            kind: FunctionParamType::Address,
+            internal_type: None,
+        },
+        FunctionParam {
+            name: "supply".to_string(),
+            kind: FunctionParamType::Uint(256),
+            internal_type: None,
+        },
+    ];
+
+    let results = Codegen::encode_constructor_args(
+        Some(&inputs),
+        vec!["0x646dB8ffC21e7ddc2B6327448dd9Fa560Df41087".to_string(), "100".to_string()],
+    )
+    .unwrap();
+    assert_eq!(results[1], Token::Uint(U256::from(100)));
+
+    let results = Codegen::encode_constructor_args(
+        Some(&inputs),
+        vec!["0x646dB8ffC21e7ddc2B6327448dd9Fa560Df41087".to_string(), "0x64".to_string()],
+    )
+    .unwrap();
+    assert_eq!(results[1], Token::Uint(U256::from(100)));
+
+    // A value that doesn't fit its declared type
+    let err = Codegen::encode_constructor_args(
+        Some(&inputs),
+        vec!["0x646dB8ffC21e7ddc2B6327448dd9Fa560Df41087".to_string(), "hello".to_string()],
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.kind,
+        CodegenErrorKind::InvalidConstructorArgument {
+            index: 1,
+            expected: "uint256".to_string(),
+            got: "hello".to_string(),
+        }
+    );
+
+    // Missing and extra arguments
+    assert!(Codegen::encode_constructor_args(Some(&inputs), vec!["100".to_string()]).is_err());
+    assert!(Codegen::encode_constructor_args(
+        Some(&inputs),
+        vec![
+            "0x646dB8ffC21e7ddc2B6327448dd9Fa560Df41087".to_string(),
+            "100".to_string(),
+            "100".to_string()
+        ]
+    )
+    .is_err());
+}
@@ -0,0 +1,19 @@
+use huff_codegen::Codegen;
+use huff_utils::bytes_util::*;
+
+#[test]
+fn test_create_address() {
+    let deployer: [u8; 20] =
+        str_to_vec("6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0").unwrap().try_into().unwrap();
+
+    let expected = [
+        (0, "cd234a471b72ba2f1ccf0a70fcaba648a5eecd8d"),
+        (1, "343c43a37d37dff08ae8c4a11544c718abb4fcf8"),
+        (2, "f778b86fa74e846c4f0a1fbd1335fe81c00a0c91"),
+        (3, "fffd933a0bc612844eaf0c6fe3e5b8e9b6c1d19c"),
+    ];
+
+    for (nonce, address) in expected {
+        assert_eq!(hex::encode(Codegen::create_address(deployer, nonce)), address);
+    }
+}
@@ -0,0 +1,18 @@
+use huff_codegen::Codegen;
+use huff_utils::bytes_util::*;
+
+#[test]
+fn test_delegation_designator() {
+    let address: [u8; 20] =
+        str_to_vec("6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0").unwrap().try_into().unwrap();
+
+    let designator = Codegen::delegation_designator(address);
+    assert!(designator.starts_with("ef0100"));
+    assert_eq!(designator, "ef01006ac7ea33f8831ea9dcc53393aaa88b25a785dbf0");
+    assert_eq!(designator.len(), 23 * 2);
+
+    // Addresses of the wrong length can't be passed
+    let short: Result<[u8; 20], _> =
+        str_to_vec("6ac7ea33f8831ea9dcc53393aaa88b25a785db").unwrap().try_into();
+    assert!(short.is_err());
+}
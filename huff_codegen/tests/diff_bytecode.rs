@@ -0,0 +1,29 @@
+use huff_codegen::*;
+use huff_utils::prelude::*;
+
+#[test]
+fn test_diff_bytecode() {
+    // PUSH1 0x04 CALLDATALOAD PUSH2 0x0100 ADD PUSH0 MSTORE
+    let old = "600435610100015f52";
+    let new = "0x600435610200015f52";
+
+    let diff = Codegen::diff_bytecode(old, new).unwrap().unwrap();
+    assert_eq!(diff.offset, 3);
+    assert_eq!(diff.old, Some(Instruction { offset: 3, text: "PUSH2 0x0100".to_string() }));
+    assert_eq!(diff.new, Some(Instruction { offset: 3, text: "PUSH2 0x0200".to_string() }));
+    assert_eq!(diff.to_string(), "PUSH2 0x0100 became PUSH2 0x0200 at offset 0x03");
+}
+
+#[test]
+fn test_diff_bytecode_identical() {
+    assert_eq!(Codegen::diff_bytecode("600435", "0x600435").unwrap(), None);
+}
+
+#[test]
+fn test_diff_bytecode_truncated() {
+    let diff = Codegen::diff_bytecode("6004355f52", "600435").unwrap().unwrap();
+    assert_eq!(diff.offset, 3);
+    assert_eq!(diff.old, Some(Instruction { offset: 3, text: "PUSH0".to_string() }));
+    assert_eq!(diff.new, None);
+    assert_eq!(diff.to_string(), "PUSH0 became end of bytecode at offset 0x03");
+}
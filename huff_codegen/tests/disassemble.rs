@@ -0,0 +1,34 @@
+use huff_codegen::*;
+use huff_utils::prelude::*;
+
+#[test]
+fn test_disassemble_bytecode() {
+    // PUSH1 0x08 DUP1 PUSH1 0x09 RETURNDATASIZE CODECOPY RETURNDATASIZE RETURN PUSH0 PUSH2 0xbeef
+    let instructions = Codegen::disassemble("0x60088060093d393df35f61beef").unwrap();
+    assert_eq!(
+        instructions,
+        vec![
+            (0, Opcode::Push1, Some(vec![0x08])),
+            (2, Opcode::Dup1, None),
+            (3, Opcode::Push1, Some(vec![0x09])),
+            (5, Opcode::Returndatasize, None),
+            (6, Opcode::Codecopy, None),
+            (7, Opcode::Returndatasize, None),
+            (8, Opcode::Return, None),
+            (9, Opcode::Push0, None),
+            (10, Opcode::Push2, Some(vec![0xbe, 0xef])),
+        ]
+    );
+}
+
+#[test]
+fn test_disassemble_truncated_push_data() {
+    let err = Codegen::disassemble("60016101").unwrap_err();
+    assert_eq!(err.kind, CodegenErrorKind::TruncatedPushData(2));
+}
+
+#[test]
+fn test_disassemble_unknown_opcode() {
+    let err = Codegen::disassemble("60010c").unwrap_err();
+    assert_eq!(err.kind, CodegenErrorKind::UnknownOpcode(2, 0x0c));
+}
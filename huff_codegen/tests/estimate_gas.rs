@@ -0,0 +1,29 @@
+mod common;
+
+use common::parse;
+use huff_codegen::*;
+use huff_utils::prelude::*;
+
+#[test]
+fn test_estimate_gas() {
+    let source = r#"
+        #define macro MAIN() = takes(0) returns(0) {
+            0x04 calldataload   // PUSH1 (3) + CALLDATALOAD (3)
+            0x01 add            // PUSH1 (3) + ADD (3)
+            0x00 mstore         // PUSH0 (2) + MSTORE (3)
+            0x20 0x00 return    // PUSH1 (3) + PUSH0 (2) + RETURN (0)
+        }
+    "#;
+
+    let contract = parse(source);
+
+    let bytecode =
+        Codegen::generate_main_bytecode(&EVMVersion::default(), &contract, None).unwrap();
+    assert_eq!(Codegen::estimate_gas(&bytecode).unwrap(), 22);
+}
+
+#[test]
+fn test_estimate_gas_unknown_opcode() {
+    let err = Codegen::estimate_gas("60010c").unwrap_err();
+    assert_eq!(err.kind, CodegenErrorKind::UnknownOpcode(2, 0x0c));
+}
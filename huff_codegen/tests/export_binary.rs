@@ -0,0 +1,72 @@
+use std::fs;
+
+use huff_codegen::*;
+use huff_utils::prelude::*;
+
+#[test]
+fn test_export_binary_writes_raw_bytes() {
+    let dir = std::env::temp_dir().join(format!("huff_export_binary_{}", std::process::id()));
+    let output = dir.join("nested").join("runtime.bin");
+
+    Codegen::export_binary(output.to_string_lossy().to_string(), "0x60016002015f5260205ff3")
+        .unwrap();
+    assert_eq!(
+        fs::read(&output).unwrap(),
+        vec![0x60, 0x01, 0x60, 0x02, 0x01, 0x5f, 0x52, 0x60, 0x20, 0x5f, 0xf3]
+    );
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_export_binary_rejects_invalid_hex() {
+    let dir = std::env::temp_dir().join(format!("huff_export_binary_bad_{}", std::process::id()));
+    let output = dir.join("runtime.bin");
+
+    for bytecode in ["60016", "6001zz"] {
+        let err =
+            Codegen::export_binary(output.to_string_lossy().to_string(), bytecode).unwrap_err();
+        assert_eq!(err.kind, CodegenErrorKind::InvalidHex(bytecode.to_string()));
+    }
+
+    // Nothing is written when the bytecode is invalid
+    assert!(!output.exists());
+}
+
+#[test]
+fn test_export_artifact_compact_and_pretty() {
+    let dir = std::env::temp_dir().join(format!("huff_export_artifact_{}", std::process::id()));
+    let art = Artifact { bytecode: "60016002".to_string(), ..Default::default() };
+
+    let compact = dir.join("compact.json");
+    Codegen::export(compact.to_string_lossy().to_string(), &art, false).unwrap();
+    let compact = fs::read_to_string(compact).unwrap();
+    assert!(!compact.contains('\n'));
+
+    let pretty = dir.join("pretty.json");
+    Codegen::export(pretty.to_string_lossy().to_string(), &art, true).unwrap();
+    let pretty = fs::read_to_string(pretty).unwrap();
+    assert!(pretty.contains('\n'));
+
+    let parsed: serde_json::Value = serde_json::from_str(&compact).unwrap();
+    assert_eq!(parsed, serde_json::from_str::<serde_json::Value>(&pretty).unwrap());
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_export_artifact_to_invalid_path_errors() {
+    let dir = std::env::temp_dir().join(format!("huff_export_artifact_bad_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+
+    // A regular file can't be used as the parent directory of the artifact
+    let blocker = dir.join("blocker");
+    fs::write(&blocker, "").unwrap();
+    let output = blocker.join("artifact.json");
+
+    let err = Codegen::export(output.to_string_lossy().to_string(), &Artifact::default(), true)
+        .unwrap_err();
+    assert!(matches!(err.kind, CodegenErrorKind::IOError(_)));
+
+    fs::remove_dir_all(dir).unwrap();
+}
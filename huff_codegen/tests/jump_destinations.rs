@@ -0,0 +1,36 @@
+use huff_codegen::Codegen;
+use huff_utils::prelude::*;
+
+#[test]
+fn test_misaligned_table_entry() {
+    // PUSH2 0x005b JUMPDEST, followed by a packed jump table
+    let code = "61005b5b";
+
+    // The `5b` at offset 2 is push data, only offset 3 is a jumpdest
+    let err =
+        Codegen::check_jump_destinations(&format!("{code}0002"), 0..4, &[(4, 2)]).unwrap_err();
+    assert_eq!(err.kind, CodegenErrorKind::InvalidJumpDestination { offset: 2 });
+
+    Codegen::check_jump_destinations(&format!("{code}0003"), 0..4, &[(4, 2)]).unwrap();
+}
+
+#[test]
+fn test_pushed_jump_targets() {
+    let check = |source: &str| {
+        Codegen::compile_str(
+            &format!("#define macro MAIN() = takes(0) returns(0) {{ {source} }}"),
+            vec![],
+        )
+    };
+
+    // Label jumps always land on their jumpdest
+    check("label jump label: 0x00 0x00 return").unwrap();
+
+    // 0x04 is the operand of the `PUSH1 0x5b`, not a jumpdest
+    match check("0x04 jump 0x5b") {
+        Err(CompilerError::CodegenError(e)) => {
+            assert_eq!(e.kind, CodegenErrorKind::InvalidJumpDestination { offset: 4 })
+        }
+        r => panic!("unexpected result: {r:?}"),
+    }
+}
@@ -0,0 +1,34 @@
+use std::{fs, sync::Arc};
+
+use huff_codegen::*;
+use huff_utils::prelude::*;
+
+#[test]
+fn test_load_artifact_round_trips_export() {
+    let source = r#"
+        #define function balanceOf(address) view returns (uint256)
+        #define event Transfer(address indexed, address indexed, uint256)
+        #define macro MAIN() = takes(0) returns(0) { 0x04 calldataload sload }
+    "#;
+    let mut artifact = Codegen::compile_str(source, vec![]).unwrap();
+    let dependency = Arc::new(FileSource { path: "./lib.huff".to_string(), ..Default::default() });
+    artifact.file = Arc::new(FileSource {
+        id: uuid::Uuid::new_v4(),
+        path: "./main.huff".to_string(),
+        source: Some(source.to_string()),
+        access: None,
+        dependencies: Some(vec![dependency]),
+    });
+    artifact.macro_map = Some(MacroMap::from([("MAIN".to_string(), (0, 4))]));
+
+    let dir = std::env::temp_dir().join(format!("huff_load_artifact_{}", std::process::id()));
+    let output = dir.join("artifact.json").to_string_lossy().to_string();
+    for pretty in [true, false] {
+        Codegen::export(output.clone(), &artifact, pretty).unwrap();
+        assert_eq!(Codegen::load_artifact(output.clone()).unwrap(), artifact);
+    }
+    fs::remove_dir_all(dir).unwrap();
+
+    let err = Codegen::load_artifact(output).unwrap_err();
+    assert!(matches!(err.kind, CodegenErrorKind::IOError(_)));
+}
@@ -0,0 +1,16 @@
+use huff_codegen::*;
+use huff_utils::{evm::Opcode, prelude::*};
+
+#[test]
+fn test_opcode_histogram() {
+    let histogram = Codegen::opcode_histogram("6001600201").unwrap();
+    assert_eq!(histogram.len(), 2);
+    assert_eq!(histogram[&Opcode::Push1], 2);
+    assert_eq!(histogram[&Opcode::Add], 1);
+}
+
+#[test]
+fn test_opcode_histogram_invalid_hex() {
+    let err = Codegen::opcode_histogram("600").unwrap_err();
+    assert_eq!(err.kind, CodegenErrorKind::InvalidHex("600".to_string()));
+}
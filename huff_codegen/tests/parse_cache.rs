@@ -0,0 +1,77 @@
+use huff_codegen::*;
+use huff_utils::prelude::*;
+use std::{collections::HashSet, sync::Arc};
+
+fn file(path: &str, source: &str, dependencies: Vec<Arc<FileSource>>) -> Arc<FileSource> {
+    Arc::new(FileSource {
+        path: path.to_string(),
+        source: Some(source.to_string()),
+        dependencies: Some(dependencies),
+        ..Default::default()
+    })
+}
+
+fn project(constant: &str) -> Arc<FileSource> {
+    let dependency = file("./constants.huff", constant, vec![]);
+    file(
+        "./main.huff",
+        "#define macro MAIN() = takes(0) returns(0) { [SLOT] sload }",
+        vec![dependency],
+    )
+}
+
+#[test]
+fn test_unchanged_sources_hit_the_cache() {
+    let mut cg = Codegen::new().with_cache(ParseCache::default());
+    let defines = HashSet::new();
+    let free_pointer = "#define constant SLOT = FREE_STORAGE_POINTER()";
+
+    let (mut contract, cached) = cg.parse_file(project(free_pointer), &defines).unwrap();
+    assert!(!cached);
+    contract.derive_storage_pointers();
+    let bytecode =
+        Codegen::generate_main_bytecode(&EVMVersion::default(), &contract, None).unwrap();
+
+    // An identical project loaded again is served from the cache
+    let (mut contract, cached) = cg.parse_file(project(free_pointer), &defines).unwrap();
+    assert!(cached);
+    contract.derive_storage_pointers();
+    assert_eq!(
+        Codegen::generate_main_bytecode(&EVMVersion::default(), &contract, None).unwrap(),
+        bytecode
+    );
+
+    // Changing a dependency or the defines misses the cache
+    let (_, cached) = cg.parse_file(project("#define constant SLOT = 0x01"), &defines).unwrap();
+    assert!(!cached);
+    let defines = HashSet::from(["DEBUG".to_string()]);
+    let (_, cached) = cg.parse_file(project(free_pointer), &defines).unwrap();
+    assert!(!cached);
+
+    // Without a cache every file is parsed
+    let (_, cached) = Codegen::new().parse_file(project(free_pointer), &defines).unwrap();
+    assert!(!cached);
+}
+
+#[test]
+fn test_persisted_cache_reuses_tokens() {
+    let path = std::env::temp_dir().join(format!("huff_parse_cache_{}.json", uuid::Uuid::new_v4()));
+    let source = project("#define constant SLOT = 0x01");
+    let defines = HashSet::new();
+
+    let mut cg = Codegen::new().with_cache(ParseCache::persisted(&path).unwrap());
+    let (contract, _) = cg.parse_file(Arc::clone(&source), &defines).unwrap();
+
+    // A fresh cache loaded from disk holds the lexed tokens, but no parsed contracts
+    let cache = ParseCache::persisted(&path).unwrap();
+    assert!(cache.tokens.contains_key(&ParseCache::key(&source, &defines)));
+    assert!(cache.contracts.is_empty());
+
+    let mut cg = Codegen::new().with_cache(cache);
+    let (reparsed, cached) = cg.parse_file(Arc::clone(&source), &defines).unwrap();
+    assert!(!cached);
+    assert_eq!(reparsed.macros, contract.macros);
+    assert!(cg.parse_file(source, &defines).unwrap().1);
+
+    std::fs::remove_file(path).unwrap();
+}
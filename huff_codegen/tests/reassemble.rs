@@ -0,0 +1,36 @@
+use huff_codegen::*;
+use huff_utils::prelude::*;
+
+#[test]
+fn test_reassemble_round_trips() {
+    let bytecodes = [
+        "60088060093d393df360ff3d5260203df3",
+        "5f35",
+        "7f000000000000000000000000000000000000000000000000000000000000000156",
+        "",
+    ];
+
+    for bytecode in bytecodes {
+        assert_eq!(Codegen::reassemble(bytecode).unwrap(), bytecode);
+    }
+
+    // The 0x prefix and casing are normalized
+    assert_eq!(Codegen::reassemble("0x6001600A01").unwrap(), "6001600a01");
+}
+
+#[test]
+fn test_reassemble_truncated_push_data() {
+    // PUSH2 with only one byte of push data
+    let err = Codegen::reassemble("60016101").unwrap_err();
+    assert_eq!(err.kind, CodegenErrorKind::TruncatedPushData(2));
+
+    // PUSH32 at the very end of the bytecode
+    let err = Codegen::reassemble("60007f").unwrap_err();
+    assert_eq!(err.kind, CodegenErrorKind::TruncatedPushData(2));
+}
+
+#[test]
+fn test_reassemble_invalid_hex() {
+    let err = Codegen::reassemble("60zz").unwrap_err();
+    assert_eq!(err.kind, CodegenErrorKind::InvalidHex("60zz".to_string()));
+}
@@ -0,0 +1,71 @@
+mod common;
+
+use common::parse;
+use huff_codegen::Codegen;
+use huff_utils::prelude::*;
+
+#[test]
+fn test_validate_reports_every_error() {
+    let source: &str = r#"
+        #define constant OWNER = 0x01
+
+        #define macro HELPER() = takes(0) returns(0) {
+            __codesize(GONE)
+        }
+
+        #define macro MAIN() = takes(0) returns(0) {
+            [MISSING] sload
+            UNDEFINED()
+            nowhere jump
+            [OWNER] HELPER()
+        }
+    "#;
+    let contract = parse(source);
+
+    // Code generation stops at the first error...
+    let err = Codegen::generate_main_bytecode(&EVMVersion::default(), &contract, None).unwrap_err();
+    assert_eq!(err.kind, CodegenErrorKind::MissingConstantDefinition("MISSING".to_string()));
+
+    // ...while validation reports all of them
+    let kinds = Codegen::validate(&contract).into_iter().map(|e| e.kind).collect::<Vec<_>>();
+    assert_eq!(
+        kinds,
+        vec![
+            CodegenErrorKind::MissingMacroDefinition("GONE".to_string()),
+            CodegenErrorKind::MissingConstantDefinition("MISSING".to_string()),
+            CodegenErrorKind::InvalidMacroInvocation("UNDEFINED".to_string()),
+            CodegenErrorKind::UnmatchedJumpLabel,
+        ]
+    );
+}
+
+#[test]
+fn test_validate_reports_every_duplicate() {
+    let source: &str = r#"
+        #define constant A = 0x01
+        #define constant A = 0x02
+        #define jumptable TABLE { done }
+        #define jumptable TABLE { done missing }
+        #define macro MAIN() = takes(0) returns(0) { done jump done: }
+    "#;
+    let contract = parse(source);
+
+    let errors = Codegen::validate(&contract);
+    assert_eq!(errors.len(), 3);
+    assert!(matches!(
+        &errors[0].kind,
+        CodegenErrorKind::DuplicateConstantDefinition { name, .. } if name == "A"
+    ));
+    assert!(matches!(
+        &errors[1].kind,
+        CodegenErrorKind::DuplicateTableDefinition { name, .. } if name == "TABLE"
+    ));
+    assert_eq!(errors[2].kind, CodegenErrorKind::UnmatchedJumpLabel);
+}
+
+#[test]
+fn test_validate_valid_contract() {
+    let source = "#define macro MAIN() = takes(0) returns(0) { done jump done: }";
+    let contract = parse(source);
+    assert!(Codegen::validate(&contract).is_empty());
+}
@@ -1,5 +1,7 @@
 use std::sync::Arc;
 
+use ethers_core::utils::hex;
+use huff_codegen::Codegen;
 use huff_utils::prelude::{Artifact, FileSource, OutputLocation};
 use walkdir::WalkDir;
 
@@ -7,7 +9,7 @@ use walkdir::WalkDir;
 pub fn get_cached_artifacts(
     files: &[Arc<FileSource>],
     out: &OutputLocation,
-    constructor_args: String,
+    constructor_args: &[String],
 ) -> Option<Vec<Arc<Artifact>>> {
     // Check if the file artifacts are already generated the the default "./artifacts/" directory or
     // the specified output dir
@@ -22,7 +24,7 @@ pub fn get_cached_artifacts(
 pub fn resolve_existing_artifacts(
     files: &[Arc<FileSource>],
     output: &OutputLocation,
-    constructor_args: String,
+    constructor_args: &[String],
 ) -> Option<Vec<(Arc<FileSource>, Artifact)>> {
     let mut artifacts: Vec<(Arc<FileSource>, Artifact)> = Vec::new();
 
@@ -56,7 +58,9 @@ pub fn resolve_existing_artifacts(
                 // If we expected compilation, the sources must match
                 match expected {
                     Some(expected_fs) => {
-                        if !artifact.bytecode.ends_with(&constructor_args) {
+                        // Arguments that don't fit the cached constructor need recompiling
+                        let encoded = encode_args(&artifact, constructor_args)?;
+                        if !artifact.bytecode.ends_with(&encoded) {
                             tracing::warn!(target: "core", "Mismatched Constructor Args for Cached Artifact \"{}\"", artifact.file.path);
                             return None;
                         }
@@ -91,3 +95,20 @@ pub fn resolve_existing_artifacts(
         }
     }
 }
+
+/// Hex encodes the constructor arguments against the artifact's constructor parameters
+fn encode_args(artifact: &Artifact, constructor_args: &[String]) -> Option<String> {
+    let inputs = artifact.abi.as_ref().and_then(|abi| abi.constructor.as_ref()).map(|c| &c.inputs);
+    // Constructors derived from the CONSTRUCTOR macro don't declare their types, in which case
+    // the types are guessed like they were when compiling
+    let tokens =
+        Codegen::encode_constructor_args(inputs.map(Vec::as_slice), constructor_args.to_vec())
+            .or_else(|_| Codegen::encode_constructor_args(None, constructor_args.to_vec()))
+            .ok()?;
+    Some(
+        tokens
+            .iter()
+            .map(|tok| hex::encode(ethers_core::abi::encode(std::slice::from_ref(tok))))
+            .collect(),
+    )
+}
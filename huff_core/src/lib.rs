@@ -4,7 +4,6 @@
 #![forbid(unsafe_code)]
 #![forbid(where_clauses_object_safety)]
 
-use ethers_core::utils::hex;
 use huff_codegen::*;
 use huff_lexer::*;
 use huff_parser::*;
@@ -18,7 +17,7 @@ use huff_utils::{
 #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
 use rayon::prelude::*;
 use std::{
-    collections::{BTreeMap, HashMap},
+    collections::{BTreeMap, HashMap, HashSet},
     ffi::OsString,
     fs,
     iter::Iterator,
@@ -74,12 +73,22 @@ pub struct Compiler<'a, 'l> {
     pub construct_args: Option<Vec<String>>,
     /// Constant Overrides
     pub constant_overrides: Option<BTreeMap<&'a str, Literal>>,
+    /// Names defined for `#ifdef` conditionals
+    pub defines: HashSet<String>,
     /// Whether to optimize compilation or not.
     pub optimize: bool,
+    /// Whether to fail compilation on warnings
+    pub deny_warnings: bool,
+    /// Whether entry point macros may declare a stack signature other than `takes(0) returns(0)`
+    pub lenient_entry_points: bool,
+    /// Whether test macros invoked from other macros are compiled into the bytecode
+    pub include_tests: bool,
     /// Generate and log bytecode
     pub bytecode: bool,
     /// Whether to check cached artifacts
     pub cached: bool,
+    /// Whether to include the macro map in artifacts
+    pub macro_map: bool,
     /// The implementation of a FileReader
     pub file_provider: Arc<dyn FileProvider<'a>>,
 }
@@ -109,8 +118,13 @@ impl<'a, 'l> Compiler<'a, 'l> {
             alternative_constructor,
             construct_args,
             constant_overrides,
+            defines: HashSet::new(),
             optimize: false,
+            deny_warnings: false,
+            lenient_entry_points: false,
+            include_tests: false,
             bytecode: false,
+            macro_map: false,
             cached,
             file_provider: Arc::new(FileSystemFileProvider {}),
         }
@@ -140,8 +154,13 @@ impl<'a, 'l> Compiler<'a, 'l> {
             alternative_constructor,
             construct_args,
             constant_overrides,
+            defines: HashSet::new(),
             optimize: false,
+            deny_warnings: false,
+            lenient_entry_points: false,
+            include_tests: false,
             bytecode: false,
+            macro_map: false,
             cached: false,
             file_provider: Arc::new(InMemoryFileProvider::new(file_sources)),
         }
@@ -206,16 +225,12 @@ impl<'a, 'l> Compiler<'a, 'l> {
 
         let mut artifacts: Vec<Arc<Artifact>> = vec![];
 
-        // Get our constructor arguments as a hex encoded string to compare to the cache
-        let inputs = self.get_constructor_args();
-        let encoded_inputs = Codegen::encode_constructor_args(inputs);
-        let encoded: Vec<Vec<u8>> =
-            encoded_inputs.iter().map(|tok| ethers_core::abi::encode(&[tok.clone()])).collect();
-        let constructor_args = encoded.iter().map(|tok| hex::encode(tok.as_slice())).collect();
+        // Get our constructor arguments to compare to the cache
+        let constructor_args = self.get_constructor_args();
 
         // Get Cached or Generate Artifacts
         tracing::debug!(target: "core", "Output directory: {}", output.0);
-        match cache::get_cached_artifacts(&files, &output, constructor_args) {
+        match cache::get_cached_artifacts(&files, &output, &constructor_args) {
             Some(arts) => artifacts = arts,
             None => {
                 tracing::debug!(target: "core", "FINISHED RECURSING DEPENDENCIES!");
@@ -344,10 +359,13 @@ impl<'a, 'l> Compiler<'a, 'l> {
 
                 // Perform Lexical Analysis
                 // Create a new lexer from the FileSource, flattening dependencies
-                let lexer = Lexer::new(full_source.source);
+                let lexer = Lexer::new(full_source.source).with_defines(self.defines.clone());
 
                 // Grab the tokens from the lexer
-                let tokens = lexer.into_iter().map(|x| x.unwrap()).collect::<Vec<Token>>();
+                let tokens = lexer
+                    .into_iter()
+                    .collect::<Result<Vec<Token>, LexicalError>>()
+                    .map_err(CompilerError::LexicalError)?;
                 tracing::info!(target: "core", "LEXICAL ANALYSIS COMPLETE FOR \"{}\"", file.path);
                 tracing::info!(target: "core", "└─ TOKEN COUNT: {}", tokens.len());
 
@@ -382,10 +400,13 @@ impl<'a, 'l> Compiler<'a, 'l> {
 
         // Perform Lexical Analysis
         // Create a new lexer from the FileSource, flattening dependencies
-        let lexer = Lexer::new(full_source.source);
+        let lexer = Lexer::new(full_source.source).with_defines(self.defines.clone());
 
         // Grab the tokens from the lexer
-        let tokens = lexer.into_iter().map(|x| x.unwrap()).collect::<Vec<Token>>();
+        let tokens = lexer
+            .into_iter()
+            .collect::<Result<Vec<Token>, LexicalError>>()
+            .map_err(CompilerError::LexicalError)?;
         tracing::info!(target: "core", "LEXICAL ANALYSIS COMPLETE FOR \"{}\"", file.path);
         tracing::info!(target: "core", "└─ TOKEN COUNT: {}", tokens.len());
 
@@ -398,9 +419,49 @@ impl<'a, 'l> Compiler<'a, 'l> {
         contract.derive_storage_pointers();
         contract.add_override_constants(&self.constant_overrides);
         tracing::info!(target: "core", "PARSED CONTRACT [{}]", file.path);
+        if let Err(e) = contract.check_duplicate_definitions() {
+            tracing::error!(target: "core", "Duplicate Definition Check Failed with CodegenError: {:?}", e.kind);
+            return Err(CompilerError::CodegenError(e))
+        }
+        if let Err(e) = contract.check_storage_slots() {
+            tracing::error!(target: "core", "Storage Slot Check Failed with CodegenError: {:?}", e.kind);
+            return Err(CompilerError::CodegenError(e))
+        }
+
+        // Optimization Passes
+        if self.optimize {
+            contract.fold_constants();
+            contract.coalesce_memory_clears(self.evm_version);
+            tracing::info!(target: "core", "OPTIMIZED CONTRACT [{}]", file.path);
+        }
+
+        // Analysis Passes
+        let mut cg = Codegen {
+            deny_warnings: self.deny_warnings,
+            lenient_entry_points: self.lenient_entry_points,
+            include_tests: self.include_tests,
+            ..Codegen::new()
+        };
+        if let Err(e) = cg.lint(&contract) {
+            tracing::error!(target: "core", "Lint Failed with CodegenError: {:?}", e.kind);
+            return Err(CompilerError::CodegenError(e))
+        }
+        if let Err(e) = cg.check_entry_signatures(
+            &contract,
+            self.alternative_main.as_deref(),
+            self.alternative_constructor.as_deref(),
+        ) {
+            tracing::error!(target: "core", "Entry Point Check Failed with CodegenError: {:?}", e.kind);
+            return Err(CompilerError::CodegenError(e))
+        }
+        if let Err(e) = Codegen::check_view_macros(self.evm_version, &contract) {
+            tracing::error!(target: "core", "View Check Failed with CodegenError: {:?}", e.kind);
+            return Err(CompilerError::CodegenError(e))
+        }
+        cg.resolve_test_invocations(&mut contract);
+        cg.resolve_push_literals(&mut contract);
 
         // Primary Bytecode Generation
-        let mut cg = Codegen::new();
         let main_bytecode = match Codegen::generate_main_bytecode(
             self.evm_version,
             &contract,
@@ -420,12 +481,32 @@ impl<'a, 'l> Compiler<'a, 'l> {
                         })
                         .collect::<Vec<Span>>(),
                 );
+                for (_, span) in e.call_stack.iter_mut() {
+                    span.0.iter_mut().for_each(|s| s.file = Some(Arc::clone(&file)));
+                }
                 tracing::error!(target: "core", "Roll Failed with CodegenError: {:?}", e.kind);
                 return Err(CompilerError::CodegenError(e));
             }
         };
         tracing::info!(target: "core", "MAIN BYTECODE GENERATED [{}]", main_bytecode);
 
+        // Macro Map Generation
+        let macro_map = if self.macro_map {
+            match Codegen::generate_macro_map(
+                self.evm_version,
+                &contract,
+                self.alternative_main.clone(),
+            ) {
+                Ok(mm) => Some(mm),
+                Err(e) => {
+                    tracing::error!(target: "core", "Macro Map Failed with CodegenError: {:?}", e.kind);
+                    return Err(CompilerError::CodegenError(e))
+                }
+            }
+        } else {
+            None
+        };
+
         // Generate Constructor Bytecode
         let inputs = self.get_constructor_args();
         let (constructor_bytecode, has_custom_bootstrap) =
@@ -453,6 +534,9 @@ impl<'a, 'l> Compiler<'a, 'l> {
                             .collect::<Vec<Span>>();
                         errs.dedup();
                         e.span = AstSpan(errs);
+                        for (_, span) in e.call_stack.iter_mut() {
+                            span.0.iter_mut().for_each(|s| s.file = Some(Arc::clone(&file)));
+                        }
                         tracing::error!(target: "codegen", "Constructor inputs provided, but contract missing \"CONSTRUCTOR\" macro!");
                         return Err(CompilerError::CodegenError(e));
                     }
@@ -464,8 +548,13 @@ impl<'a, 'l> Compiler<'a, 'l> {
             };
         tracing::info!(target: "core", "CONSTRUCTOR BYTECODE GENERATED [{}]", constructor_bytecode);
 
-        // Encode Constructor Arguments
-        let encoded_inputs = Codegen::encode_constructor_args(inputs);
+        // Encode Constructor Arguments against the constructor's declared parameters
+        let constructor = Constructor::declared(&contract);
+        let encoded_inputs = Codegen::encode_constructor_args(
+            constructor.as_ref().map(|c| c.inputs.as_slice()),
+            inputs,
+        )
+        .map_err(CompilerError::CodegenError)?;
         tracing::info!(target: "core", "ENCODED {} INPUTS", encoded_inputs.len());
 
         // Generate Artifact with ABI
@@ -478,6 +567,7 @@ impl<'a, 'l> Compiler<'a, 'l> {
         );
         match churn_res {
             Ok(mut artifact) => {
+                artifact.macro_map = macro_map;
                 // Then we can have the code gen output the artifact
                 let abiout = cg.abi_gen(contract, None);
                 match abiout {
@@ -507,12 +597,33 @@ impl<'a, 'l> Compiler<'a, 'l> {
     }
 
     /// Recurses file dependencies
+    ///
+    /// A file that ends up including itself results in a `CircularInclude` error listing the
+    /// include cycle.
     pub fn recurse_deps(
         fs: Arc<FileSource>,
         remapper: &Remapper,
         reader: Arc<dyn FileProvider<'a>>,
+    ) -> Result<Arc<FileSource>, Arc<CompilerError>> {
+        Self::recurse_deps_within(fs, remapper, reader, &[])
+    }
+
+    /// Recurses file dependencies of a file included through the `includers` chain
+    fn recurse_deps_within(
+        fs: Arc<FileSource>,
+        remapper: &Remapper,
+        reader: Arc<dyn FileProvider<'a>>,
+        includers: &[String],
     ) -> Result<Arc<FileSource>, Arc<CompilerError>> {
         tracing::debug!(target: "core", "RECURSING DEPENDENCIES FOR {}", fs.path);
+        if let Some(pos) = includers.iter().position(|path| *path == fs.path) {
+            let mut cycle = includers[pos..].to_vec();
+            cycle.push(fs.path.clone());
+            tracing::error!(target: "core", "CIRCULAR INCLUDE: {:?}", cycle);
+            return Err(Arc::new(CompilerError::CircularInclude(cycle)))
+        }
+        let includers = [includers, std::slice::from_ref(&fs.path)].concat();
+
         let mut new_fs = FileSource { path: fs.path.clone(), ..Default::default() };
         let file_source = if let Some(s) = &fs.source {
             s.clone()
@@ -569,14 +680,16 @@ impl<'a, 'l> Compiler<'a, 'l> {
         // Now that we have all the file sources, we have to recurse and get their source
         file_sources = file_sources
             .into_par_iter()
-            .map(|inner_fs| match Self::recurse_deps(Arc::clone(&inner_fs), remapper, reader.clone()) {
-                Ok(new_fs) => new_fs,
+            .map(|inner_fs| match Self::recurse_deps_within(Arc::clone(&inner_fs), remapper, reader.clone(), &includers) {
+                Ok(new_fs) => Ok(new_fs),
+                // An include cycle can never be resolved, so it fails the whole resolution
+                Err(e) if matches!(*e, CompilerError::CircularInclude(_)) => Err(e),
                 Err(e) => {
                     tracing::error!(target: "core", "NESTED DEPENDENCY RESOLUTION FAILED: \"{:?}\"", e);
-                    Arc::clone(&inner_fs)
+                    Ok(Arc::clone(&inner_fs))
                 }
             })
-            .collect();
+            .collect::<Result<_, _>>()?;
 
         // Finally set the parent deps
         new_fs.dependencies = Some(file_sources);
@@ -0,0 +1,47 @@
+mod common;
+
+use common::parse;
+use huff_utils::prelude::*;
+
+fn abi_json(source: &str) -> String {
+    serde_json::to_string(&Abi::from(parse(source))).unwrap()
+}
+
+#[test]
+fn test_abi_order_is_independent_of_source_order() {
+    let source = r#"
+        #define function transfer(address,uint256) nonpayable returns (bool)
+        #define function balanceOf(address) view returns (uint256)
+        #define function transfer(address) nonpayable returns (bool)
+        #define event Transfer(address indexed,address indexed,uint256)
+        #define event Approval(address indexed,address indexed,uint256)
+        #define error Unauthorized(address)
+        #define error Overflow()
+    "#;
+    let reordered = r#"
+        #define error Overflow()
+        #define event Approval(address indexed,address indexed,uint256)
+        #define function transfer(address) nonpayable returns (bool)
+        #define error Unauthorized(address)
+        #define function balanceOf(address) view returns (uint256)
+        #define event Transfer(address indexed,address indexed,uint256)
+        #define function transfer(address,uint256) nonpayable returns (bool)
+    "#;
+
+    assert_eq!(abi_json(source), abi_json(reordered));
+}
+
+#[test]
+fn test_abi_keys_overloads_by_signature() {
+    let source = r#"
+        #define function transfer(address,uint256) nonpayable returns (bool)
+        #define function transfer(address) nonpayable returns (bool)
+        #define function balanceOf(address) view returns (uint256)
+    "#;
+    let abi = Abi::from(parse(source));
+
+    assert_eq!(
+        abi.functions.keys().cloned().collect::<Vec<String>>(),
+        vec!["balanceOf", "transfer(address)", "transfer(address,uint256)"]
+    );
+}
@@ -32,3 +32,32 @@ fn test_alternative_main_macro_provided() {
         Err(_) => panic!("moose"),
     }
 }
+
+#[test]
+fn test_parameterized_alternative_main_macro() {
+    let source = r#"
+    #define macro MAIN() = takes(0) returns(0) {
+        MINT(0x04)
+    }
+
+    #define macro MINT(offset) = takes(0) returns (0) {
+        <offset> calldataload
+    }
+    "#;
+
+    let full_source = FullFileSource { source, file: None, spans: vec![] };
+    let lexer = Lexer::new(full_source.source);
+    let tokens = lexer.into_iter().map(|x| x.unwrap()).collect::<Vec<Token>>();
+    let mut parser = Parser::new(tokens, Some("".to_string()));
+    let mut contract = parser.parse().unwrap();
+    contract.derive_storage_pointers();
+
+    // The macro can be invoked with arguments, but not compiled as an entry point
+    assert!(Codegen::generate_main_bytecode(&EVMVersion::default(), &contract, None).is_ok());
+
+    let alternative_main = Some(String::from("MINT"));
+    let err = Codegen::generate_main_bytecode(&EVMVersion::default(), &contract, alternative_main)
+        .unwrap_err();
+    assert_eq!(err.kind, CodegenErrorKind::ParameterizedEntryPoint("MINT".to_string()));
+    assert_eq!(err.span, contract.find_macro_by_name("MINT").unwrap().span);
+}
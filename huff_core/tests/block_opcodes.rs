@@ -0,0 +1,53 @@
+mod common;
+
+use common::parse;
+use huff_codegen::Codegen;
+use huff_utils::prelude::*;
+
+#[test]
+fn test_basefee_on_every_version() {
+    let contract = parse(
+        r#"
+        #define macro MAIN() = takes(0) returns(0) {
+            basefee
+        }
+    "#,
+    );
+
+    for version in ["paris", "shanghai", "cancun"] {
+        let evm_version = EVMVersion::from(version.to_string());
+        let bytecode = Codegen::generate_main_bytecode(&evm_version, &contract, None).unwrap();
+        assert_eq!(bytecode, "48");
+    }
+}
+
+#[test]
+fn test_blob_opcodes_on_cancun() {
+    let contract = parse(
+        r#"
+        #define macro MAIN() = takes(0) returns(0) {
+            0x00 blobhash blobbasefee
+        }
+    "#,
+    );
+
+    let evm_version = EVMVersion::new(SupportedEVMVersions::Cancun);
+    let bytecode = Codegen::generate_main_bytecode(&evm_version, &contract, None).unwrap();
+    assert_eq!(bytecode, "5f494a");
+}
+
+#[test]
+fn test_blob_opcodes_before_cancun_fail() {
+    for (opcode, source) in
+        [(Opcode::Blobhash, "0x00 blobhash"), (Opcode::Blobbasefee, "blobbasefee")]
+    {
+        let contract = parse(&format!("#define macro MAIN() = takes(0) returns(0) {{ {source} }}"));
+
+        let evm_version = EVMVersion::new(SupportedEVMVersions::Shanghai);
+        let err = Codegen::generate_main_bytecode(&evm_version, &contract, None).unwrap_err();
+        assert_eq!(
+            err.kind,
+            CodegenErrorKind::UnsupportedOpcode { opcode, evm_version: "shanghai".to_string() }
+        );
+    }
+}
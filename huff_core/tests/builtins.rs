@@ -83,7 +83,7 @@ fn test_dyn_constructor_arg_builtin() {
         Codegen::generate_constructor_bytecode(evm_version, &contract, None).unwrap();
     let main_code = Codegen::generate_main_bytecode(evm_version, &contract, None).unwrap();
 
-    let args = Codegen::encode_constructor_args(vec![String::from("testing")]);
+    let args = Codegen::encode_constructor_args(None, vec![String::from("testing")]).unwrap();
     let final_bytecode = cg.churn(
         Arc::new(FileSource::default()),
         args,
@@ -543,6 +543,7 @@ fn test_error_selector_builtin() {
         #define macro PANIC() = takes (1) returns (0) {
             // Input stack:          [panic_code]
             __ERROR(PanicError)   // [panic_error_selector, panic_code]
+            0xe0 shl              // [panic_error_selector << 224, panic_code]
             0x00 mstore           // [panic_code]
             0x04 mstore           // []
             0x24 0x00 revert
@@ -553,6 +554,7 @@ fn test_error_selector_builtin() {
             continue jumpi        // [message]
 
             __ERROR("Error(string)")        // [error_selector, message_length, message]
+            0xe0 shl              // [error_selector << 224, message_length, message]
             0x00 mstore           // [message_length, message]
             0x20 0x04 mstore      // [message_length, message]
             0x24 mstore           // [message]
@@ -591,12 +593,13 @@ fn test_error_selector_builtin() {
 
     // Have Codegen create the runtime bytecode
     let r_bytes = Codegen::generate_main_bytecode(&EVMVersion::default(), &contract, None).unwrap();
-    assert_eq!(&r_bytes[2..66], "be20788c00000000000000000000000000000000000000000000000000000000");
-    assert_eq!(&r_bytes[94..102], "08c379a0");
+    // `PanicError(uint256)` selector = 0xbe20788c, `Error(string)` selector = 0x08c379a0
+    assert_eq!(&r_bytes[0..10], "63be20788c");
+    assert_eq!(&r_bytes[42..52], "6308c379a0");
     assert_eq!(
         r_bytes,
         String::from(
-            "7fbe20788c000000000000000000000000000000000000000000000000000000005f5260045260245ffd610044576308c379a05f52602060045260245260445260645ffd5b50"
+            "63be20788c60e01b5f5260045260245ffd61002e576308c379a060e01b5f52602060045260245260445260645ffd5b50"
         )
     );
 }
@@ -648,3 +651,370 @@ fn test_rightpad_builtin() {
         )
     );
 }
+
+#[test]
+fn test_rightpad_single_byte() {
+    let source: &str = r#"
+        #define macro MAIN() = takes (0) returns (0) {
+            __RIGHTPAD(0x61)
+        }
+    "#;
+
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let lexer = Lexer::new(flattened_source.source);
+    let tokens = lexer.into_iter().map(|x| x.unwrap()).collect::<Vec<Token>>();
+    let mut parser = Parser::new(tokens, None);
+    let mut contract = parser.parse().unwrap();
+    contract.derive_storage_pointers();
+
+    let r_bytes = Codegen::generate_main_bytecode(&EVMVersion::default(), &contract, None).unwrap();
+    assert_eq!(r_bytes, format!("7f61{}", "00".repeat(31)));
+}
+
+#[test]
+fn test_rightpad_rejects_oversized_argument() {
+    let source = format!(
+        r#"
+        #define macro MAIN() = takes (0) returns (0) {{
+            __RIGHTPAD("{}")
+        }}
+    "#,
+        "ab".repeat(33)
+    );
+
+    let flattened_source = FullFileSource { source: &source, file: None, spans: vec![] };
+    let lexer = Lexer::new(flattened_source.source);
+    let tokens = lexer.into_iter().map(|x| x.unwrap()).collect::<Vec<Token>>();
+    let mut parser = Parser::new(tokens, None);
+    let mut contract = parser.parse().unwrap();
+    contract.derive_storage_pointers();
+
+    let err = Codegen::generate_main_bytecode(&EVMVersion::default(), &contract, None).unwrap_err();
+    assert_eq!(
+        err.kind,
+        CodegenErrorKind::InvalidArguments(
+            "__RIGHTPAD argument must be at most 32 bytes, got 33".to_string()
+        )
+    );
+}
+
+#[test]
+fn test_shift_builtins() {
+    let source: &str = r#"
+        #define macro MAIN() = takes (0) returns (0) {
+            __SHL(8, 0xff)
+            __SHR(8, 0xff00)
+            __SHL(0xf8, 0x01)
+            __SHR(0x100, 0xff)
+        }
+    "#;
+
+    // Parse tokens
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let lexer = Lexer::new(flattened_source.source);
+    let tokens = lexer.into_iter().map(|x| x.unwrap()).collect::<Vec<Token>>();
+    let mut parser = Parser::new(tokens, None);
+
+    // Parse the AST
+    let mut contract = parser.parse().unwrap();
+
+    // Derive storage pointers
+    contract.derive_storage_pointers();
+
+    // Have Codegen create the runtime bytecode
+    let r_bytes = Codegen::generate_main_bytecode(&EVMVersion::default(), &contract, None).unwrap();
+    assert_eq!(
+        r_bytes,
+        String::from(
+            "61ff0060ff7f01000000000000000000000000000000000000000000000000000000000000005f"
+        )
+    );
+}
+
+#[test]
+fn test_codesize_forward_references() {
+    let source: &str = r#"
+        #define macro MAIN() = takes (0) returns (0) {
+            A()
+            B()
+        }
+
+        // A's size depends on B's, which depends on C's, all defined later
+        #define macro A() = takes (0) returns (0) {
+            __codesize(B) pop
+        }
+
+        #define macro B() = takes (0) returns (0) {
+            __codesize(C) __codesize(A) add pop
+        }
+
+        #define macro C() = takes (0) returns (0) {
+            caller pop caller pop
+        }
+    "#;
+
+    // Parse tokens
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let lexer = Lexer::new(flattened_source.source);
+    let tokens = lexer.into_iter().map(|x| x.unwrap()).collect::<Vec<Token>>();
+    let mut parser = Parser::new(tokens, None);
+
+    // Parse the AST
+    let mut contract = parser.parse().unwrap();
+
+    // Derive storage pointers
+    contract.derive_storage_pointers();
+
+    // A is 3 bytes and B is 6 bytes
+    let r_bytes = Codegen::generate_main_bytecode(&EVMVersion::default(), &contract, None).unwrap();
+    assert_eq!(r_bytes, String::from("600650600460030150"));
+}
+
+#[test]
+fn test_codesize_cycle() {
+    let source: &str = r#"
+        #define macro MAIN() = takes (0) returns (0) {
+            __codesize(A)
+        }
+
+        #define macro A() = takes (0) returns (0) {
+            __codesize(B) pop
+        }
+
+        #define macro B() = takes (0) returns (0) {
+            __codesize(A) pop
+        }
+    "#;
+
+    // Parse tokens
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let lexer = Lexer::new(flattened_source.source);
+    let tokens = lexer.into_iter().map(|x| x.unwrap()).collect::<Vec<Token>>();
+    let mut parser = Parser::new(tokens, None);
+
+    // Parse the AST
+    let mut contract = parser.parse().unwrap();
+
+    // Derive storage pointers
+    contract.derive_storage_pointers();
+
+    // Neither size can be computed without the other
+    let err = Codegen::generate_main_bytecode(&EVMVersion::default(), &contract, None).unwrap_err();
+    assert_eq!(
+        err.kind,
+        CodegenErrorKind::CircularCodesize(vec!["A".to_string(), "B".to_string(), "A".to_string()])
+    );
+}
+
+#[test]
+fn test_codesize_of_current_macro() {
+    let source: &str = r#"
+        #define macro PUSH_OWN_SIZE() = takes (0) returns (1) {
+            __codesize() 0x01 add
+        }
+
+        #define macro PASS_OWN_SIZE() = takes (0) returns (1) {
+            caller pop
+            ADD_ONE(__codesize())
+        }
+
+        #define macro ADD_ONE(value) = takes (0) returns (1) {
+            <value> 0x01 add
+        }
+
+        #define macro MAIN() = takes (0) returns (0) {
+            PUSH_OWN_SIZE() pop
+            PASS_OWN_SIZE() pop
+            __codesize(PUSH_OWN_SIZE) pop
+        }
+    "#;
+
+    // Parse tokens
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let lexer = Lexer::new(flattened_source.source);
+    let tokens = lexer.into_iter().map(|x| x.unwrap()).collect::<Vec<Token>>();
+    let mut parser = Parser::new(tokens, None);
+
+    // Parse the AST
+    let mut contract = parser.parse().unwrap();
+
+    // Derive storage pointers
+    contract.derive_storage_pointers();
+
+    // PUSH_OWN_SIZE is 5 bytes and PASS_OWN_SIZE is 7 bytes, passing its own size to the
+    // invoked macro
+    let r_bytes = Codegen::generate_main_bytecode(&EVMVersion::default(), &contract, None).unwrap();
+    assert_eq!(r_bytes, String::from("6005600101503350600760010150600550"));
+}
+
+#[test]
+fn test_calldata_arg_builtin() {
+    let source: &str = r#"
+        #define macro MAIN() = takes (0) returns (0) {
+            __CALLDATA_ARG(0)
+            __CALLDATA_ARG(0x02)
+        }
+    "#;
+
+    // Parse tokens
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let lexer = Lexer::new(flattened_source.source);
+    let tokens = lexer.into_iter().map(|x| x.unwrap()).collect::<Vec<Token>>();
+    let mut parser = Parser::new(tokens, None);
+
+    // Parse the AST
+    let mut contract = parser.parse().unwrap();
+
+    // Derive storage pointers
+    contract.derive_storage_pointers();
+
+    // PUSH1 0x04 CALLDATALOAD PUSH1 0x44 CALLDATALOAD
+    let r_bytes = Codegen::generate_main_bytecode(&EVMVersion::default(), &contract, None).unwrap();
+    assert_eq!(r_bytes, String::from("600435604435"));
+
+    // The index must be a compile-time literal
+    let source: &str = r#"
+        #define macro MAIN() = takes (0) returns (0) {
+            __CALLDATA_ARG(index)
+        }
+    "#;
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let lexer = Lexer::new(flattened_source.source);
+    let tokens = lexer.into_iter().map(|x| x.unwrap()).collect::<Vec<Token>>();
+    let mut parser = Parser::new(tokens, None);
+    let mut contract = parser.parse().unwrap();
+    contract.derive_storage_pointers();
+    let err = Codegen::generate_main_bytecode(&EVMVersion::default(), &contract, None).unwrap_err();
+    assert_eq!(err.kind, CodegenErrorKind::InvalidHex("index".to_string()));
+}
+
+#[test]
+fn test_func_sig_builtin_undeclared() {
+    // A full signature doesn't require a function definition
+    let source: &str = r#"
+        #define macro MAIN() = takes (0) returns (0) {
+            __FUNC_SIG("transfer(address,uint)")
+        }
+    "#;
+
+    // Parse tokens
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let lexer = Lexer::new(flattened_source.source);
+    let tokens = lexer.into_iter().map(|x| x.unwrap()).collect::<Vec<Token>>();
+    let mut parser = Parser::new(tokens, None);
+
+    // Parse the AST
+    let mut contract = parser.parse().unwrap();
+
+    // Derive storage pointers
+    contract.derive_storage_pointers();
+
+    // `uint` is normalized to `uint256` before hashing
+    let r_bytes = Codegen::generate_main_bytecode(&EVMVersion::default(), &contract, None).unwrap();
+    assert_eq!(r_bytes, String::from("63a9059cbb"));
+
+    // A bare name must match a declared function
+    let source: &str = r#"
+        #define macro MAIN() = takes (0) returns (0) {
+            __FUNC_SIG(transfer)
+        }
+    "#;
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let lexer = Lexer::new(flattened_source.source);
+    let tokens = lexer.into_iter().map(|x| x.unwrap()).collect::<Vec<Token>>();
+    let mut parser = Parser::new(tokens, None);
+    let mut contract = parser.parse().unwrap();
+    contract.derive_storage_pointers();
+    let err = Codegen::generate_main_bytecode(&EVMVersion::default(), &contract, None).unwrap_err();
+    assert_eq!(err.kind, CodegenErrorKind::MissingFunctionInterface("transfer".to_string()));
+    assert!(!err.span.0.is_empty());
+}
+
+#[test]
+fn test_event_hash_builtin_indexed() {
+    let source: &str = r#"
+        #define event Transfer(address indexed from, address indexed to, uint256 value)
+
+        #define macro MAIN() = takes (0) returns (0) {
+            __EVENT_HASH(Transfer)
+            __EVENT_HASH("Transfer(address,address,uint)")
+        }
+    "#;
+
+    // Parse tokens
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let lexer = Lexer::new(flattened_source.source);
+    let tokens = lexer.into_iter().map(|x| x.unwrap()).collect::<Vec<Token>>();
+    let mut parser = Parser::new(tokens, None);
+
+    // Parse the AST
+    let mut contract = parser.parse().unwrap();
+
+    // Derive storage pointers
+    contract.derive_storage_pointers();
+
+    // Indexed parameters don't change the canonical signature
+    let topic = "ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef";
+    let r_bytes = Codegen::generate_main_bytecode(&EVMVersion::default(), &contract, None).unwrap();
+    assert_eq!(r_bytes, format!("7f{topic}7f{topic}"));
+
+    // An unknown event name is an error rather than a hash of the bare name
+    let source: &str = r#"
+        #define macro MAIN() = takes (0) returns (0) {
+            __EVENT_HASH(Transfer)
+        }
+    "#;
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let lexer = Lexer::new(flattened_source.source);
+    let tokens = lexer.into_iter().map(|x| x.unwrap()).collect::<Vec<Token>>();
+    let mut parser = Parser::new(tokens, None);
+    let mut contract = parser.parse().unwrap();
+    contract.derive_storage_pointers();
+    let err = Codegen::generate_main_bytecode(&EVMVersion::default(), &contract, None).unwrap_err();
+    assert_eq!(err.kind, CodegenErrorKind::MissingEventInterface("Transfer".to_string()));
+    assert!(!err.span.0.is_empty());
+}
+
+#[test]
+fn test_error_selector_builtin_undeclared() {
+    // A full signature doesn't require an error definition
+    let source: &str = r#"
+        #define macro MAIN() = takes (0) returns (0) {
+            __ERROR("InsufficientBalance(uint,uint)")
+        }
+    "#;
+
+    // Parse tokens
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let lexer = Lexer::new(flattened_source.source);
+    let tokens = lexer.into_iter().map(|x| x.unwrap()).collect::<Vec<Token>>();
+    let mut parser = Parser::new(tokens, None);
+
+    // Parse the AST
+    let mut contract = parser.parse().unwrap();
+
+    // Derive storage pointers
+    contract.derive_storage_pointers();
+
+    // `InsufficientBalance(uint256,uint256)` selector = 0xcf479181
+    let r_bytes = Codegen::generate_main_bytecode(&EVMVersion::default(), &contract, None).unwrap();
+    assert_eq!(r_bytes, String::from("63cf479181"));
+
+    // An unknown error name is an error rather than a hash of the bare name
+    let source: &str = r#"
+        #define macro MAIN() = takes (0) returns (0) {
+            __ERROR(InsufficientBalance)
+        }
+    "#;
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let lexer = Lexer::new(flattened_source.source);
+    let tokens = lexer.into_iter().map(|x| x.unwrap()).collect::<Vec<Token>>();
+    let mut parser = Parser::new(tokens, None);
+    let mut contract = parser.parse().unwrap();
+    contract.derive_storage_pointers();
+    let err = Codegen::generate_main_bytecode(&EVMVersion::default(), &contract, None).unwrap_err();
+    assert_eq!(
+        err.kind,
+        CodegenErrorKind::MissingErrorDefinition("InsufficientBalance".to_string())
+    );
+}
@@ -0,0 +1,46 @@
+mod common;
+
+use common::parse;
+use huff_codegen::Codegen;
+use huff_utils::prelude::*;
+
+const SOURCE: &str = r#"
+    #define constant WETH = 0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2
+
+    #define macro MAIN() = takes(0) returns(0) {
+        0x00 0x00 0x00 0x00 0x00
+        0xd8da6bf26964af9d7eed9e03e53415d37aa96045 gas call
+        0x00 0x00 0x00 0x00
+        [WETH] 0xffff staticcall
+        // Not a call target
+        0x1111111111111111111111111111111111111111 pop
+    }
+"#;
+
+#[test]
+fn test_call_targets_are_opt_in() {
+    let contract = parse(SOURCE);
+
+    let mut cg = Codegen::new();
+    cg.lint(&contract).unwrap();
+    assert!(cg.warnings.is_empty());
+}
+
+#[test]
+fn test_call_targets_are_reported() {
+    let contract = parse(SOURCE);
+
+    let mut cg = Codegen { lint_addresses: true, ..Codegen::new() };
+    cg.lint(&contract).unwrap();
+
+    assert_eq!(cg.warnings.len(), 2);
+    assert_eq!(
+        cg.warnings[0].kind,
+        CodegenWarningKind::CallTarget("0xd8da6bf26964af9d7eed9e03e53415d37aa96045".to_string())
+    );
+    assert_eq!(cg.warnings[0].span.0[0].start, SOURCE.find("0xd8da6b").unwrap());
+    assert_eq!(
+        cg.warnings[1].kind,
+        CodegenWarningKind::CallTarget("0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2".to_string())
+    );
+}
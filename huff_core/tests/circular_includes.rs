@@ -0,0 +1,49 @@
+use std::{collections::HashMap, sync::Arc};
+
+use huff_core::Compiler;
+use huff_utils::prelude::{CompilerError, EVMVersion};
+
+#[test]
+fn test_circular_include() {
+    let source_a = r#"
+    #include "./b.huff"
+
+    #define macro MAIN() = takes(0) returns (0) {
+        B()
+    }
+    "#;
+
+    let source_b = r#"
+    #include "./a.huff"
+
+    #define macro B() = takes(0) returns (0) {
+        0x00 0x00 revert
+    }
+    "#;
+
+    let mut file_sources = HashMap::new();
+    file_sources.insert(String::from("contracts/a.huff"), String::from(source_a));
+    file_sources.insert(String::from("contracts/b.huff"), String::from(source_b));
+
+    let evm_version = EVMVersion::default();
+    let compiler = Compiler::new_in_memory(
+        &evm_version,
+        Arc::new(vec![String::from("contracts/a.huff")]),
+        file_sources,
+        None,
+        None,
+        None,
+        None,
+        false,
+    );
+
+    let err = compiler.execute().unwrap_err();
+    assert_eq!(
+        *err,
+        CompilerError::CircularInclude(vec![
+            String::from("contracts/a.huff"),
+            String::from("contracts/b.huff"),
+            String::from("contracts/a.huff"),
+        ])
+    );
+}
@@ -0,0 +1,80 @@
+mod common;
+
+use common::parse;
+use huff_codegen::Codegen;
+use huff_utils::prelude::*;
+
+#[test]
+fn test_codeoffset_resolves_to_macro_start() {
+    let source: &str = r#"
+        #define macro PAYLOAD() = takes (0) returns (0) {
+            0x2a 0x00 mstore
+        }
+
+        #define macro MAIN() = takes (0) returns (0) {
+            __codeoffset(PAYLOAD)
+            callvalue pop
+            PAYLOAD()
+            __codeoffset(PAYLOAD)
+        }
+    "#;
+
+    // Parse tokens
+    let contract = parse(source);
+
+    // PUSH2 0x0005 CALLVALUE POP | PUSH1 0x2a PUSH0 MSTORE | PUSH2 0x0005
+    let main_bytecode =
+        Codegen::generate_main_bytecode(&EVMVersion::default(), &contract, None).unwrap();
+    assert_eq!(main_bytecode, "6100053450602a5f52610005");
+    assert_eq!(&main_bytecode[10..14], "602a");
+}
+
+#[test]
+fn test_codeoffset_of_unplaced_macro() {
+    let source: &str = r#"
+        #define macro PAYLOAD() = takes (0) returns (0) {
+            0x2a 0x00 mstore
+        }
+
+        #define macro MAIN() = takes (0) returns (0) {
+            __codeoffset(PAYLOAD)
+        }
+    "#;
+
+    // Parse tokens
+    let contract = parse(source);
+
+    // PAYLOAD is never placed, so it has no offset to resolve to
+    let err = Codegen::generate_main_bytecode(&EVMVersion::default(), &contract, None).unwrap_err();
+    assert_eq!(err.kind, CodegenErrorKind::MissingCodeOffset("PAYLOAD".to_string()));
+    assert!(!err.span.0.is_empty());
+}
+
+#[test]
+fn test_codeoffset_past_two_bytes() {
+    let source = format!(
+        r#"
+        #define macro FILLER() = takes (0) returns (0) {{
+            {}
+        }}
+
+        #define macro PAYLOAD() = takes (0) returns (0) {{
+            0x2a 0x00 mstore
+        }}
+
+        #define macro MAIN() = takes (0) returns (0) {{
+            __codeoffset(PAYLOAD)
+            FILLER()
+            PAYLOAD()
+        }}
+    "#,
+        "stop ".repeat(0x10000)
+    );
+
+    // Parse tokens
+    let contract = parse(&source);
+
+    // PAYLOAD starts at 0x10003, past what the PUSH2 can hold
+    let err = Codegen::generate_main_bytecode(&EVMVersion::default(), &contract, None).unwrap_err();
+    assert_eq!(err.kind, CodegenErrorKind::CodeOffsetOverflow("PAYLOAD".to_string(), 0x10003));
+}
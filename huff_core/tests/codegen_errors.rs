@@ -53,7 +53,21 @@ fn test_storage_pointers_not_derived() {
                         Span { start: 44, end: 44, file: None },
                         Span { start: 46, end: 67, file: None }
                     ]),
-                    token: None
+                    token: None,
+                    call_stack: vec![
+                        (
+                            "MAIN".to_string(),
+                            contract.find_macro_by_name("MAIN").unwrap().span.clone()
+                        ),
+                        (
+                            "MINT".to_string(),
+                            AstSpan(vec![
+                                Span { start: 747, end: 750, file: None },
+                                Span { start: 751, end: 751, file: None },
+                                Span { start: 752, end: 752, file: None }
+                            ])
+                        )
+                    ]
                 }
             )
         }
@@ -109,7 +123,21 @@ fn test_invalid_constant_definition() {
                         "UNKNOWN_CONSTANT_DEFINITION".to_string()
                     ),
                     span: AstSpan(vec![Span { start: const_start, end: const_end, file: None }]),
-                    token: None
+                    token: None,
+                    call_stack: vec![
+                        (
+                            "MAIN".to_string(),
+                            contract.find_macro_by_name("MAIN").unwrap().span.clone()
+                        ),
+                        (
+                            "MINT".to_string(),
+                            AstSpan(vec![
+                                Span { start: 792, end: 795, file: None },
+                                Span { start: 796, end: 796, file: None },
+                                Span { start: 797, end: 797, file: None }
+                            ])
+                        )
+                    ]
                 }
             )
         }
@@ -150,7 +178,8 @@ fn test_missing_constructor() {
                 CodegenError {
                     kind: CodegenErrorKind::MissingMacroDefinition("CONSTRUCTOR".to_string()),
                     span: AstSpan(vec![Span { start: 0, end: 0, file: None }]),
-                    token: None
+                    token: None,
+                    call_stack: vec![]
                 }
             )
         }
@@ -183,7 +212,8 @@ fn test_missing_main() {
                 CodegenError {
                     kind: CodegenErrorKind::MissingMacroDefinition("MAIN".to_string()),
                     span: AstSpan(vec![Span { start: 0, end: 0, file: None }]),
-                    token: None
+                    token: None,
+                    call_stack: vec![]
                 }
             )
         }
@@ -218,7 +248,8 @@ fn test_missing_when_alternative_main_provided() {
                 CodegenError {
                     kind: CodegenErrorKind::MissingMacroDefinition("NAH".to_string()),
                     span: AstSpan(vec![Span { start: 0, end: 0, file: None }]),
-                    token: None
+                    token: None,
+                    call_stack: vec![]
                 }
             )
         }
@@ -263,7 +294,11 @@ fn test_unknown_macro_definition() {
                         Span { start: 351, end: 351, file: None },
                         Span { start: 352, end: 352, file: None }
                     ]),
-                    token: None
+                    token: None,
+                    call_stack: vec![(
+                        "MAIN".to_string(),
+                        contract.find_macro_by_name("MAIN").unwrap().span.clone()
+                    )]
                 }
             )
         }
@@ -311,9 +346,104 @@ fn test_unmatched_jump_label() {
                         Span { start: 377, end: 379, file: None },
                         Span { start: 380, end: 380, file: None }
                     ]),
-                    token: None
+                    token: None,
+                    call_stack: vec![]
                 }
             )
         }
     }
 }
+
+#[test]
+fn test_table_with_undefined_label() {
+    let source = r#"
+    #define jumptable TABLE {
+        missing
+    }
+
+    #define macro MAIN() = takes(0) returns (0) {
+        __tablestart(TABLE)
+    }
+    "#;
+
+    let full_source = FullFileSource { source, file: None, spans: vec![] };
+    let lexer = Lexer::new(full_source.source);
+    let tokens = lexer.into_iter().map(|x| x.unwrap()).collect::<Vec<Token>>();
+    let mut parser = Parser::new(tokens, Some("".to_string()));
+    let mut contract = parser.parse().unwrap();
+    contract.derive_storage_pointers();
+
+    let err = Codegen::generate_main_bytecode(&EVMVersion::default(), &contract, None).unwrap_err();
+    assert_eq!(err.kind, CodegenErrorKind::UnmatchedJumpLabel);
+    assert_eq!(err.span, contract.tables[0].statements[0].span);
+}
+
+#[test]
+fn test_invalid_table_size() {
+    let mut table = TableDefinition::new(
+        "TABLE".to_string(),
+        TableKind::JumpTable,
+        vec![],
+        [0xff; 32],
+        AstSpan(vec![Span { start: 0, end: 10, file: None }]),
+    );
+    table.placement = TablePlacement::Before;
+
+    let err = Codegen::leading_tables_size(&[table.clone()]).unwrap_err();
+    assert_eq!(
+        err,
+        CodegenError {
+            kind: CodegenErrorKind::InvalidTableSize("TABLE".to_string()),
+            span: table.span,
+            token: None,
+            call_stack: vec![]
+        }
+    );
+}
+
+#[test]
+fn test_error_call_stack() {
+    let source = r#"
+    #define macro TRANSFER() = takes(0) returns (0) {
+        [MISSING_SLOT] sload
+    }
+
+    #define macro DISPATCH() = takes(0) returns (0) {
+        0x00 calldataload
+        TRANSFER()
+    }
+
+    #define macro MAIN() = takes(0) returns (0) {
+        DISPATCH()
+    }
+    "#;
+
+    let full_source = FullFileSource { source, file: None, spans: vec![] };
+    let lexer = Lexer::new(full_source.source);
+    let tokens = lexer.into_iter().map(|x| x.unwrap()).collect::<Vec<Token>>();
+    let mut parser = Parser::new(tokens, Some("".to_string()));
+    let contract = parser.parse().unwrap();
+
+    let e = Codegen::generate_main_bytecode(&EVMVersion::default(), &contract, None).unwrap_err();
+    assert_eq!(e.kind, CodegenErrorKind::MissingConstantDefinition("MISSING_SLOT".to_string()));
+
+    let invocation = |name: &str| {
+        let start = source.find(&format!("{name}()\n")).unwrap();
+        AstSpan(vec![
+            Span { start, end: start + name.len() - 1, file: None },
+            Span { start: start + name.len(), end: start + name.len(), file: None },
+            Span { start: start + name.len() + 1, end: start + name.len() + 1, file: None },
+        ])
+    };
+    assert_eq!(
+        e.call_stack,
+        vec![
+            ("MAIN".to_string(), contract.find_macro_by_name("MAIN").unwrap().span.clone()),
+            ("DISPATCH".to_string(), invocation("DISPATCH")),
+            ("TRANSFER".to_string(), invocation("TRANSFER")),
+        ]
+    );
+    assert!(CompilerError::CodegenError(e)
+        .to_string()
+        .contains("In: MAIN -> DISPATCH -> TRANSFER"));
+}
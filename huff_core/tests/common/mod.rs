@@ -0,0 +1,22 @@
+// Each test binary only uses some of the helpers
+#![allow(dead_code)]
+
+use huff_lexer::Lexer;
+use huff_parser::Parser;
+use huff_utils::prelude::*;
+
+/// Lexes and parses a source
+pub fn try_parse(source: &str) -> Result<Contract, ParserError> {
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let lexer = Lexer::new(flattened_source.source);
+    let tokens = lexer.into_iter().map(|x| x.unwrap()).collect::<Vec<Token>>();
+    let mut parser = Parser::new(tokens, None);
+    parser.parse()
+}
+
+/// Lexes and parses a valid source, deriving its storage pointers
+pub fn parse(source: &str) -> Contract {
+    let mut contract = try_parse(source).unwrap();
+    contract.derive_storage_pointers();
+    contract
+}
@@ -0,0 +1,44 @@
+mod common;
+
+use common::parse;
+use huff_codegen::Codegen;
+use huff_utils::prelude::*;
+
+const SOURCE: &str = r#"
+    #define macro DIRECT() = takes(0) returns(0) {
+        dest jump
+        dest:
+            0x01 0x00 mstore
+    }
+
+    #define macro COMPUTED() = takes(0) returns(0) {
+        0x00 calldataload jumpi
+    }
+
+    #define macro MAIN() = takes(0) returns(0) {
+        DIRECT()
+        COMPUTED()
+    }
+"#;
+
+#[test]
+fn test_computed_jumps_are_opt_in() {
+    let contract = parse(SOURCE);
+
+    let mut cg = Codegen::new();
+    cg.lint(&contract).unwrap();
+    assert!(cg.warnings.is_empty());
+}
+
+#[test]
+fn test_computed_jumps_are_flagged() {
+    let contract = parse(SOURCE);
+
+    let mut cg = Codegen { lint_jumps: true, ..Codegen::new() };
+    cg.lint(&contract).unwrap();
+
+    // Only the jump following `calldataload` is flagged
+    assert_eq!(cg.warnings.len(), 1);
+    assert_eq!(cg.warnings[0].kind, CodegenWarningKind::ComputedJump("COMPUTED".to_string()));
+    assert_eq!(cg.warnings[0].span.0[0].start, SOURCE.find("jumpi").unwrap());
+}
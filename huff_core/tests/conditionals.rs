@@ -0,0 +1,44 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+use huff_core::Compiler;
+use huff_utils::prelude::EVMVersion;
+
+#[test]
+fn test_ifdef_guards_code() {
+    let source = r#"
+        #define macro MAIN() = takes(0) returns(0) {
+            0x00 calldataload
+            #ifdef DEBUG
+                0x01 0x00 sstore
+            #endif
+            0x00 mstore
+            0x20 0x00 return
+        }
+    "#;
+
+    let file_name = String::from("main.huff");
+    let file_sources = HashMap::from([(file_name.clone(), String::from(source))]);
+
+    let evm_version = EVMVersion::default();
+    let mut compiler = Compiler::new_in_memory(
+        &evm_version,
+        Arc::new(vec![file_name]),
+        file_sources,
+        None,
+        None,
+        None,
+        None,
+        false,
+    );
+
+    let release = compiler.execute().unwrap()[0].runtime.clone();
+    compiler.defines = HashSet::from([String::from("DEBUG")]);
+    let debug = compiler.execute().unwrap()[0].runtime.clone();
+
+    // The debug build only adds the guarded `0x01 0x00 sstore`
+    assert_eq!(release, "5f355f5260205ff3");
+    assert_eq!(debug, "5f3560015f555f5260205ff3");
+}
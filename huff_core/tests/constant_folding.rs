@@ -0,0 +1,88 @@
+mod common;
+
+use std::{collections::HashMap, sync::Arc};
+
+use common::parse;
+use huff_codegen::Codegen;
+use huff_core::Compiler;
+use huff_utils::prelude::*;
+
+fn folded_main_bytecode(source: &str) -> String {
+    let mut contract = parse(source);
+    contract.fold_constants();
+
+    Codegen::generate_main_bytecode(&EVMVersion::default(), &contract, None).unwrap()
+}
+
+#[test]
+fn test_folds_constant_arithmetic() {
+    let source = r#"
+        #define macro MAIN() = takes(0) returns(0) {
+            0x20 0x20 add
+            0x01 0x05 sub
+            0xff00 0x0ff0 and
+            0x01 0x02 add 0x03 mul
+        }
+    "#;
+
+    // PUSH1 0x40 PUSH1 0x04 PUSH1 0x0f00 PUSH1 0x09
+    assert_eq!(folded_main_bytecode(source), "60406004610f006009");
+}
+
+#[test]
+fn test_folding_wraps_like_the_evm() {
+    let source = r#"
+        #define macro MAIN() = takes(0) returns(0) {
+            0x02 0x01 sub
+        }
+    "#;
+
+    // 1 - 2 underflows to 2^256 - 1
+    assert_eq!(folded_main_bytecode(source), format!("7f{}", "ff".repeat(32)));
+}
+
+#[test]
+fn test_does_not_fold_across_labels_or_explicit_pushes() {
+    let source = r#"
+        #define macro MAIN() = takes(0) returns(0) {
+            0x01
+            continue:
+                0x02 add
+            push2 0x0001 0x02 add
+            0x03 0x04 div
+        }
+    "#;
+
+    assert_eq!(folded_main_bytecode(source), "60015b6002016100016002016003600404");
+}
+
+#[test]
+fn test_optimize_flag_folds_constants() {
+    let source = r#"
+        #define macro MAIN() = takes(0) returns(0) {
+            0x20 0x20 add 0x00 mstore
+        }
+    "#;
+
+    let file_name = String::from("contracts/main.huff");
+    let file_sources = HashMap::from([(file_name.clone(), String::from(source))]);
+
+    let evm_version = EVMVersion::default();
+    let mut compiler = Compiler::new_in_memory(
+        &evm_version,
+        Arc::new(vec![file_name]),
+        file_sources,
+        None,
+        None,
+        None,
+        None,
+        false,
+    );
+
+    let unoptimized = compiler.execute().unwrap();
+    assert_eq!(unoptimized[0].runtime, "60206020015f52");
+
+    compiler.optimize = true;
+    let optimized = compiler.execute().unwrap();
+    assert_eq!(optimized[0].runtime, "60405f52");
+}
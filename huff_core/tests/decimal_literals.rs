@@ -0,0 +1,43 @@
+mod common;
+
+use common::parse;
+use huff_codegen::Codegen;
+use huff_utils::prelude::*;
+
+#[test]
+fn test_decimal_literals() {
+    let source = r#"
+        #define constant DECIMALS = 18
+
+        #define macro SCALE(factor) = takes(0) returns(1) {
+            <factor>
+        }
+
+        #define macro MAIN() = takes(0) returns(0) {
+            [DECIMALS] 1000000 SCALE(256)
+        }
+    "#;
+
+    let contract = parse(source);
+
+    let bytecode =
+        Codegen::generate_main_bytecode(&EVMVersion::default(), &contract, None).unwrap();
+    // PUSH1 0x12, PUSH3 0x0f4240, PUSH2 0x0100
+    assert_eq!(bytecode, "6012620f4240610100");
+}
+
+#[test]
+fn test_negative_decimal_literals() {
+    let source = r#"
+        #define macro MAIN() = takes(0) returns(0) {
+            -1 -2
+        }
+    "#;
+
+    let contract = parse(source);
+
+    let bytecode =
+        Codegen::generate_main_bytecode(&EVMVersion::default(), &contract, None).unwrap();
+    // PUSH32 0xff..ff, PUSH32 0xff..fe
+    assert_eq!(bytecode, format!("7f{}7f{}fe", "ff".repeat(32), "ff".repeat(31)));
+}
@@ -0,0 +1,42 @@
+mod common;
+
+use common::parse;
+use huff_codegen::Codegen;
+use huff_utils::prelude::*;
+
+const DEPTH: usize = 5000;
+
+fn compile(source: &str) -> String {
+    let contract = parse(source);
+    Codegen::generate_main_bytecode(&EVMVersion::default(), &contract, None).unwrap()
+}
+
+/// Builds a chain of macros where each one holds a single statement depending on the previous
+fn chain(statement: impl Fn(usize) -> String) -> String {
+    let mut source = String::from("#define macro M_0() = takes(0) returns(1) {\n    0x01\n}\n");
+    for i in 1..DEPTH {
+        source.push_str(&format!(
+            "#define macro M_{i}() = takes(0) returns(1) {{\n    {}\n}}\n",
+            statement(i - 1)
+        ));
+    }
+    source.push_str(&format!(
+        "#define macro MAIN() = takes(0) returns(0) {{\n    {}\n}}\n",
+        statement(DEPTH - 1)
+    ));
+    source
+}
+
+#[test]
+fn test_deeply_nested_invocations() {
+    let source = chain(|inner| format!("M_{inner}()"));
+
+    assert_eq!(compile(&source), "6001");
+}
+
+#[test]
+fn test_deeply_nested_codesize() {
+    let source = chain(|inner| format!("__codesize(M_{inner})"));
+
+    assert_eq!(compile(&source), "6002");
+}
@@ -0,0 +1,38 @@
+mod common;
+
+use common::parse;
+use huff_codegen::Codegen;
+use huff_utils::prelude::*;
+
+const SOURCE: &str = r#"
+    #define constant USED = 0x01
+    #define constant UNUSED = 0x02
+
+    #define macro MAIN() = takes(0) returns(0) {
+        [USED] 0x00 mstore
+        0x20 0x00 return
+    }
+"#;
+
+#[test]
+fn test_warnings_are_collected() {
+    let contract = parse(SOURCE);
+
+    let mut cg = Codegen::new();
+    cg.lint(&contract).unwrap();
+    assert_eq!(cg.warnings.len(), 1);
+    assert_eq!(cg.warnings[0].kind, CodegenWarningKind::UnusedConstant("UNUSED".to_string()));
+}
+
+#[test]
+fn test_deny_warnings_fails_build() {
+    let contract = parse(SOURCE);
+
+    let mut cg = Codegen { deny_warnings: true, ..Codegen::new() };
+    let err = cg.lint(&contract).unwrap_err();
+    assert_eq!(
+        err.kind,
+        CodegenErrorKind::DeniedWarning(CodegenWarningKind::UnusedConstant("UNUSED".to_string()))
+    );
+    assert!(cg.warnings.is_empty());
+}
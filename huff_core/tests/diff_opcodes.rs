@@ -0,0 +1,58 @@
+mod common;
+
+use common::parse;
+use huff_codegen::Codegen;
+use huff_utils::prelude::*;
+
+#[test]
+fn test_diff_opcodes() {
+    let old = parse(
+        r#"
+        #define macro MAIN() = takes(0) returns(0) {
+            0x00 calldataload
+            0x01 add
+            0x00 mstore
+        }
+    "#,
+    );
+    let new = parse(
+        r#"
+        #define macro MAIN() = takes(0) returns(0) {
+            0x00 calldataload
+            0x02 add
+            0x00 mstore
+        }
+    "#,
+    );
+
+    let diff = Codegen::diff_opcodes(&EVMVersion::default(), &old, &new).unwrap();
+    let changes = diff
+        .iter()
+        .filter(|d| !matches!(d, InstructionDiff::Unchanged(_)))
+        .collect::<Vec<&InstructionDiff>>();
+    assert_eq!(
+        changes,
+        vec![&InstructionDiff::Changed {
+            old: Instruction { offset: 0x02, text: "PUSH1 0x01".to_string() },
+            new: Instruction { offset: 0x02, text: "PUSH1 0x02".to_string() },
+        }]
+    );
+    assert_eq!(changes[0].to_string(), "~ PUSH1 0x01 became PUSH1 0x02 at offset 0x02");
+
+    // Every instruction is listed, in order
+    assert_eq!(
+        diff.iter().map(|d| d.to_string()).collect::<Vec<String>>(),
+        vec![
+            "  PUSH0 at offset 0x00",
+            "  CALLDATALOAD at offset 0x01",
+            "~ PUSH1 0x01 became PUSH1 0x02 at offset 0x02",
+            "  ADD at offset 0x04",
+            "  PUSH0 at offset 0x05",
+            "  MSTORE at offset 0x06",
+        ]
+    );
+
+    // Identical contracts have no changes
+    let diff = Codegen::diff_opcodes(&EVMVersion::default(), &old, &old).unwrap();
+    assert!(diff.iter().all(|d| matches!(d, InstructionDiff::Unchanged(_))));
+}
@@ -0,0 +1,103 @@
+mod common;
+
+use common::parse;
+use huff_codegen::Codegen;
+use huff_utils::prelude::*;
+
+const INTERFACE: &str = r#"
+    #define function balanceOf(address) view returns (uint256)
+    #define function transfer(address,uint256) nonpayable returns ()
+"#;
+
+#[test]
+fn test_generate_dispatcher() {
+    let handlers = r#"
+        #define macro MAIN() = takes(0) returns(0) {
+            DISPATCH()
+            0x00 dup1 revert
+            balanceOf:
+                0x00 0x00 return
+            transfer:
+                stop
+        }
+    "#;
+    let contract = parse(&format!("{INTERFACE}{handlers}"));
+
+    let dispatcher = Codegen::generate_dispatcher(&contract).unwrap();
+    assert_eq!(
+        dispatcher,
+        "0x00 calldataload 0xe0 shr\n\
+         dup1 0x70a08231 eq balanceOf jumpi\n\
+         dup1 0xa9059cbb eq transfer jumpi"
+    );
+
+    // The generated dispatcher compiles into the standard selector comparison sequence
+    let dispatch_macro =
+        format!("#define macro DISPATCH() = takes(0) returns(0) {{\n{dispatcher}\n}}");
+    let contract = parse(&format!("{INTERFACE}{dispatch_macro}{handlers}"));
+    let bytecode =
+        Codegen::generate_main_bytecode(&EVMVersion::default(), &contract, None).unwrap();
+    assert_eq!(
+        bytecode,
+        "5f3560e01c\
+         806370a082311461001e57\
+         8063a9059cbb1461002257\
+         5f80fd5b5f5ff35b00"
+    );
+}
+
+#[test]
+fn test_generate_dispatcher_missing_handler() {
+    let contract = parse(&format!(
+        "{INTERFACE}{}",
+        r#"
+        #define macro MAIN() = takes(0) returns(0) {
+            balanceOf:
+                0x00 0x00 return
+        }
+    "#
+    ));
+
+    let err = Codegen::generate_dispatcher(&contract).unwrap_err();
+    assert_eq!(err.kind, CodegenErrorKind::MissingDispatchHandler("transfer".to_string()));
+}
+
+#[test]
+fn test_generate_dispatcher_unreachable_handler() {
+    let contract = parse(&format!(
+        "{INTERFACE}{}",
+        r#"
+        #define macro UNUSED() = takes(0) returns(0) {
+            transfer:
+                stop
+        }
+
+        #define macro MAIN() = takes(0) returns(0) {
+            balanceOf:
+                0x00 0x00 return
+        }
+    "#
+    ));
+
+    // A handler label in a macro the entry point never invokes can't be jumped to
+    let err = Codegen::generate_dispatcher(&contract).unwrap_err();
+    assert_eq!(err.kind, CodegenErrorKind::MissingDispatchHandler("transfer".to_string()));
+}
+
+#[test]
+fn test_generate_dispatcher_overloaded_function() {
+    let contract = parse(
+        r#"
+        #define function transfer(address,uint256) nonpayable returns ()
+        #define function transfer(address) nonpayable returns ()
+
+        #define macro MAIN() = takes(0) returns(0) {
+            transfer:
+                stop
+        }
+    "#,
+    );
+
+    let err = Codegen::generate_dispatcher(&contract).unwrap_err();
+    assert_eq!(err.kind, CodegenErrorKind::OverloadedDispatchFunction("transfer".to_string()));
+}
@@ -0,0 +1,114 @@
+mod common;
+
+use common::parse;
+use std::{collections::HashMap, sync::Arc};
+
+use huff_core::Compiler;
+use huff_utils::prelude::*;
+
+#[test]
+fn test_duplicate_macro_reports_both_definitions() {
+    let contract = parse(
+        r#"
+        #define macro STORE() = takes(0) returns(0) {
+            0x01 0x00 sstore
+        }
+
+        #define macro MAIN() = takes(0) returns(0) {
+            STORE()
+        }
+
+        #define macro STORE() = takes(0) returns(0) {
+            0x02 0x00 sstore
+        }
+    "#,
+    );
+
+    let err = contract.check_duplicate_definitions().unwrap_err();
+    let spans = vec![contract.macros[0].span.clone(), contract.macros[2].span.clone()];
+    assert_eq!(
+        err.kind,
+        CodegenErrorKind::DuplicateMacroDefinition {
+            name: "STORE".to_string(),
+            spans: spans.clone()
+        }
+    );
+    assert_eq!(err.span, AstSpan(spans.into_iter().flat_map(|s| s.0).collect()));
+}
+
+#[test]
+fn test_duplicate_constant_and_function() {
+    let contract = parse(
+        r#"
+        #define constant OWNER = 0x01
+        #define constant OWNER = 0x02
+    "#,
+    );
+    let err = contract.check_duplicate_definitions().unwrap_err();
+    assert!(matches!(
+        err.kind,
+        CodegenErrorKind::DuplicateConstantDefinition { ref name, ref spans }
+            if name == "OWNER" && spans.len() == 2
+    ));
+
+    // Overloaded functions have different selectors and aren't duplicates
+    let contract = parse(
+        r#"
+        #define function transfer(address,uint256) nonpayable returns ()
+        #define function transfer(address) nonpayable returns ()
+    "#,
+    );
+    contract.check_duplicate_definitions().unwrap();
+
+    let contract = parse(
+        r#"
+        #define function transfer(address,uint256) nonpayable returns ()
+        #define function transfer(address,uint256) nonpayable returns ()
+    "#,
+    );
+    let err = contract.check_duplicate_definitions().unwrap_err();
+    assert!(matches!(
+        err.kind,
+        CodegenErrorKind::DuplicateFunctionDefinition { ref name, .. } if name == "transfer"
+    ));
+}
+
+#[test]
+fn test_compiler_rejects_duplicate_definitions() {
+    let source = r#"
+        #define macro MAIN() = takes(0) returns(0) {
+            0x00 0x00 return
+        }
+
+        #define macro HELPER() = takes(0) returns(0) {}
+
+        #define macro MAIN() = takes(0) returns(0) {
+            0x00 0x00 revert
+        }
+    "#;
+    let file_name = String::from("main.huff");
+    let file_sources = HashMap::from([(file_name.clone(), String::from(source))]);
+
+    let evm_version = EVMVersion::default();
+    let compiler = Compiler::new_in_memory(
+        &evm_version,
+        Arc::new(vec![file_name]),
+        file_sources,
+        None,
+        None,
+        None,
+        None,
+        false,
+    );
+
+    match compiler.execute().unwrap_err().as_ref() {
+        CompilerError::FailedCompiles(errors) => assert!(matches!(
+            &errors[..],
+            [CompilerError::CodegenError(CodegenError {
+                kind: CodegenErrorKind::DuplicateMacroDefinition { .. },
+                ..
+            })]
+        )),
+        e => panic!("unexpected error: {e:?}"),
+    }
+}
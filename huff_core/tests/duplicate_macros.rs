@@ -0,0 +1,56 @@
+mod common;
+
+use common::parse;
+use huff_codegen::Codegen;
+use huff_utils::prelude::*;
+
+#[test]
+fn test_duplicate_macro_bodies() {
+    let contract = parse(
+        r#"
+        #define constant OWNER = FREE_STORAGE_POINTER()
+
+        #define macro ONLY_OWNER() = takes(0) returns(0) {
+            [OWNER] sload caller eq ok jumpi
+            0x00 dup1 revert
+            ok:
+        }
+
+        #define macro REQUIRE_OWNER() = takes(0) returns(0) {
+            [OWNER] sload
+            caller eq ok jumpi
+            0x00 dup1 revert
+            ok:
+        }
+
+        #define macro GET_OWNER() = takes(0) returns(1) {
+            [OWNER] sload
+        }
+
+        #define macro GET_CALLER() = takes(0) returns(1) {
+            caller
+        }
+
+        #define macro MAIN() = takes(0) returns(0) {
+            ONLY_OWNER() REQUIRE_OWNER() GET_OWNER() GET_CALLER()
+        }
+    "#,
+    );
+
+    // Only reported if enabled
+    let mut cg = Codegen::new();
+    cg.lint(&contract).unwrap();
+    assert!(cg.warnings.is_empty());
+
+    let mut cg = Codegen { lint_duplicates: true, ..Codegen::new() };
+    cg.lint(&contract).unwrap();
+    assert_eq!(cg.warnings.len(), 1);
+    assert_eq!(
+        cg.warnings[0].kind,
+        CodegenWarningKind::DuplicateMacroBody(
+            "ONLY_OWNER".to_string(),
+            "REQUIRE_OWNER".to_string()
+        )
+    );
+    assert_eq!(cg.warnings[0].span, contract.macros[1].span);
+}
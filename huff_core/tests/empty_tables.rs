@@ -0,0 +1,55 @@
+mod common;
+
+use common::parse;
+use huff_codegen::Codegen;
+use huff_utils::prelude::*;
+
+#[test]
+fn test_empty_table_is_reported() {
+    let source = r#"
+        #define jumptable EMPTY {}
+        #define jumptable__packed FULL { done }
+
+        #define macro SIZE(size) = takes(0) returns(1) {
+            <size>
+        }
+
+        #define macro MAIN() = takes(0) returns(0) {
+            __tablesize(EMPTY) __tablestart(FULL) SIZE(__tablestart(EMPTY))
+            done:
+        }
+    "#;
+    let contract = parse(source);
+
+    let mut cg = Codegen::new();
+    cg.lint(&contract).unwrap();
+    let warnings = cg.warnings.iter().map(|w| w.kind.clone()).collect::<Vec<_>>();
+    assert_eq!(warnings, vec![CodegenWarningKind::EmptyTable("EMPTY".to_string()); 2]);
+
+    // The warning points at the builtin call
+    let span = &cg.warnings[0].span.0[0];
+    assert_eq!(&source[span.start..span.end + 1], "__tablesize");
+
+    // Denied warnings turn into errors
+    let mut cg = Codegen { deny_warnings: true, ..Codegen::new() };
+    let err = cg.lint(&contract).unwrap_err();
+    assert_eq!(
+        err.kind,
+        CodegenErrorKind::DeniedWarning(CodegenWarningKind::EmptyTable("EMPTY".to_string()))
+    );
+}
+
+#[test]
+fn test_missing_table_is_reported() {
+    for builtin in ["__tablesize", "__tablestart"] {
+        let contract =
+            parse(&format!("#define macro MAIN() = takes(0) returns(0) {{ {builtin}(MISSING) }}"));
+        let err =
+            Codegen::generate_main_bytecode(&EVMVersion::default(), &contract, None).unwrap_err();
+        assert_eq!(err.kind, CodegenErrorKind::MissingTableDefinition("MISSING".to_string()));
+
+        let errors = Codegen::validate(&contract);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].kind, err.kind);
+    }
+}
@@ -0,0 +1,71 @@
+mod common;
+
+use std::{collections::HashMap, sync::Arc};
+
+use common::parse;
+use huff_codegen::Codegen;
+use huff_core::Compiler;
+use huff_utils::prelude::*;
+
+const SOURCE: &str = r#"
+    #define macro CONSTRUCTOR() = takes(0) returns(0) {}
+
+    #define macro MAIN() = takes(2) returns(0) {
+        add 0x00 mstore
+        0x20 0x00 return
+    }
+"#;
+
+#[test]
+fn test_main_taking_stack_items_is_rejected() {
+    let contract = parse(SOURCE);
+
+    let err = Codegen::new().check_entry_signatures(&contract, None, None).unwrap_err();
+    assert_eq!(
+        err.kind,
+        CodegenErrorKind::InvalidEntryMacroSignature {
+            macro_name: "MAIN".to_string(),
+            takes: 2,
+            returns: 0,
+        }
+    );
+    assert_eq!(err.span, contract.macros[1].span);
+
+    // Only the macros compiled as entry points are checked
+    Codegen::new().check_entry_signatures(&contract, Some("CONSTRUCTOR"), None).unwrap();
+    Codegen { lenient_entry_points: true, ..Codegen::new() }
+        .check_entry_signatures(&contract, None, None)
+        .unwrap();
+}
+
+#[test]
+fn test_compiler_checks_entry_signatures() {
+    let file_name = String::from("main.huff");
+    let file_sources = HashMap::from([(file_name.clone(), String::from(SOURCE))]);
+
+    let evm_version = EVMVersion::default();
+    let mut compiler = Compiler::new_in_memory(
+        &evm_version,
+        Arc::new(vec![file_name]),
+        file_sources,
+        None,
+        None,
+        None,
+        None,
+        false,
+    );
+
+    match compiler.execute().unwrap_err().as_ref() {
+        CompilerError::FailedCompiles(errors) => assert!(matches!(
+            &errors[..],
+            [CompilerError::CodegenError(CodegenError {
+                kind: CodegenErrorKind::InvalidEntryMacroSignature { takes: 2, .. },
+                ..
+            })]
+        )),
+        e => panic!("unexpected error: {e:?}"),
+    }
+
+    compiler.lenient_entry_points = true;
+    assert!(compiler.execute().is_ok());
+}
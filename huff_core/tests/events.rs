@@ -0,0 +1,74 @@
+mod common;
+
+use std::{
+    io::Write,
+    sync::{Arc, Mutex},
+};
+
+use common::parse;
+use huff_codegen::Codegen;
+use huff_utils::prelude::*;
+
+/// A writer sharing its buffer with the test
+#[derive(Clone, Default)]
+struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+impl Write for Buffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_progress_events() {
+    let source: &str = r#"
+        #define jumptable TABLE {
+            done
+        }
+
+        #define macro HELPER() = takes(0) returns(1) {
+            __tablestart(TABLE)
+        }
+
+        #define macro MAIN() = takes(0) returns(0) {
+            HELPER() pop
+            done:
+                stop
+        }
+    "#;
+    let contract = parse(source);
+
+    let buffer = Buffer::default();
+    let cg = Codegen::new().with_event_sink(Box::new(buffer.clone()));
+    let bytecode = cg
+        .with_events(|| Codegen::generate_main_bytecode(&EVMVersion::default(), &contract, None))
+        .unwrap();
+    assert_eq!(
+        bytecode,
+        "610006505b000000000000000000000000000000000000000000000000000000000000000004"
+    );
+
+    let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+    let events = output
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect::<Vec<serde_json::Value>>();
+    assert_eq!(
+        events,
+        vec![
+            serde_json::json!({ "event": "macro_started", "macro": "MAIN", "offset": 0 }),
+            serde_json::json!({ "event": "macro_started", "macro": "HELPER", "offset": 0 }),
+            serde_json::json!({ "event": "macro_completed", "macro": "HELPER", "offset": 3 }),
+            serde_json::json!({ "event": "macro_completed", "macro": "MAIN", "offset": 6 }),
+            serde_json::json!({ "event": "table_generated", "table": "TABLE", "size": 32 }),
+        ]
+    );
+
+    // No events are written outside of `with_events`
+    Codegen::generate_main_bytecode(&EVMVersion::default(), &contract, None).unwrap();
+    assert_eq!(buffer.0.lock().unwrap().len(), output.len());
+}
@@ -1,8 +1,14 @@
+use std::{collections::HashMap, sync::Arc};
+
 use huff_codegen::Codegen;
+use huff_core::Compiler;
 use huff_lexer::*;
 use huff_parser::Parser;
 use huff_utils::{
-    prelude::{EVMVersion, FullFileSource},
+    prelude::{
+        str_to_bytes32, CodegenErrorKind, CompilerError, ConstVal, EVMVersion, FullFileSource,
+        SupportedEVMVersions,
+    },
     token::Token,
 };
 
@@ -36,5 +42,143 @@ fn test_set_free_storage_pointers() {
 
     // Assert the Free storage pointer has been set to 0
     let mbytes = Codegen::generate_main_bytecode(&EVMVersion::default(), &contract, None).unwrap();
-    assert!(mbytes.starts_with("6000"));
+    assert!(mbytes.starts_with("5f54"));
+
+    // Pre-Shanghai targets push zero with PUSH1
+    let paris = EVMVersion::new(SupportedEVMVersions::Paris);
+    let mbytes = Codegen::generate_main_bytecode(&paris, &contract, None).unwrap();
+    assert!(mbytes.starts_with("600054"));
+}
+
+/// Check that free storage pointers are assigned around constants used as explicit slots
+#[test]
+fn test_explicit_storage_slots() {
+    let source: &str = r#"
+        #define constant FIRST = FREE_STORAGE_POINTER()
+        #define constant EXPLICIT = 0x01
+        #define constant IMPL_SLOT = 0x360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc
+        #define constant SECOND = FREE_STORAGE_POINTER()
+        #define constant VALUE = 0x02
+
+        #define macro MAIN() = {
+            [FIRST] sload
+            [EXPLICIT] sload
+            [IMPL_SLOT] sload
+            [VALUE] [SECOND] sstore
+        }
+    "#;
+
+    // Parse tokens
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let lexer = Lexer::new(flattened_source.source);
+    let tokens = lexer.into_iter().map(|x| x.unwrap()).collect::<Vec<Token>>();
+    let mut parser = Parser::new(tokens, None);
+
+    // Parse AST
+    let mut contract = parser.parse().unwrap();
+
+    // Only constants pushed as a slot are explicit slots
+    let explicit_slots = contract.explicit_storage_slots();
+    assert_eq!(
+        explicit_slots.iter().map(|(name, _)| name.as_str()).collect::<Vec<_>>(),
+        vec!["EXPLICIT", "IMPL_SLOT"]
+    );
+
+    // Derive storage pointers
+    contract.derive_storage_pointers();
+
+    // The explicit slots keep their values, and SECOND skips the slot taken by EXPLICIT
+    let value_of = |name: &str| {
+        let constants = contract.constants.lock().unwrap();
+        constants.iter().find(|c| c.name == name).unwrap().value.clone()
+    };
+    assert_eq!(value_of("FIRST"), ConstVal::Literal(str_to_bytes32("0")));
+    assert_eq!(value_of("EXPLICIT"), ConstVal::Literal(str_to_bytes32("01")));
+    assert_eq!(
+        value_of("IMPL_SLOT"),
+        ConstVal::Literal(str_to_bytes32(
+            "360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc"
+        ))
+    );
+    assert_eq!(value_of("SECOND"), ConstVal::Literal(str_to_bytes32("2")));
+}
+
+fn compile_with_slots(slots: &str) -> Result<String, Arc<CompilerError>> {
+    let source_main = r#"
+        #include "./slots.huff"
+
+        #define constant BALANCE = FREE_STORAGE_POINTER()
+        #define constant SUPPLY = FREE_STORAGE_POINTER()
+
+        #define macro MAIN() = takes(0) returns(0) {
+            [BALANCE] sload
+            OWNER()
+            [SUPPLY] sload
+        }
+    "#;
+
+    let file_sources = HashMap::from([
+        (String::from("main.huff"), String::from(source_main)),
+        (String::from("slots.huff"), String::from(slots)),
+    ]);
+    let evm_version = EVMVersion::default();
+    let compiler = Compiler::new_in_memory(
+        &evm_version,
+        Arc::new(vec![String::from("main.huff")]),
+        file_sources,
+        None,
+        None,
+        None,
+        None,
+        false,
+    );
+    compiler.execute().map(|artifacts| artifacts[0].runtime.clone())
+}
+
+/// Check that free storage pointers skip slots picked by hand in included files
+#[test]
+fn test_free_storage_pointers_skip_included_slots() {
+    let runtime = compile_with_slots(
+        r#"
+        #define constant OWNER_SLOT = 0x00
+
+        #define macro OWNER() = takes(0) returns(1) {
+            [OWNER_SLOT] sload
+        }
+    "#,
+    )
+    .unwrap();
+
+    // BALANCE is bumped past the owner slot
+    assert_eq!(runtime, "6001545f54600254");
+}
+
+/// Check that explicit slots picked twice are rejected
+#[test]
+fn test_colliding_explicit_slots() {
+    let err = compile_with_slots(
+        r#"
+        #define constant OWNER_SLOT = 0x00
+        #define constant ADMIN_SLOT = 0x00
+
+        #define macro OWNER() = takes(0) returns(1) {
+            [OWNER_SLOT] sload [ADMIN_SLOT] sload pop
+        }
+    "#,
+    )
+    .unwrap_err();
+
+    match err.as_ref() {
+        CompilerError::FailedCompiles(errors) => match &errors[..] {
+            [CompilerError::CodegenError(ce)] => assert_eq!(
+                ce.kind,
+                CodegenErrorKind::StorageSlotCollision(
+                    "OWNER_SLOT".to_string(),
+                    "ADMIN_SLOT".to_string()
+                )
+            ),
+            errors => panic!("unexpected errors: {errors:?}"),
+        },
+        e => panic!("unexpected error: {e:?}"),
+    }
 }
@@ -102,7 +102,8 @@ fn test_missing_constructor_with_inputs() {
                 CompilerError::CodegenError(CodegenError {
                     kind: CodegenErrorKind::MissingMacroDefinition("CONSTRUCTOR".to_string()),
                     span: AstSpan(vec![Span { start: 0, end: 0, file: Some(arc_source) }]),
-                    token: None
+                    token: None,
+                    call_stack: vec![]
                 })
             )
         }
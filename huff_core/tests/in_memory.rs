@@ -1,7 +1,7 @@
 use std::{collections::HashMap, sync::Arc};
 
 use huff_core::Compiler;
-use huff_utils::prelude::EVMVersion;
+use huff_utils::prelude::{CompilerError, EVMVersion, LexicalError, LexicalErrorKind};
 
 #[test]
 fn test_in_memory_compiler() {
@@ -55,3 +55,36 @@ fn test_in_memory_compiler() {
         "60188060093d393df35f3560e01c806340c10f1914610010575b6004355f602435".to_string()
     );
 }
+
+#[test]
+fn test_in_memory_compiler_surfaces_lexical_errors() {
+    let source =
+        format!("#define macro MAIN() = takes(0) returns (0) {{\n    0x{}\n}}\n", "ff".repeat(33));
+
+    let main_file_name = String::from("contracts/main.huff");
+    let file_sources = HashMap::from([(main_file_name.clone(), source)]);
+
+    let evm_version = EVMVersion::default();
+    let compiler = Compiler::new_in_memory(
+        &evm_version,
+        Arc::new(vec![main_file_name]),
+        file_sources,
+        None,
+        None,
+        None,
+        None,
+        false,
+    );
+
+    let err = compiler.execute().unwrap_err();
+    assert!(matches!(
+        &*err,
+        CompilerError::FailedCompiles(errors) if matches!(
+            errors.as_slice(),
+            [CompilerError::LexicalError(LexicalError {
+                kind: LexicalErrorKind::LiteralTooLarge(33),
+                ..
+            })]
+        )
+    ));
+}
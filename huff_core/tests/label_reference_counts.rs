@@ -0,0 +1,35 @@
+mod common;
+
+use common::parse;
+use huff_codegen::Codegen;
+
+#[test]
+fn test_label_reference_counts() {
+    let source = r#"
+        #define jumptable SWITCH {
+            table_entry
+        }
+
+        #define macro MAIN() = takes(0) returns(0) {
+            __tablestart(SWITCH) pop
+            0x00 calldataload hot jumpi
+            0x20 calldataload hot jumpi
+            0x00 0x00 revert
+
+            hot:
+                0x00 0x00 return
+            cold:
+                0x00 0x00 revert
+            table_entry:
+                stop
+        }
+    "#;
+
+    let contract = parse(source);
+
+    let counts = Codegen::label_reference_counts(&contract);
+    assert_eq!(counts.len(), 3);
+    assert_eq!(counts.get("hot"), Some(&2));
+    assert_eq!(counts.get("cold"), Some(&0));
+    assert_eq!(counts.get("table_entry"), Some(&1));
+}
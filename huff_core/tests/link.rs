@@ -0,0 +1,63 @@
+mod common;
+
+use common::try_parse;
+use huff_codegen::Codegen;
+use huff_utils::prelude::*;
+
+const LIBRARY: &str = r#"
+    #define function add(uint256,uint256) pure returns (uint256)
+    #define constant ONE = 0x01
+
+    #define macro ADD_ONE() = takes(1) returns(1) {
+        [ONE] add
+    }
+"#;
+
+#[test]
+fn test_link_contracts() {
+    let main = try_parse(
+        r#"
+        #define function add(uint256,uint256) pure returns (uint256)
+
+        #define macro MAIN() = takes(0) returns(0) {
+            0x04 calldataload ADD_ONE()
+            0x00 mstore 0x20 0x00 return
+        }
+    "#,
+    )
+    .unwrap();
+
+    let mut contract = main.link(try_parse(LIBRARY).unwrap()).unwrap();
+    contract.derive_storage_pointers();
+
+    // The shared interface is only kept once
+    assert_eq!(contract.functions.len(), 1);
+    assert_eq!(contract.macros.len(), 2);
+
+    let bytecode =
+        Codegen::generate_main_bytecode(&EVMVersion::default(), &contract, None).unwrap();
+    assert_eq!(bytecode, "6004356001015f5260205ff3");
+}
+
+#[test]
+fn test_link_conflicting_definitions() {
+    let conflicting_constant = try_parse(
+        r#"
+        #define constant ONE = 0x02
+    "#,
+    )
+    .unwrap();
+    let err = try_parse(LIBRARY).unwrap().link(conflicting_constant).unwrap_err();
+    assert_eq!(err.kind, CodegenErrorKind::LinkCollision("ONE".to_string()));
+
+    let duplicate_macro = try_parse(
+        r#"
+        #define macro ADD_ONE() = takes(1) returns(1) {
+            0x01 add
+        }
+    "#,
+    )
+    .unwrap();
+    let err = try_parse(LIBRARY).unwrap().link(duplicate_macro).unwrap_err();
+    assert_eq!(err.kind, CodegenErrorKind::LinkCollision("ADD_ONE".to_string()));
+}
@@ -0,0 +1,93 @@
+mod common;
+
+use common::try_parse;
+use huff_codegen::Codegen;
+use huff_utils::prelude::*;
+
+#[test]
+fn test_macro_local_constants() {
+    let source = r#"
+        #define macro OFFSET() = takes(0) returns(1) {
+            const X = 0x20
+            [X] mload
+        }
+
+        #define macro MAIN() = takes(0) returns(0) {
+            OFFSET()
+            [X]
+        }
+    "#;
+    let mut contract = try_parse(source).unwrap();
+    contract.derive_storage_pointers();
+    assert!(contract.constants.lock().unwrap().is_empty());
+
+    // The local constant is resolved within its macro...
+    let offset = contract.find_macro_by_name("OFFSET").unwrap();
+    assert_eq!(offset.statements[0].ty, StatementType::Literal(str_to_bytes32("20")));
+
+    // ...but is not visible outside of it
+    let err = Codegen::generate_main_bytecode(&EVMVersion::default(), &contract, None).unwrap_err();
+    assert_eq!(err.kind, CodegenErrorKind::MissingConstantDefinition("X".to_string()));
+
+    let source = r#"
+        #define macro MAIN() = takes(0) returns(0) {
+            const X = 0x20
+            [X] mload
+            label:
+                [X] mstore
+        }
+    "#;
+    let mut contract = try_parse(source).unwrap();
+    contract.derive_storage_pointers();
+    let bytecode =
+        Codegen::generate_main_bytecode(&EVMVersion::default(), &contract, None).unwrap();
+    assert_eq!(bytecode, "6020515b602052");
+}
+
+#[test]
+fn test_macro_local_constant_shadowing() {
+    let source = r#"
+        #define constant X = 0x01
+
+        #define macro MAIN() = takes(0) returns(0) {
+            const X = 0x20
+            [X]
+        }
+    "#;
+    let err = try_parse(source).unwrap_err();
+    assert_eq!(err.kind, ParserErrorKind::ShadowedConstant("X".to_string()));
+
+    let source = r#"
+        #define macro MAIN() = takes(0) returns(0) {
+            const X = 0x20
+            const X = 0x40
+            [X]
+        }
+    "#;
+    let err = try_parse(source).unwrap_err();
+    assert_eq!(err.kind, ParserErrorKind::ShadowedConstant("X".to_string()));
+}
+
+#[test]
+fn test_macro_local_constant_arguments() {
+    let source = r#"
+        #define macro PUSHER(value) = takes(0) returns(1) {
+            <value>
+        }
+
+        #define macro MAIN() = takes(0) returns(0) {
+            const SIZE = 0x20
+            const SHIFT = 0x04
+            PUSHER(SIZE)
+            PUSHER(__SHL(SHIFT, 0x01))
+            __SHR(SHIFT, 0x40)
+            label:
+                PUSHER(SIZE)
+        }
+    "#;
+    let mut contract = try_parse(source).unwrap();
+    contract.derive_storage_pointers();
+    let bytecode =
+        Codegen::generate_main_bytecode(&EVMVersion::default(), &contract, None).unwrap();
+    assert_eq!(bytecode, "6020601060045b6020");
+}
@@ -0,0 +1,54 @@
+mod common;
+
+use common::parse;
+use huff_codegen::Codegen;
+use huff_utils::prelude::*;
+
+fn main_bytecode(invocations: &str) -> Result<String, CodegenError> {
+    let source = format!(
+        r#"
+        #define macro FOO(x = 0x20) = takes(0) returns(0) {{
+            <x>
+        }}
+
+        #define macro BAR(x = 0x01, y) = takes(0) returns(0) {{
+            <x> <y>
+        }}
+
+        #define macro MAIN() = takes(0) returns(0) {{
+            {invocations}
+        }}
+    "#
+    );
+    Codegen::generate_main_bytecode(&EVMVersion::default(), &parse(&source), None)
+}
+
+#[test]
+fn test_parses_macro_parameter_defaults() {
+    let contract = parse("#define macro FOO(x = 0x20, y) = takes(0) returns(0) { <x> <y> }");
+    let params = &contract.macros[0].parameters;
+    assert_eq!(params[0].name.as_deref(), Some("x"));
+    assert_eq!(params[0].default, Some(MacroArg::Literal(str_to_bytes32("20"))));
+    assert_eq!(params[1].name.as_deref(), Some("y"));
+    assert_eq!(params[1].default, None);
+}
+
+#[test]
+fn test_omitted_argument_uses_default() {
+    assert_eq!(main_bytecode("FOO()").unwrap(), main_bytecode("FOO(0x20)").unwrap());
+    assert_eq!(main_bytecode("FOO(x: 0x20)").unwrap(), main_bytecode("FOO(0x20)").unwrap());
+    assert_eq!(main_bytecode("FOO(0x40)").unwrap(), "6040");
+    assert_eq!(main_bytecode("BAR(y: 0x02)").unwrap(), "60016002");
+}
+
+#[test]
+fn test_missing_argument_without_default() {
+    let err = main_bytecode("BAR(0x01)").unwrap_err();
+    assert_eq!(
+        err.kind,
+        CodegenErrorKind::MissingMacroArgument {
+            macro_name: "BAR".to_string(),
+            param: "y".to_string()
+        }
+    );
+}
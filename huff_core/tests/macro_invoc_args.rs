@@ -63,7 +63,8 @@ fn test_all_opcodes_in_macro_args() {
         let mut contract = parser.parse().unwrap();
         contract.derive_storage_pointers();
 
-        let evm_version = EVMVersion::default();
+        // Target the latest version so that every opcode is available
+        let evm_version = EVMVersion::new(SupportedEVMVersions::Cancun);
 
         // Create main and constructor bytecode
         let main_bytecode = Codegen::generate_main_bytecode(&evm_version, &contract, None).unwrap();
@@ -308,3 +309,70 @@ fn test_bubbled_arg_with_different_name() {
     // Check the bytecode
     assert_eq!(main_bytecode, expected_bytecode);
 }
+
+#[test]
+fn test_builtin_macro_arg() {
+    let source = r#"
+          #define function transfer(address,uint256) nonpayable returns ()
+
+          #define macro MACRO_A(selector) = takes(0) returns(0) {
+            <selector> 0x00 mstore
+          }
+          #define macro MACRO_B(sig) = takes(0) returns(0) {
+            MACRO_A(<sig>)
+          }
+          #define macro MAIN() = takes(0) returns(0) {
+            MACRO_A(__FUNC_SIG(transfer))
+            MACRO_B(__FUNC_SIG("approve(address,uint256)"))
+          }
+      "#;
+
+    // Lex + Parse
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let lexer = Lexer::new(flattened_source.source);
+    let tokens = lexer.into_iter().map(|x| x.unwrap()).collect::<Vec<Token>>();
+    let mut parser = Parser::new(tokens, None);
+    let mut contract = parser.parse().unwrap();
+    contract.derive_storage_pointers();
+
+    let evm_version = EVMVersion::default();
+
+    // Create main and constructor bytecode
+    let main_bytecode = Codegen::generate_main_bytecode(&evm_version, &contract, None).unwrap();
+
+    // The selectors are pushed wherever the argument is referenced
+    let expected_bytecode = "63a9059cbb5f5263095ea7b35f52";
+
+    // Check the bytecode
+    assert_eq!(main_bytecode, expected_bytecode);
+}
+
+#[test]
+fn test_zero_literal_macro_arg_uses_push0() {
+    let source = r#"
+        #define macro STORE(slot) = takes(1) returns(0) {
+            <slot> sstore
+        }
+        #define macro MAIN() = takes(0) returns(0) {
+            0x01 STORE(0x00)
+        }
+    "#;
+
+    // Lex + Parse
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let lexer = Lexer::new(flattened_source.source);
+    let tokens = lexer.into_iter().map(|x| x.unwrap()).collect::<Vec<Token>>();
+    let mut parser = Parser::new(tokens, None);
+    let mut contract = parser.parse().unwrap();
+    contract.derive_storage_pointers();
+
+    // Shanghai and later push zero with PUSH0
+    let main_bytecode =
+        Codegen::generate_main_bytecode(&EVMVersion::default(), &contract, None).unwrap();
+    assert_eq!(main_bytecode, "60015f55");
+
+    // Earlier versions fall back to PUSH1 0x00
+    let paris = EVMVersion::new(SupportedEVMVersions::Paris);
+    let main_bytecode = Codegen::generate_main_bytecode(&paris, &contract, None).unwrap();
+    assert_eq!(main_bytecode, "6001600055");
+}
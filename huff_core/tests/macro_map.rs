@@ -0,0 +1,57 @@
+use std::{collections::HashMap, sync::Arc};
+
+use huff_core::Compiler;
+use huff_utils::prelude::EVMVersion;
+
+#[test]
+fn test_macro_map() {
+    let source = r#"
+        #define macro FIRST() = takes(0) returns(1) {
+            0x01 0x02 add
+        }
+
+        #define macro SECOND() = takes(1) returns(0) {
+            0x00 mstore
+            0x20 0x00 return
+        }
+
+        #define macro MAIN() = takes(0) returns(0) {
+            FIRST()
+            SECOND()
+        }
+    "#;
+
+    let file_name = String::from("main.huff");
+    let file_sources = HashMap::from([(file_name.clone(), String::from(source))]);
+
+    let evm_version = EVMVersion::default();
+    let mut compiler = Compiler::new_in_memory(
+        &evm_version,
+        Arc::new(vec![file_name]),
+        file_sources,
+        None,
+        None,
+        None,
+        None,
+        false,
+    );
+
+    // The macro map is only generated when requested
+    let artifacts = compiler.execute().unwrap();
+    assert_eq!(artifacts[0].macro_map, None);
+
+    compiler.macro_map = true;
+    let artifacts = compiler.execute().unwrap();
+    let artifact = &artifacts[0];
+    let macro_map = artifact.macro_map.as_ref().unwrap();
+    assert_eq!(macro_map.len(), 3);
+
+    // FIRST: 6001 6002 01
+    // SECOND: 5f 52 6020 5f f3
+    let runtime_len = artifact.runtime.len() / 2;
+    assert_eq!(runtime_len, 11);
+    assert_eq!(macro_map["MAIN"], (0, runtime_len));
+    assert_eq!(macro_map["FIRST"], (0, 5));
+    assert_eq!(macro_map["SECOND"], (5, 6));
+    assert_eq!(&artifact.runtime[10..22], "5f5260205ff3");
+}
@@ -0,0 +1,133 @@
+mod common;
+
+use std::{collections::HashMap, sync::Arc};
+
+use common::parse;
+use huff_codegen::Codegen;
+use huff_core::Compiler;
+use huff_utils::prelude::*;
+
+fn optimized_main_bytecode(source: &str, evm_version: &EVMVersion) -> String {
+    let mut contract = parse(source);
+    contract.coalesce_memory_clears(evm_version);
+
+    Codegen::generate_main_bytecode(evm_version, &contract, None).unwrap()
+}
+
+#[test]
+fn test_coalesces_memory_clears() {
+    let source = r#"
+        #define macro MAIN() = takes(0) returns(0) {
+            0x00 0x00 mstore
+            0x00 0x20 mstore
+            0x00 0x40 mstore
+        }
+    "#;
+
+    let evm_version = EVMVersion::new(SupportedEVMVersions::Paris);
+    let main_bytecode = optimized_main_bytecode(source, &evm_version);
+
+    // PUSH1 0x00 DUP1 PUSH1 0x00 MSTORE DUP1 PUSH1 0x20 MSTORE PUSH1 0x40 MSTORE
+    assert_eq!(main_bytecode, "60008060005280602052604052");
+}
+
+#[test]
+fn test_coalesces_explicit_push1_memory_clears() {
+    let source = r#"
+        #define macro MAIN() = takes(0) returns(0) {
+            push1 0x00 push1 0x80 mstore
+            push1 0x00 push2 0x0100 mstore
+        }
+    "#;
+
+    // Explicit PUSH1 zeros are coalesced even when PUSH0 is available
+    let main_bytecode = optimized_main_bytecode(source, &EVMVersion::default());
+    assert_eq!(main_bytecode, "60008060805261010052");
+}
+
+#[test]
+fn test_coalesces_memory_clears_inside_labels() {
+    let source = r#"
+        #define macro MAIN() = takes(0) returns(0) {
+            clear:
+                0x00 0x00 mstore
+                0x00 0x20 mstore
+        }
+    "#;
+
+    let evm_version = EVMVersion::new(SupportedEVMVersions::Paris);
+    let main_bytecode = optimized_main_bytecode(source, &evm_version);
+    assert_eq!(main_bytecode, "5b600080600052602052");
+}
+
+#[test]
+fn test_does_not_coalesce_memory_clears() {
+    let paris = EVMVersion::new(SupportedEVMVersions::Paris);
+    let sources = [
+        // A single memory clear
+        (
+            r#"#define macro MAIN() = takes(0) returns(0) { 0x00 0x00 mstore }"#,
+            &paris,
+            "6000600052",
+        ),
+        // Non-zero values
+        (
+            r#"#define macro MAIN() = takes(0) returns(0) { 0x01 0x00 mstore 0x01 0x20 mstore }"#,
+            &paris,
+            "60016000526001602052",
+        ),
+        // Stores separated by another opcode
+        (
+            r#"#define macro MAIN() = takes(0) returns(0) { 0x00 0x00 mstore pop 0x00 0x20 mstore }"#,
+            &paris,
+            "6000600052506000602052",
+        ),
+        // Stores separated by a label
+        (
+            r#"#define macro MAIN() = takes(0) returns(0) { 0x00 0x00 mstore loop: 0x00 0x20 mstore }"#,
+            &paris,
+            "60006000525b6000602052",
+        ),
+    ];
+
+    for (source, evm_version, expected) in sources {
+        assert_eq!(optimized_main_bytecode(source, evm_version), expected);
+    }
+
+    // PUSH0 is cheaper than DUP1
+    let source =
+        r#"#define macro MAIN() = takes(0) returns(0) { 0x00 0x00 mstore 0x00 0x20 mstore }"#;
+    assert_eq!(optimized_main_bytecode(source, &EVMVersion::default()), "5f5f525f602052");
+}
+
+#[test]
+fn test_optimize_flag_coalesces_memory_clears() {
+    let source = r#"
+        #define macro MAIN() = takes(0) returns(0) {
+            push1 0x00 0x00 mstore
+            push1 0x00 0x20 mstore
+        }
+    "#;
+
+    let file_name = String::from("contracts/main.huff");
+    let file_sources = HashMap::from([(file_name.clone(), String::from(source))]);
+
+    let evm_version = EVMVersion::default();
+    let mut compiler = Compiler::new_in_memory(
+        &evm_version,
+        Arc::new(vec![file_name]),
+        file_sources,
+        None,
+        None,
+        None,
+        None,
+        false,
+    );
+
+    let unoptimized = compiler.execute().unwrap();
+    assert_eq!(unoptimized[0].runtime, "60005f526000602052");
+
+    compiler.optimize = true;
+    let optimized = compiler.execute().unwrap();
+    assert_eq!(optimized[0].runtime, "6000805f52602052");
+}
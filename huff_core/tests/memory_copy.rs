@@ -0,0 +1,68 @@
+mod common;
+
+use common::parse;
+use huff_codegen::Codegen;
+use huff_utils::prelude::*;
+
+#[test]
+fn test_mcopy_on_cancun() {
+    let contract = parse(
+        r#"
+        #define macro MAIN() = takes(0) returns(0) {
+            0x20 0x00 0x40 mcopy
+        }
+    "#,
+    );
+
+    let evm_version = EVMVersion::new(SupportedEVMVersions::Cancun);
+    let bytecode = Codegen::generate_main_bytecode(&evm_version, &contract, None).unwrap();
+    assert_eq!(bytecode, "60205f60405e");
+}
+
+#[test]
+fn test_mcopy_before_cancun_fails() {
+    let contract = parse(
+        r#"
+        #define macro MAIN() = takes(0) returns(0) {
+            0x20 0x00 0x40
+            copy:
+                mcopy
+        }
+    "#,
+    );
+
+    let evm_version = EVMVersion::new(SupportedEVMVersions::Shanghai);
+    let err = Codegen::generate_main_bytecode(&evm_version, &contract, None).unwrap_err();
+    assert_eq!(
+        err.kind,
+        CodegenErrorKind::UnsupportedOpcode {
+            opcode: Opcode::Mcopy,
+            evm_version: "shanghai".to_string()
+        }
+    );
+}
+
+#[test]
+fn test_mcopy_macro_arg_before_cancun_fails() {
+    let contract = parse(
+        r#"
+        #define macro COPY(op) = takes(3) returns(0) {
+            <op>
+        }
+
+        #define macro MAIN() = takes(0) returns(0) {
+            0x20 0x00 0x40 COPY(mcopy)
+        }
+    "#,
+    );
+
+    let evm_version = EVMVersion::new(SupportedEVMVersions::Paris);
+    let err = Codegen::generate_main_bytecode(&evm_version, &contract, None).unwrap_err();
+    assert_eq!(
+        err.kind,
+        CodegenErrorKind::UnsupportedOpcode {
+            opcode: Opcode::Mcopy,
+            evm_version: "paris".to_string()
+        }
+    );
+}
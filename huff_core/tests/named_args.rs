@@ -0,0 +1,48 @@
+mod common;
+
+use common::try_parse;
+use huff_codegen::Codegen;
+use huff_utils::prelude::*;
+
+fn main_bytecode(invocations: &str) -> String {
+    let source = format!(
+        r#"
+        #define macro TRANSFER(to, amount, err) = takes(0) returns(0) {{
+            <to> <amount> <err>
+        }}
+
+        #define macro FORWARD(dest) = takes(0) returns(0) {{
+            TRANSFER(err: fail, amount: 0x02, to: <dest>)
+        }}
+
+        #define macro MAIN() = takes(0) returns(0) {{
+            {invocations}
+            fail:
+                0x00 0x00 revert
+        }}
+    "#
+    );
+    let mut contract = try_parse(&source).unwrap();
+    contract.derive_storage_pointers();
+    Codegen::generate_main_bytecode(&EVMVersion::default(), &contract, None).unwrap()
+}
+
+#[test]
+fn test_named_args_match_positional_args() {
+    let positional = main_bytecode("TRANSFER(0x01, 0x02, fail) TRANSFER(0x01, 0x02, fail)");
+    let named = main_bytecode(
+        "TRANSFER(to: 0x01, amount: 0x02, err: fail) TRANSFER(amount: 0x02, err: fail, to: 0x01)",
+    );
+    let forwarded = main_bytecode("FORWARD(0x01) FORWARD(dest: 0x01)");
+    assert_eq!(named, positional);
+    assert_eq!(forwarded, positional);
+}
+
+#[test]
+fn test_mixed_named_and_positional_args() {
+    for invocation in ["TRANSFER(0x01, amount: 0x02)", "TRANSFER(to: 0x01, 0x02)"] {
+        let source = format!("#define macro MAIN() = takes(0) returns(0) {{ {invocation} }}");
+        let err = try_parse(&source).unwrap_err();
+        assert_eq!(err.kind, ParserErrorKind::MixedMacroArgs);
+    }
+}
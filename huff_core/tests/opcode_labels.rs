@@ -0,0 +1,23 @@
+mod common;
+
+use common::parse;
+use huff_codegen::Codegen;
+use huff_utils::prelude::*;
+
+#[test]
+fn test_uppercase_label_named_like_an_opcode() {
+    let contract = parse(
+        r#"
+        #define macro MAIN() = takes(0) returns(0) {
+            0x00 calldataload RETURN jumpi
+            RETURN:
+                stop
+        }
+    "#,
+    );
+
+    // The label is jumped to rather than the RETURN opcode being emitted
+    let bytecode =
+        Codegen::generate_main_bytecode(&EVMVersion::default(), &contract, None).unwrap();
+    assert_eq!(bytecode, "5f35610006575b00");
+}
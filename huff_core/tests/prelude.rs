@@ -0,0 +1,59 @@
+mod common;
+
+use common::try_parse;
+use huff_codegen::Codegen;
+use huff_utils::prelude::*;
+
+const PRELUDE: &str = r#"
+    #define constant OWNER_SLOT = FREE_STORAGE_POINTER()
+
+    #define macro ONLY_OWNER() = takes(0) returns(0) {
+        [OWNER_SLOT] sload caller eq ok jumpi
+        0x00 dup1 revert
+        ok:
+    }
+"#;
+
+#[test]
+fn test_prelude_macro_is_usable_without_import() {
+    let cg = Codegen::new().with_prelude(PRELUDE.to_string()).unwrap();
+    let contract = try_parse(
+        r#"
+        #define macro MAIN() = takes(0) returns(0) {
+            ONLY_OWNER()
+            [OWNER_SLOT] sload
+        }
+    "#,
+    )
+    .unwrap();
+
+    let mut contract = cg.link_prelude(contract).unwrap();
+    contract.derive_storage_pointers();
+    let bytecode =
+        Codegen::generate_main_bytecode(&EVMVersion::default(), &contract, None).unwrap();
+    assert_eq!(bytecode, "5f54331461000b575f80fd5b5f54");
+}
+
+#[test]
+fn test_prelude_collision() {
+    let cg = Codegen::new().with_prelude(PRELUDE.to_string()).unwrap();
+    let contract = try_parse(
+        r#"
+        #define macro ONLY_OWNER() = takes(0) returns(0) {}
+
+        #define macro MAIN() = takes(0) returns(0) {
+            ONLY_OWNER()
+        }
+    "#,
+    )
+    .unwrap();
+
+    let err = cg.link_prelude(contract).unwrap_err();
+    assert_eq!(err.kind, CodegenErrorKind::LinkCollision("ONLY_OWNER".to_string()));
+}
+
+#[test]
+fn test_invalid_prelude() {
+    let err = Codegen::new().with_prelude("#define macro".to_string()).err().unwrap();
+    assert!(matches!(err, CompilerError::ParserError(_)));
+}
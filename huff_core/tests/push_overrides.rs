@@ -161,3 +161,35 @@ fn test_literal_to_push0() {
     let cbytes = Codegen::generate_main_bytecode(&EVMVersion::default(), &contract, None).unwrap();
     assert_eq!(cbytes, String::from("5f5f5f60005f6001"));
 }
+
+#[test]
+fn test_trims_push_override_literals_when_enabled() {
+    let source = r#"
+        #define macro MAIN() = takes(0) returns(0) {
+            push3 0x0000ff
+            label:
+                push2 0x0001
+            0x0000ff
+        }
+    "#;
+
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let lexer = Lexer::new(flattened_source.source);
+    let tokens = lexer.into_iter().map(|x| x.unwrap()).collect::<Vec<Token>>();
+    let mut parser = Parser::new(tokens, None);
+    let mut contract = parser.parse().unwrap();
+    contract.derive_storage_pointers();
+
+    // By default, explicit pushes keep their width
+    let cg = Codegen::new();
+    cg.resolve_push_literals(&mut contract);
+    let untrimmed =
+        Codegen::generate_main_bytecode(&EVMVersion::default(), &contract, None).unwrap();
+    assert_eq!(untrimmed, "620000ff5b61000160ff");
+
+    // With the flag set, the leading zero bytes are stripped
+    let cg = Codegen { trim_push_literals: true, ..Codegen::new() };
+    cg.resolve_push_literals(&mut contract);
+    let trimmed = Codegen::generate_main_bytecode(&EVMVersion::default(), &contract, None).unwrap();
+    assert_eq!(trimmed, "60ff5b600160ff");
+}
@@ -0,0 +1,52 @@
+mod common;
+
+use common::try_parse;
+use huff_codegen::Codegen;
+use huff_utils::prelude::*;
+
+#[test]
+fn test_receive_and_fallback_abi_entries() {
+    let source = r#"
+        #define function owner() view returns (address)
+        #define receive()
+        #define fallback() payable
+
+        #define macro MAIN() = takes(0) returns(0) {}
+    "#;
+    let contract = try_parse(source).unwrap();
+    assert!(contract.receive);
+    assert_eq!(contract.fallback, Some(FunctionType::Payable));
+
+    let mut cg = Codegen::new();
+    let abi = cg.abi_gen(contract, None).unwrap();
+    assert!(abi.receive);
+    assert!(abi.fallback);
+    assert!(abi.fallback_payable);
+    assert_eq!(abi.functions.len(), 1);
+
+    let ts = abi.to_typescript();
+    let array = ts
+        .strip_prefix("export const abi = ")
+        .and_then(|ts| ts.strip_suffix(" as const;\n"))
+        .unwrap();
+    let entries: Vec<serde_json::Value> = serde_json::from_str(array).unwrap();
+    assert_eq!(entries[1], serde_json::json!({ "type": "receive", "stateMutability": "payable" }));
+    assert_eq!(entries[2], serde_json::json!({ "type": "fallback", "stateMutability": "payable" }));
+}
+
+#[test]
+fn test_fallback_defaults_to_nonpayable() {
+    let contract = try_parse("#define fallback()").unwrap();
+    assert!(!contract.receive);
+    assert_eq!(contract.fallback, Some(FunctionType::NonPayable));
+
+    let abi = Abi::from(contract);
+    assert!(abi.fallback);
+    assert!(!abi.fallback_payable);
+}
+
+#[test]
+fn test_nonpayable_receive_fails() {
+    let err = try_parse("#define receive() nonpayable").unwrap_err();
+    assert_eq!(err.kind, ParserErrorKind::UnexpectedType(TokenKind::NonPayable));
+}
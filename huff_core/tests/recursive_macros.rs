@@ -0,0 +1,96 @@
+mod common;
+
+use common::parse;
+use huff_codegen::Codegen;
+use huff_utils::prelude::*;
+
+fn main_bytecode(source: &str) -> Result<String, CodegenError> {
+    let contract = parse(source);
+    Codegen::generate_main_bytecode(&EVMVersion::default(), &contract, None)
+}
+
+#[test]
+fn test_self_invoking_macro() {
+    let source: &str = r#"
+        #define macro A() = takes (0) returns (0) {
+            A()
+        }
+
+        #define macro MAIN() = takes (0) returns (0) {
+            A()
+        }
+    "#;
+
+    let err = main_bytecode(source).unwrap_err();
+    assert_eq!(
+        err.kind,
+        CodegenErrorKind::RecursiveMacroInvocation(vec!["A".to_string(), "A".to_string()])
+    );
+    assert!(!err.span.0.is_empty());
+}
+
+#[test]
+fn test_mutually_recursive_macros() {
+    let source: &str = r#"
+        #define macro A() = takes (0) returns (0) {
+            B()
+        }
+
+        #define macro B() = takes (0) returns (0) {
+            0x01 A()
+        }
+
+        #define macro MAIN() = takes (0) returns (0) {
+            A()
+        }
+    "#;
+
+    let err = main_bytecode(source).unwrap_err();
+    assert_eq!(
+        err.kind,
+        CodegenErrorKind::RecursiveMacroInvocation(vec![
+            "A".to_string(),
+            "B".to_string(),
+            "A".to_string()
+        ])
+    );
+}
+
+#[test]
+fn test_recursive_main() {
+    let source: &str = r#"
+        #define macro MAIN() = takes (0) returns (0) {
+            0x01 MAIN()
+        }
+    "#;
+
+    let err = main_bytecode(source).unwrap_err();
+    assert_eq!(
+        err.kind,
+        CodegenErrorKind::RecursiveMacroInvocation(vec!["MAIN".to_string(), "MAIN".to_string()])
+    );
+}
+
+#[test]
+fn test_sized_macro_invoking_its_invoker() {
+    // B is only sized from within A, so the cycle goes through `__codesize`
+    let source: &str = r#"
+        #define macro A() = takes (0) returns (0) {
+            __codesize(B)
+        }
+
+        #define macro B() = takes (0) returns (0) {
+            A()
+        }
+
+        #define macro MAIN() = takes (0) returns (0) {
+            A()
+        }
+    "#;
+
+    let err = main_bytecode(source).unwrap_err();
+    assert_eq!(
+        err.kind,
+        CodegenErrorKind::CircularCodesize(vec!["B".to_string(), "A".to_string(), "B".to_string()])
+    );
+}
@@ -0,0 +1,65 @@
+mod common;
+
+use common::parse;
+use huff_codegen::Codegen;
+use huff_utils::prelude::*;
+
+#[test]
+fn test_compile_report() {
+    let source = r#"
+        #define constant OWNER = FREE_STORAGE_POINTER()
+        #define constant UNUSED = 0x01
+
+        #define jumptable__packed SWITCH {
+            zero one
+        }
+
+        #define macro UNUSED_MACRO() = takes(0) returns(0) {
+            0x01 0x02 add
+        }
+
+        #define macro CONSTRUCTOR() = takes(0) returns(0) {
+            caller [OWNER] sstore
+        }
+
+        #define macro MAIN() = takes(0) returns(0) {
+            __tablestart(SWITCH) pop
+            zero:
+                0x00 0x00 return
+            one:
+                0x00 0x00 revert
+        }
+    "#;
+
+    let contract = parse(source);
+
+    let report = Codegen::report(&EVMVersion::default(), &contract).unwrap();
+
+    for key in [
+        "runtime_size",
+        "init_size",
+        "table_count",
+        "tables",
+        "label_count",
+        "unused_macros",
+        "unused_constants",
+        "warnings",
+    ] {
+        assert!(report.get(key).is_some(), "missing report key \"{key}\"");
+    }
+
+    // PUSH2 POP JUMPDEST PUSH0 PUSH0 RETURN JUMPDEST PUSH0 PUSH0 REVERT + 4 table bytes
+    assert_eq!(report["runtime_size"], 16);
+    // CALLER PUSH0 SSTORE + 9 byte bootstrap + runtime
+    assert_eq!(report["init_size"], 3 + 9 + 16);
+    assert_eq!(report["table_count"], 1);
+    assert_eq!(report["tables"][0]["name"], "SWITCH");
+    assert_eq!(report["tables"][0]["size"], 4);
+    assert_eq!(report["label_count"], 2);
+    assert_eq!(report["unused_macros"], serde_json::json!(["UNUSED_MACRO"]));
+    assert_eq!(report["unused_constants"], serde_json::json!(["UNUSED"]));
+    assert_eq!(
+        report["warnings"],
+        serde_json::json!(["Unused macro \"UNUSED_MACRO\"", "Unused constant \"UNUSED\""])
+    );
+}
@@ -0,0 +1,83 @@
+mod common;
+
+use common::parse;
+use huff_codegen::Codegen;
+use huff_utils::prelude::*;
+
+#[test]
+fn test_source_map_of_invoked_macro() {
+    let source: &str = r#"
+        #define macro STORE() = takes (1) returns (0) {
+            0x00 mstore
+        }
+
+        #define macro MAIN() = takes (0) returns (0) {
+            0x2a
+            STORE()
+            0x20 0x00 return
+        }
+    "#;
+
+    // Parse tokens
+    let contract = parse(source);
+
+    // PUSH1 0x2a | PUSH0 MSTORE | PUSH1 0x20 PUSH0 RETURN
+    let main_bytecode =
+        Codegen::generate_main_bytecode(&EVMVersion::default(), &contract, None).unwrap();
+    assert_eq!(main_bytecode, "602a5f5260205ff3");
+
+    let mut cg = Codegen::new();
+    let source_map = cg.generate_source_map(&EVMVersion::default(), &contract, None).unwrap();
+
+    // Every instruction is mapped
+    assert_eq!(source_map.keys().copied().collect::<Vec<_>>(), vec![0, 2, 3, 4, 6, 7]);
+
+    // The MSTORE at offset 3 maps back to the body of STORE rather than its invocation
+    let span = &source_map[&3].0[0];
+    assert!(source[span.start..].starts_with("mstore"));
+    assert_eq!(source[..span.start].lines().count(), 3);
+
+    // The RETURN at offset 7 maps to MAIN
+    let span = &source_map[&7].0[0];
+    assert!(source[span.start..].starts_with("return"));
+
+    // The source map is stored on the artifact
+    assert_eq!(cg.artifact.unwrap().source_map, Some(source_map));
+}
+
+#[test]
+fn test_bytecode_provenance_of_invoked_macro() {
+    let source: &str = r#"
+        #define macro STORE() = takes (1) returns (0) {
+            0x00 mstore
+        }
+
+        #define macro MAIN() = takes (0) returns (0) {
+            0x2a
+            STORE()
+            0x20 0x00 return
+        }
+    "#;
+
+    let contract = parse(source);
+
+    let (bytecode, provenance) = Codegen::generate_main_bytecode_with_stack_comments(
+        &EVMVersion::default(),
+        &contract,
+        None,
+    )
+    .unwrap();
+
+    // The bytecode matches the plain main bytecode
+    assert_eq!(bytecode, "602a5f5260205ff3");
+
+    // Every instruction is attributed to the macro it was written in
+    assert_eq!(
+        provenance.iter().map(|(o, m, _)| (*o, m.as_str())).collect::<Vec<_>>(),
+        vec![(0, "MAIN"), (2, "STORE"), (3, "STORE"), (4, "MAIN"), (6, "MAIN"), (7, "MAIN")]
+    );
+
+    // Along with the span of its source
+    let span = &provenance[2].2 .0[0];
+    assert!(source[span.start..].starts_with("mstore"));
+}
@@ -0,0 +1,109 @@
+mod common;
+
+use common::parse;
+use huff_codegen::Codegen;
+use huff_utils::prelude::*;
+
+#[test]
+fn test_balanced_macros_are_verified() {
+    let contract = parse(
+        r#"
+        #define macro ADD_ONE() = takes(1) returns(1) {
+            0x01 add
+        }
+
+        #define macro MAIN() = takes(0) returns(0) {
+            0x04 calldataload
+            ADD_ONE()
+            0x00 mstore
+            0x20 0x00 return
+        }
+    "#,
+    );
+
+    let mut cg = Codegen::new();
+    cg.verify_stack_balance(&contract).unwrap();
+    assert!(cg.warnings.is_empty());
+}
+
+#[test]
+fn test_imbalanced_macro_fails() {
+    let contract = parse(
+        r#"
+        #define macro LEAKS() = takes(1) returns(1) {
+            dup1 0x01 add
+        }
+
+        #define macro MAIN() = takes(0) returns(0) {
+            0x00 LEAKS() pop pop
+        }
+    "#,
+    );
+
+    let mut cg = Codegen::new();
+    let err = cg.verify_stack_balance(&contract).unwrap_err();
+    assert_eq!(
+        err.kind,
+        CodegenErrorKind::StackImbalance { macro_name: "LEAKS".to_string(), expected: 1, got: 2 }
+    );
+    assert_eq!(err.span, contract.macros[0].span);
+}
+
+#[test]
+fn test_stack_underflow_fails() {
+    let contract = parse(
+        r#"
+        #define macro MAIN() = takes(1) returns(0) {
+            add 0x00 0x00
+        }
+    "#,
+    );
+
+    let mut cg = Codegen::new();
+    let err = cg.verify_stack_balance(&contract).unwrap_err();
+    assert_eq!(
+        err.kind,
+        CodegenErrorKind::StackImbalance { macro_name: "MAIN".to_string(), expected: 0, got: -1 }
+    );
+}
+
+#[test]
+fn test_macro_with_jumps_is_only_warned() {
+    let contract = parse(
+        r#"
+        #define macro MAIN() = takes(0) returns(0) {
+            0x00 calldataload done jumpi
+            0x01
+            done:
+                stop
+        }
+    "#,
+    );
+
+    let mut cg = Codegen::new();
+    cg.verify_stack_balance(&contract).unwrap();
+    assert_eq!(cg.warnings.len(), 1);
+    assert_eq!(cg.warnings[0].kind, CodegenWarningKind::UnverifiedStack("MAIN".to_string()));
+}
+
+#[test]
+fn test_lint_verifies_stack_when_enabled() {
+    let contract = parse(
+        r#"
+        #define macro MAIN() = takes(0) returns(0) {
+            0x01
+        }
+    "#,
+    );
+
+    // Stack balance isn't verified by default
+    let mut cg = Codegen::new();
+    cg.lint(&contract).unwrap();
+
+    let mut cg = Codegen { verify_stack: true, ..Codegen::new() };
+    let err = cg.lint(&contract).unwrap_err();
+    assert_eq!(
+        err.kind,
+        CodegenErrorKind::StackImbalance { macro_name: "MAIN".to_string(), expected: 0, got: 1 }
+    );
+}
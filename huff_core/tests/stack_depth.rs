@@ -0,0 +1,71 @@
+mod common;
+
+use common::parse;
+use huff_codegen::Codegen;
+use huff_utils::prelude::*;
+
+#[test]
+fn test_unreachable_stack_item_is_reported() {
+    let contract = parse(
+        r#"
+        #define macro PUSH_EIGHT() = takes(0) returns(8) {
+            0x01 0x02 0x03 0x04 0x05 0x06 0x07 0x08
+        }
+
+        #define macro MAIN() = takes(1) returns(0) {
+            PUSH_EIGHT()
+            0x09 0x0a 0x0b 0x0c 0x0d 0x0e 0x0f 0x10
+            dup16
+        }
+    "#,
+    );
+
+    // The input item sits 17 deep, below the reach of DUP16
+    let mut cg = Codegen::new();
+    cg.lint(&contract).unwrap();
+    assert_eq!(cg.warnings.len(), 1);
+    assert_eq!(cg.warnings[0].kind, CodegenWarningKind::StackTooDeep("MAIN".to_string(), 17));
+    assert_eq!(cg.warnings[0].span, contract.macros[1].statements[9].span);
+}
+
+#[test]
+fn test_reachable_stack_is_not_reported() {
+    let contract = parse(
+        r#"
+        #define macro MAIN() = takes(0) returns(0) {
+            0x01 0x02 0x03 0x04 0x05 0x06 0x07 0x08
+            0x09 0x0a 0x0b 0x0c 0x0d 0x0e 0x0f 0x10
+            dup16 pop
+            0x11 pop
+            swap15
+            end jump
+            end:
+                0x11 swap1
+        }
+    "#,
+    );
+
+    // The stack never holds more than 16 items when a DUP or SWAP executes, and tracking stops
+    // at the label
+    let mut cg = Codegen::new();
+    cg.lint(&contract).unwrap();
+    assert!(cg.warnings.is_empty());
+}
+
+#[test]
+fn test_shallow_access_on_deep_stack_is_not_reported() {
+    let contract = parse(
+        r#"
+        #define macro MAIN() = takes(1) returns(0) {
+            0x01 0x02 0x03 0x04 0x05 0x06 0x07 0x08
+            0x09 0x0a 0x0b 0x0c 0x0d 0x0e 0x0f 0x10
+            dup1 swap2 dup8
+        }
+    "#,
+    );
+
+    // The stack holds more than 16 items, but every DUP and SWAP stays near the top
+    let mut cg = Codegen::new();
+    cg.lint(&contract).unwrap();
+    assert!(cg.warnings.is_empty());
+}
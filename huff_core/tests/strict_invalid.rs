@@ -0,0 +1,48 @@
+mod common;
+
+use common::parse;
+use huff_codegen::Codegen;
+use huff_utils::prelude::*;
+
+#[test]
+fn test_explicit_invalid_is_allowed() {
+    let contract = parse(
+        r#"
+        #define macro MAIN() = takes(0) returns(0) {
+            0x00 calldataload ok jumpi
+            __INVALID()
+            ok:
+                stop
+        }
+    "#,
+    );
+
+    let mut cg = Codegen { strict_invalid: true, ..Codegen::new() };
+    cg.lint(&contract).unwrap();
+
+    let bytecode =
+        Codegen::generate_main_bytecode(&EVMVersion::default(), &contract, None).unwrap();
+    assert_eq!(bytecode, "5f3561000757fe5b00");
+}
+
+#[test]
+fn test_incidental_invalid_is_rejected() {
+    let contract = parse(
+        r#"
+        #define macro MAIN() = takes(0) returns(0) {
+            0x00 calldataload ok jumpi
+            invalid
+            ok:
+                stop
+        }
+    "#,
+    );
+
+    // Only rejected in strict mode
+    Codegen::new().lint(&contract).unwrap();
+
+    let mut cg = Codegen { strict_invalid: true, ..Codegen::new() };
+    let err = cg.lint(&contract).unwrap_err();
+    assert_eq!(err.kind, CodegenErrorKind::IncidentalInvalid("MAIN".to_string()));
+    assert_eq!(err.span, contract.macros[0].statements[4].span);
+}
@@ -0,0 +1,63 @@
+mod common;
+
+use common::parse;
+use huff_codegen::Codegen;
+use huff_utils::prelude::*;
+
+fn compile(source: &str) -> String {
+    let contract = parse(source);
+    Codegen::generate_main_bytecode(&EVMVersion::default(), &contract, None).unwrap()
+}
+
+#[test]
+fn test_identical_tables_share_bytecode() {
+    let source = r#"
+        #define jumptable__packed FIRST {
+            one two
+        }
+
+        #define jumptable__packed SECOND {
+            one two
+        }
+
+        #define macro MAIN() = takes(0) returns(0) {
+            __tablestart(FIRST) pop
+            __tablestart(SECOND) pop
+            one:
+                0x00 0x00 revert
+            two:
+                0x00 0x00 return
+        }
+    "#;
+
+    let bytecode = compile(source);
+
+    // Both `__tablestart` invocations point at the single copy of the table
+    assert_eq!(bytecode, "61001050610010505b5f5ffd5b5f5ff30008000c");
+    assert_eq!(bytecode.matches("0008000c").count(), 1);
+}
+
+#[test]
+fn test_differing_tables_are_kept_separate() {
+    let source = r#"
+        #define jumptable__packed FIRST {
+            one two
+        }
+
+        #define jumptable__packed SECOND {
+            two one
+        }
+
+        #define macro MAIN() = takes(0) returns(0) {
+            __tablestart(FIRST) pop
+            __tablestart(SECOND) pop
+            one:
+                0x00 0x00 revert
+            two:
+                0x00 0x00 return
+        }
+    "#;
+
+    let bytecode = compile(source);
+    assert_eq!(bytecode, "61001050610014505b5f5ffd5b5f5ff30008000c000c0008");
+}
@@ -0,0 +1,34 @@
+mod common;
+
+use common::parse;
+use huff_codegen::Codegen;
+use huff_utils::prelude::*;
+
+#[test]
+fn test_table_placed_before_code() {
+    let source = r#"
+        #define jumptable__packed HEADER {
+            target other
+        }
+
+        #define macro MAIN() = takes(0) returns(0) {
+            __tablestart(HEADER) pop
+            target jump
+            other:
+                0x00 0x00 revert
+            target:
+                0x00 0x00 return
+        }
+    "#;
+
+    let mut contract = parse(source);
+
+    // By default, the table is appended after the code
+    let after = Codegen::generate_main_bytecode(&EVMVersion::default(), &contract, None).unwrap();
+    assert_eq!(after, "6100105061000c565b5f5ffd5b5f5ff3000c0008");
+
+    // Placing the table before the code shifts every label and jump destination by its size
+    contract.tables[0].placement = TablePlacement::Before;
+    let before = Codegen::generate_main_bytecode(&EVMVersion::default(), &contract, None).unwrap();
+    assert_eq!(before, "0010000c61000050610010565b5f5ffd5b5f5ff3");
+}
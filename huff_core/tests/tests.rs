@@ -1,4 +1,7 @@
+use std::{collections::HashMap, sync::Arc};
+
 use huff_codegen::Codegen;
+use huff_core::Compiler;
 use huff_lexer::*;
 use huff_parser::Parser;
 use huff_utils::{
@@ -9,6 +12,8 @@ use huff_utils::{
 #[test]
 fn test_invocation_should_fail() {
     let source: &str = r#"
+        #define function test1() nonpayable returns ()
+
         #define test MY_TEST() = takes (0) returns (0) {
             0x00 0x01 eq
         }
@@ -52,3 +57,79 @@ fn test_invocation_should_fail() {
         }
     }
 }
+
+#[test]
+fn test_invocations_are_included_only_with_flag() {
+    let source: &str = r#"
+        #define test MY_TEST() = takes (0) returns (0) {
+            0x00 0x01 eq pop
+        }
+
+        #define macro MAIN() = takes (0) returns (0) {
+            0x01
+            start:
+                MY_TEST()
+            0x02
+        }
+    "#;
+
+    // Parse tokens
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let lexer = Lexer::new(flattened_source.source);
+    let tokens = lexer.into_iter().map(|x| x.unwrap()).collect::<Vec<Token>>();
+    let mut parser = Parser::new(tokens, None);
+
+    // Parse the AST
+    let mut contract = parser.parse().unwrap();
+    contract.derive_storage_pointers();
+
+    // Tests are excluded by default
+    let mut excluded = contract.clone();
+    Codegen::new().resolve_test_invocations(&mut excluded);
+    let excluded_bytecode =
+        Codegen::generate_main_bytecode(&EVMVersion::default(), &excluded, None).unwrap();
+    assert_eq!(excluded_bytecode, "60015b6002");
+
+    // With the flag set, the test code is compiled where it is invoked
+    let mut included = contract.clone();
+    Codegen { include_tests: true, ..Codegen::new() }.resolve_test_invocations(&mut included);
+    let included_bytecode =
+        Codegen::generate_main_bytecode(&EVMVersion::default(), &included, None).unwrap();
+    assert_eq!(included_bytecode, "60015b5f600114506002");
+
+    // The bytecode only differs by the test code
+    assert_eq!(included_bytecode.replacen("5f60011450", "", 1), excluded_bytecode);
+}
+
+#[test]
+fn test_compiler_include_tests() {
+    let source = r#"
+        #define test MY_TEST() = takes (0) returns (0) {
+            0x00 0x01 eq pop
+        }
+
+        #define macro MAIN() = takes (0) returns (0) {
+            0x01
+            MY_TEST()
+            0x02
+        }
+    "#;
+    let file_name = String::from("main.huff");
+    let file_sources = HashMap::from([(file_name.clone(), String::from(source))]);
+
+    let evm_version = EVMVersion::default();
+    let mut compiler = Compiler::new_in_memory(
+        &evm_version,
+        Arc::new(vec![file_name]),
+        file_sources,
+        None,
+        None,
+        None,
+        None,
+        false,
+    );
+    assert_eq!(compiler.execute().unwrap()[0].runtime, "60016002");
+
+    compiler.include_tests = true;
+    assert_eq!(compiler.execute().unwrap()[0].runtime, "60015f600114506002");
+}
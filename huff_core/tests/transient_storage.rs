@@ -0,0 +1,38 @@
+mod common;
+
+use common::parse;
+use huff_codegen::Codegen;
+use huff_utils::prelude::*;
+
+fn compile(source: &str) -> String {
+    let contract = parse(source);
+    Codegen::generate_main_bytecode(&EVMVersion::default(), &contract, None).unwrap()
+}
+
+#[test]
+fn test_transient_storage_opcodes() {
+    let source = r#"
+        #define macro MAIN() = takes(0) returns(0) {
+            0x2a 0x01 tstore
+            0x01 tload
+        }
+    "#;
+
+    assert_eq!(compile(source), "602a60015d60015c");
+}
+
+#[test]
+fn test_transient_storage_opcode_macro_args() {
+    let source = r#"
+        #define macro ACCESS(op) = takes(1) returns(0) {
+            <op>
+        }
+
+        #define macro MAIN() = takes(0) returns(0) {
+            0x2a 0x01 ACCESS(tstore)
+            0x01 ACCESS(tload)
+        }
+    "#;
+
+    assert_eq!(compile(source), "602a60015d60015c");
+}
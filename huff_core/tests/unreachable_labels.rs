@@ -0,0 +1,81 @@
+mod common;
+
+use common::parse;
+use huff_codegen::Codegen;
+use huff_utils::prelude::*;
+
+#[test]
+fn test_dead_jumpdest_is_reported() {
+    let contract = parse(
+        r#"
+        #define macro MAIN() = takes(0) returns(0) {
+            live jump
+            dead:
+                0x00 0x00 revert
+            live:
+                0x00 0x00 return
+        }
+    "#,
+    );
+
+    let mut cg = Codegen::new();
+    cg.lint(&contract).unwrap();
+    assert_eq!(cg.warnings.len(), 1);
+    assert_eq!(cg.warnings[0].kind, CodegenWarningKind::UnreachableLabel("dead".to_string()));
+}
+
+#[test]
+fn test_reachable_jumpdests_are_not_reported() {
+    let contract = parse(
+        r#"
+        #define macro MAIN() = takes(0) returns(0) {
+            0x00 calldataload iszero skip jumpi
+            0x01 pop
+            skip:
+                target jump
+            target:
+                stop
+        }
+    "#,
+    );
+
+    // `target` follows a terminating jump, but is referenced
+    let mut cg = Codegen::new();
+    cg.lint(&contract).unwrap();
+    assert!(cg.warnings.is_empty());
+}
+
+#[test]
+fn test_unreferenced_labels_are_reported_when_enabled() {
+    let source = r#"
+        #define macro MAIN() = takes(0) returns(0) {
+            0x00 calldataload used jumpi
+            leftover:
+                0x01 pop
+            used:
+                stop
+            dead:
+                0x00 0x00 revert
+        }
+    "#;
+    let contract = parse(source);
+
+    // Off by default
+    let mut cg = Codegen::new();
+    cg.lint(&contract).unwrap();
+    assert_eq!(cg.warnings.len(), 1);
+    assert_eq!(cg.warnings[0].kind, CodegenWarningKind::UnreachableLabel("dead".to_string()));
+
+    // `dead` is already reported as unreachable
+    let mut cg = Codegen { lint_labels: true, ..Codegen::new() };
+    cg.lint(&contract).unwrap();
+    assert_eq!(cg.warnings.len(), 2);
+    assert_eq!(cg.warnings[1].kind, CodegenWarningKind::UnreferencedLabel("leftover".to_string()));
+    let span = &cg.warnings[1].span.0[0];
+    assert!(source[span.start..].starts_with("leftover"));
+
+    // The emitted bytecode is unchanged
+    let bytecode =
+        Codegen::generate_main_bytecode(&EVMVersion::default(), &contract, None).unwrap();
+    assert_eq!(bytecode, "5f3561000a575b6001505b005b5f5ffd");
+}
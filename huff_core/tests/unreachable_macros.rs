@@ -0,0 +1,41 @@
+mod common;
+
+use common::parse;
+use huff_codegen::Codegen;
+
+#[test]
+fn test_unreachable_macros() {
+    let source = r#"
+        #define macro HELPER() = takes(0) returns(1) {
+            0x01
+        }
+
+        #define macro MEASURED() = takes(0) returns(0) {
+            stop
+        }
+
+        #define macro NESTED() = takes(0) returns(1) {
+            HELPER()
+        }
+
+        #define macro FORGOTTEN() = takes(0) returns(0) {
+            0x00 0x00 revert
+        }
+
+        #define macro CONSTRUCTOR() = takes(0) returns(0) {
+            __codesize(MEASURED) pop
+        }
+
+        #define macro MAIN() = takes(0) returns(0) {
+            NESTED() pop
+        }
+
+        #define test MY_TEST() = {
+            FORGOTTEN()
+        }
+    "#;
+
+    let contract = parse(source);
+
+    assert_eq!(Codegen::unreachable_macros(&contract), vec!["FORGOTTEN".to_string()]);
+}
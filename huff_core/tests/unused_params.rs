@@ -0,0 +1,56 @@
+mod common;
+
+use common::parse;
+use huff_codegen::Codegen;
+use huff_utils::prelude::*;
+
+#[test]
+fn test_used_params_are_allowed() {
+    let contract = parse(
+        r#"
+        #define macro STORE(slot, value) = takes(0) returns(0) {
+            <value> <slot> sstore
+        }
+
+        #define macro FORWARD(slot) = takes(0) returns(0) {
+            STORE(<slot>, 0x01)
+        }
+
+        #define macro MAIN() = takes(0) returns(0) {
+            FORWARD(0x00)
+        }
+    "#,
+    );
+
+    let mut cg = Codegen { strict_params: true, ..Codegen::new() };
+    cg.lint(&contract).unwrap();
+}
+
+#[test]
+fn test_unused_param_is_rejected() {
+    let contract = parse(
+        r#"
+        #define macro STORE(slot, value) = takes(0) returns(0) {
+            0x01 <slot> sstore
+        }
+
+        #define macro MAIN() = takes(0) returns(0) {
+            STORE(0x00, 0x02)
+        }
+    "#,
+    );
+
+    // Only rejected in strict mode
+    Codegen::new().lint(&contract).unwrap();
+
+    let mut cg = Codegen { strict_params: true, ..Codegen::new() };
+    let err = cg.lint(&contract).unwrap_err();
+    assert_eq!(
+        err.kind,
+        CodegenErrorKind::UnusedMacroParameter {
+            macro_name: "STORE".to_string(),
+            param: "value".to_string(),
+        }
+    );
+    assert_eq!(err.span, contract.macros[0].parameters[1].span);
+}
@@ -43,3 +43,46 @@ fn test_verbatim_invalid_hex() {
     // Expect failure to generate bytecode with verbatim
     assert!(Codegen::generate_main_bytecode(&EVMVersion::default(), &contract, None).is_err());
 }
+
+#[test]
+fn test_verbatim_label_offsets() {
+    let source = r#"
+    #define macro MAIN() = takes(0) returns(0) {
+        __VERBATIM(0x6001600101)
+        done jump
+        __VERBATIM("00fe")
+        done:
+            stop
+    }
+    "#;
+
+    let full_source = FullFileSource { source, file: None, spans: vec![] };
+    let lexer = Lexer::new(full_source.source);
+    let tokens = lexer.into_iter().map(|x| x.unwrap()).collect::<Vec<Token>>();
+    let mut parser = Parser::new(tokens, Some("".to_string()));
+    let mut contract = parser.parse().unwrap();
+    contract.derive_storage_pointers();
+
+    // The injected bytes shift the `done` label to offset 0x0b
+    let mb = Codegen::generate_main_bytecode(&EVMVersion::default(), &contract, None).unwrap();
+    assert_eq!(mb, "600160010161000b5600fe5b00");
+}
+
+#[test]
+fn test_verbatim_odd_length_hex() {
+    let source = r#"
+    #define macro MAIN() = takes(0) returns(0) {
+        __VERBATIM("60016")
+    }
+    "#;
+
+    let full_source = FullFileSource { source, file: None, spans: vec![] };
+    let lexer = Lexer::new(full_source.source);
+    let tokens = lexer.into_iter().map(|x| x.unwrap()).collect::<Vec<Token>>();
+    let mut parser = Parser::new(tokens, Some("".to_string()));
+    let mut contract = parser.parse().unwrap();
+    contract.derive_storage_pointers();
+
+    let err = Codegen::generate_main_bytecode(&EVMVersion::default(), &contract, None).unwrap_err();
+    assert_eq!(err.kind, CodegenErrorKind::InvalidHex("60016".to_string()));
+}
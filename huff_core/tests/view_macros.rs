@@ -0,0 +1,82 @@
+mod common;
+
+use common::parse;
+use huff_codegen::Codegen;
+use huff_utils::prelude::*;
+
+#[test]
+fn test_view_macro_reading_state() {
+    let source = r#"
+        #define constant OWNER = FREE_STORAGE_POINTER()
+
+        #[view]
+        #define macro GET_OWNER() = takes(0) returns(1) {
+            [OWNER] sload
+        }
+
+        #[view]
+        #define macro STATIC_CALL() = takes(1) returns(1) {
+            0x20 0x00 0x00 0x00 dup5 gas staticcall
+        }
+
+        #define macro MAIN() = takes(0) returns(0) {
+            GET_OWNER() STATIC_CALL()
+            0x00 mstore 0x20 0x00 return
+        }
+    "#;
+    let contract = parse(source);
+    assert!(contract.find_macro_by_name("GET_OWNER").unwrap().is_view());
+    assert!(!contract.find_macro_by_name("MAIN").unwrap().is_view());
+
+    assert!(Codegen::check_view_macros(&EVMVersion::default(), &contract).is_ok());
+}
+
+#[test]
+fn test_view_macro_writing_state() {
+    let source = r#"
+        #define constant OWNER = FREE_STORAGE_POINTER()
+
+        #define macro WRITE() = takes(1) returns(0) {
+            [OWNER] sstore
+        }
+
+        #[view]
+        #define macro SET_OWNER() = takes(1) returns(0) {
+            WRITE()
+        }
+
+        #define macro MAIN() = takes(0) returns(0) {
+            caller SET_OWNER()
+        }
+    "#;
+    let contract = parse(source);
+
+    let err = Codegen::check_view_macros(&EVMVersion::default(), &contract).unwrap_err();
+    assert_eq!(
+        err.kind,
+        CodegenErrorKind::StateChangeInView("SET_OWNER".to_string(), Opcode::Sstore)
+    );
+}
+
+#[test]
+fn test_view_macro_calls() {
+    let zero_value_call = r#"
+        #[view]
+        #define macro CALL_WITHOUT_VALUE() = takes(1) returns(1) {
+            0x20 0x00 0x00 0x00 0x00 dup6 gas call
+        }
+    "#;
+    assert!(Codegen::check_view_macros(&EVMVersion::default(), &parse(zero_value_call)).is_ok());
+
+    let value_call = r#"
+        #[view]
+        #define macro CALL_WITH_VALUE() = takes(1) returns(1) {
+            0x20 0x00 0x00 0x00 callvalue dup6 gas call
+        }
+    "#;
+    let err = Codegen::check_view_macros(&EVMVersion::default(), &parse(value_call)).unwrap_err();
+    assert_eq!(
+        err.kind,
+        CodegenErrorKind::StateChangeInView("CALL_WITH_VALUE".to_string(), Opcode::Call)
+    );
+}
@@ -0,0 +1,44 @@
+mod common;
+
+use common::parse;
+use huff_codegen::Codegen;
+use huff_utils::prelude::*;
+
+#[test]
+fn test_written_slots() {
+    let source = r#"
+        #define constant OWNER_SLOT = FREE_STORAGE_POINTER()
+        #define constant BALANCE_SLOT = FREE_STORAGE_POINTER()
+
+        #define macro SET_BALANCE() = takes(0) returns(0) {
+            callvalue [BALANCE_SLOT] sstore
+        }
+
+        #define macro SETUP() = takes(0) returns(0) {
+            caller [OWNER_SLOT] sstore
+            SET_BALANCE()
+            // Dynamic slots are not captured
+            0x01 0x04 calldataload sstore
+            // Neither are slots that are not pushed right before the store
+            0x02 0x01 swap1 sstore
+        }
+
+        #define macro MAIN() = takes(0) returns(0) {
+            SETUP()
+        }
+    "#;
+
+    let contract = parse(source);
+
+    let evm_version = EVMVersion::default();
+
+    let slots = Codegen::written_slots(&evm_version, "SETUP", &contract).unwrap();
+    assert_eq!(slots, vec![str_to_bytes32("00"), str_to_bytes32("01")]);
+
+    let slots = Codegen::written_slots(&evm_version, "SET_BALANCE", &contract).unwrap();
+    assert_eq!(slots, vec![str_to_bytes32("01")]);
+
+    // Missing macros error
+    let err = Codegen::written_slots(&evm_version, "MISSING", &contract).unwrap_err();
+    assert_eq!(err.kind, CodegenErrorKind::MissingMacroDefinition("MISSING".to_string()));
+}
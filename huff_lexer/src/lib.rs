@@ -1,9 +1,9 @@
 use huff_utils::prelude::*;
 use regex::Regex;
 use std::{
-    iter::{Peekable, Zip},
-    ops::RangeFrom,
-    str::Chars,
+    collections::HashSet,
+    iter::Peekable,
+    str::{CharIndices, FromStr},
 };
 
 /// Defines a context in which the lexing happens.
@@ -29,14 +29,51 @@ pub enum Context {
     CodeTableBody,
 }
 
+/// An open `#ifdef` conditional
+#[derive(Debug)]
+struct Conditional {
+    /// The name checked by the `#ifdef`
+    name: String,
+    /// The span of the `#ifdef` directive
+    span: Span,
+    /// Whether the enclosing code is enabled
+    parent_enabled: bool,
+    /// Whether the name is defined
+    defined: bool,
+    /// Whether the `#else` branch was entered
+    in_else: bool,
+}
+
+impl Conditional {
+    /// Whether the code of the current branch is enabled
+    fn enabled(&self) -> bool {
+        self.parent_enabled && self.defined != self.in_else
+    }
+}
+
 /// ## Lexer
 ///
 /// The lexer encapsulated in a struct.
+///
+/// Every token, including whitespace and comments, spans the source bytes it was lexed from,
+/// with inclusive start and end byte offsets. The spans of consecutive tokens are contiguous, so
+/// the tokens preceding `Eof` cover the whole source.
+///
+/// Code can be conditionally compiled with `#ifdef NAME`, `#else` and `#endif` directives, which
+/// may be nested. The directives and the tokens of disabled branches are not emitted, so the
+/// tokens only cover the whole source when it has no conditionals.
+///
+/// Tokens are lexed lazily, one per call to `next()`, pulling characters from the source as they
+/// are needed. Besides the token being lexed, the lexer only holds the previous token and the open
+/// conditionals, so iterating never buffers the tokens of the rest of the source.
 pub struct Lexer<'a> {
-    /// The source code as peekable chars.
+    /// The source code as peekable chars with their byte offsets.
     /// WARN: SHOULD NEVER BE MODIFIED!
-    pub chars: Peekable<Zip<Chars<'a>, RangeFrom<u32>>>,
+    pub chars: Peekable<CharIndices<'a>>,
+    /// The byte offset of the last byte of the last consumed char
     position: u32,
+    /// The byte offset of the first byte of the last consumed char
+    char_start: u32,
     /// The previous lexed Token.
     /// NOTE: Cannot be a whitespace.
     pub lookback: Option<Token>,
@@ -44,6 +81,12 @@ pub struct Lexer<'a> {
     pub eof: bool,
     /// Current context.
     pub context: Context,
+    /// The names defined for `#ifdef` conditionals
+    pub defines: HashSet<String>,
+    /// The open `#ifdef` conditionals, innermost last
+    conditionals: Vec<Conditional>,
+    /// The names of the labels defined anywhere in the source
+    labels: HashSet<String>,
 }
 
 pub type TokenResult = Result<Token, LexicalError>;
@@ -51,26 +94,39 @@ pub type TokenResult = Result<Token, LexicalError>;
 impl<'a> Lexer<'a> {
     pub fn new(source: &'a str) -> Self {
         Lexer {
-            // We zip with the character index here to ensure the first char has index 0
-            chars: source.chars().zip(0..).peekable(),
+            chars: source.char_indices().peekable(),
             position: 0,
+            char_start: 0,
             lookback: None,
             eof: false,
             context: Context::Global,
+            defines: HashSet::new(),
+            conditionals: Vec::new(),
+            labels: Regex::new(r"([a-zA-Z_][a-zA-Z0-9_]*):")
+                .unwrap()
+                .captures_iter(source)
+                .map(|c| c[1].to_string())
+                .collect(),
         }
     }
 
+    /// Sets the names defined for `#ifdef` conditionals
+    pub fn with_defines(mut self, defines: HashSet<String>) -> Self {
+        self.defines = defines;
+        self
+    }
+
     /// Consumes the next character
     pub fn consume(&mut self) -> Option<char> {
-        let (c, index) = self.chars.next()?;
-        self.position = index;
+        let (index, c) = self.chars.next()?;
+        self.char_start = index as u32;
+        self.position = (index + c.len_utf8() - 1) as u32;
         Some(c)
     }
 
     /// Try to peek at the next character from the source
     pub fn peek(&mut self) -> Option<char> {
-        //self.chars.peek().copied()
-        self.chars.peek().map(|(c, _)| *c)
+        self.chars.peek().map(|(_, c)| *c)
     }
 
     fn next_token(&mut self) -> TokenResult {
@@ -87,7 +143,9 @@ impl<'a> Lexer<'a> {
                                 comment_string.push(ch2);
                                 let (comment_string, start, end) =
                                     self.eat_while(Some(ch), |c| c != '\n');
-                                Ok(TokenKind::Comment(comment_string).into_span(start, end))
+                                let kind = Lexer::region_marker(&comment_string)
+                                    .unwrap_or(TokenKind::Comment(comment_string));
+                                Ok(kind.into_span(start, end))
                             }
                             '*' => {
                                 // ref: https://github.com/rust-lang/rust/blob/900c3540378c8422b8087ffa3db60fa6c8abfcad/compiler/rustc_lexer/src/lib.rs#L474
@@ -139,7 +197,8 @@ impl<'a> Lexer<'a> {
 
                     let mut found_kind: Option<TokenKind> = None;
 
-                    let keys = [TokenKind::Define, TokenKind::Include];
+                    let keys =
+                        [TokenKind::Define, TokenKind::Include, TokenKind::Else, TokenKind::EndIf];
                     for kind in keys.into_iter() {
                         let key = kind.to_string();
                         let peeked = word.clone();
@@ -149,9 +208,24 @@ impl<'a> Lexer<'a> {
                         }
                     }
 
-                    if let Some(kind) = &found_kind {
+                    if word == "#ifdef" {
+                        // The directive spans the name it checks
+                        self.eat_while(None, |c| c == ' ' || c == '\t');
+                        let (name, _, end) =
+                            self.eat_while(None, |c| c.is_alphanumeric() || c == '_');
+                        if name.is_empty() {
+                            tracing::error!(target: "lexer", "MISSING NAME AFTER '#ifdef'");
+                            return Err(LexicalError::new(
+                                LexicalErrorKind::MissingConditionalName,
+                                Span { start: start as usize, end: end as usize, file: None },
+                            ))
+                        }
+                        Ok(TokenKind::IfDef(name).into_span(start, end))
+                    } else if let Some(kind) = &found_kind {
                         Ok(kind.clone().into_span(start, end))
-                    } else if self.context == Context::Global && self.peek().unwrap() == '[' {
+                    } else if matches!(self.context, Context::Global | Context::Constant) &&
+                        self.peek().unwrap() == '['
+                    {
                         Ok(TokenKind::Pound.into_single_span(self.position))
                     } else {
                         // Otherwise we don't support # prefixed indentifiers
@@ -179,6 +253,8 @@ impl<'a> Lexer<'a> {
                         TokenKind::Function,
                         TokenKind::Constant,
                         TokenKind::Error,
+                        TokenKind::Receive,
+                        TokenKind::Fallback,
                         TokenKind::Takes,
                         TokenKind::Returns,
                         TokenKind::Event,
@@ -218,9 +294,11 @@ impl<'a> Lexer<'a> {
                             TokenKind::Macro | TokenKind::Fn | TokenKind::Test => {
                                 self.context = Context::MacroDefinition
                             }
-                            TokenKind::Function | TokenKind::Event | TokenKind::Error => {
-                                self.context = Context::Abi
-                            }
+                            TokenKind::Function |
+                            TokenKind::Event |
+                            TokenKind::Error |
+                            TokenKind::Receive |
+                            TokenKind::Fallback => self.context = Context::Abi,
                             TokenKind::Constant => self.context = Context::Constant,
                             TokenKind::CodeTable => self.context = Context::CodeTableBody,
                             _ => (),
@@ -239,7 +317,7 @@ impl<'a> Lexer<'a> {
                         if let Some(')') = self.peek() {
                             self.consume();
                         }
-                        end += 2;
+                        end = self.position;
                         found_kind = Some(TokenKind::FreeStoragePointer);
                     }
 
@@ -259,8 +337,25 @@ impl<'a> Lexer<'a> {
                     }
 
                     if !(self.context != Context::MacroBody || found_kind.is_some()) {
-                        if let Some(o) = OPCODES_MAP.get(&word) {
-                            found_kind = Some(TokenKind::Opcode(o.to_owned()));
+                        // Mnemonics match in any casing, but constant, argument, macro and label
+                        // names are commonly uppercase, e.g. `[BALANCE]`, so only lowercase words
+                        // are opcodes there, or when a label of the same name is defined
+                        let is_name = matches!(
+                            self.lookback.as_ref().map(|t| &t.kind),
+                            Some(
+                                TokenKind::OpenBracket |
+                                    TokenKind::LeftAngle |
+                                    TokenKind::OpenParen |
+                                    TokenKind::Comma
+                            )
+                        ) || self.peek() == Some('(') ||
+                            self.labels.contains(&word);
+                        let lowercase = !word.chars().any(|c| c.is_ascii_uppercase());
+                        match Opcode::from_str(&word) {
+                            Ok(o) if lowercase || !is_name => {
+                                found_kind = Some(TokenKind::Opcode(o))
+                            }
+                            _ => {}
                         }
                     }
 
@@ -348,7 +443,7 @@ impl<'a> Lexer<'a> {
 
                     let kind = if let Some(kind) = &found_kind {
                         kind.clone()
-                    } else if self.context == Context::MacroBody &&
+                    } else if matches!(self.context, Context::MacroBody | Context::MacroArgs) &&
                         BuiltinFunctionKind::try_from(&word).is_ok()
                     {
                         TokenKind::BuiltinFunction(word)
@@ -360,6 +455,8 @@ impl<'a> Lexer<'a> {
                 }
                 // If it's the start of a hex literal
                 ch if ch == '0' && self.peek().unwrap() == 'x' => self.eat_hex_digit(ch),
+                // If it's the start of a binary literal
+                ch if ch == '0' && self.peek() == Some('b') => self.eat_binary_digit(ch),
                 '=' => self.single_char_token(TokenKind::Assign),
                 '(' => {
                     match self.context {
@@ -392,6 +489,12 @@ impl<'a> Lexer<'a> {
                     self.single_char_token(TokenKind::CloseBrace)
                 }
                 '+' => self.single_char_token(TokenKind::Add),
+                // A minus sign directly followed by a digit negates a decimal literal
+                '-' if matches!(self.context, Context::MacroBody | Context::Constant) &&
+                    self.peek().is_some_and(|c| c.is_ascii_digit()) =>
+                {
+                    self.eat_negative_digit(ch)
+                }
                 '-' => self.single_char_token(TokenKind::Sub),
                 '*' => self.single_char_token(TokenKind::Mul),
                 '<' => self.single_char_token(TokenKind::LeftAngle),
@@ -407,13 +510,13 @@ impl<'a> Lexer<'a> {
                     Ok(TokenKind::Whitespace.into_span(start, end))
                 }
                 // String literals. String literals can also be wrapped by single quotes
-                '"' | '\'' => Ok(self.eat_string_literal()),
+                '"' | '\'' => self.eat_string_literal(),
                 ch => {
                     tracing::error!(target: "lexer", "UNSUPPORTED TOKEN '{}'", ch);
                     return Err(LexicalError::new(
                         LexicalErrorKind::InvalidCharacter(ch),
                         Span {
-                            start: self.position as usize,
+                            start: self.char_start as usize,
                             end: self.position as usize,
                             file: None,
                         },
@@ -443,13 +546,33 @@ impl<'a> Lexer<'a> {
         Ok(token_kind.into_single_span(self.position))
     }
 
+    /// Checks if a single line comment is a region folding marker, returning the
+    /// `TokenKind::Region` for `// region: name` and `// endregion` comments.
+    fn region_marker(comment: &str) -> Option<TokenKind> {
+        let marker = comment.trim_start_matches('/').trim();
+        let (rest, start) = if let Some(rest) = marker.strip_prefix("endregion") {
+            (rest, false)
+        } else if let Some(rest) = marker.strip_prefix("region") {
+            (rest, true)
+        } else {
+            return None
+        };
+
+        // The marker must be followed by a colon, whitespace or nothing at all
+        if !(rest.is_empty() || rest.starts_with(':') || rest.starts_with(char::is_whitespace)) {
+            return None
+        }
+        let name = rest.strip_prefix(':').unwrap_or(rest).trim();
+        Some(TokenKind::Region(name.to_string(), start))
+    }
+
     /// Keeps consuming tokens as long as the predicate is satisfied
     fn eat_while<F: Fn(char) -> bool>(
         &mut self,
         initial_char: Option<char>,
         predicate: F,
     ) -> (String, u32, u32) {
-        let start = self.position;
+        let start = self.char_start;
 
         // This function is only called when we want to continue consuming a character of the same
         // type. For example, we see a digit and we want to consume the whole integer
@@ -479,18 +602,68 @@ impl<'a> Lexer<'a> {
 
     fn eat_digit(&mut self, initial_char: char) -> TokenResult {
         let (integer_str, start, end) =
-            self.eat_while(Some(initial_char), |ch| ch.is_ascii_digit());
+            self.eat_while(Some(initial_char), |ch| ch.is_ascii_digit() || ch == '_');
+        let integer_str = strip_underscores(&integer_str, &integer_str, start, end)?;
+        let span = Span { start: start as usize, end: end as usize, file: None };
+
+        // Values pushed from macro bodies and constants are literals, anywhere else a decimal is
+        // a count such as the stack items a macro takes
+        if matches!(self.context, Context::MacroBody | Context::Constant) {
+            let hex = decimal_to_hex(&integer_str);
+            let len = hex.len() / 2;
+            if len > 32 {
+                tracing::error!(target: "lexer", "LITERAL TOO LARGE '{}'", integer_str);
+                return Err(LexicalError::new(LexicalErrorKind::LiteralTooLarge(len), span))
+            }
+            return Ok(Token { kind: TokenKind::Literal(str_to_bytes32(&hex)), span })
+        }
 
         let integer = integer_str.parse().unwrap();
 
         let integer_token = TokenKind::Num(integer);
-        let span = Span { start: start as usize, end: end as usize, file: None };
         Ok(Token { kind: integer_token, span })
     }
 
+    /// Lexes a negative decimal literal as its 256-bit two's complement
+    fn eat_negative_digit(&mut self, initial_char: char) -> TokenResult {
+        let (literal, start, end) =
+            self.eat_while(Some(initial_char), |ch| ch.is_ascii_digit() || ch == '_');
+        let integer_str = strip_underscores(&literal, &literal[1..], start, end)?;
+        let span = Span { start: start as usize, end: end as usize, file: None };
+
+        let hex = decimal_to_hex(&integer_str);
+        let len = hex.len() / 2;
+        if len > 32 {
+            tracing::error!(target: "lexer", "LITERAL TOO LARGE '{}'", literal);
+            return Err(LexicalError::new(LexicalErrorKind::LiteralTooLarge(len), span))
+        }
+
+        // The magnitude of the smallest signed value, -2^255, is 0x80 followed by zeroes
+        let magnitude = str_to_bytes32(&hex);
+        if magnitude[0] > 0x80 || (magnitude[0] == 0x80 && magnitude[1..].iter().any(|b| *b != 0)) {
+            tracing::error!(target: "lexer", "NEGATIVE LITERAL TOO SMALL '{}'", literal);
+            return Err(LexicalError::new(LexicalErrorKind::NegativeLiteralTooSmall(literal), span))
+        }
+
+        // Invert the magnitude and add one
+        let mut literal = magnitude.map(|b| !b);
+        for byte in literal.iter_mut().rev() {
+            let (sum, overflow) = byte.overflowing_add(1);
+            *byte = sum;
+            if !overflow {
+                break
+            }
+        }
+        Ok(Token { kind: TokenKind::Literal(literal), span })
+    }
+
     fn eat_hex_digit(&mut self, initial_char: char) -> TokenResult {
-        let (integer_str, mut start, end) =
-            self.eat_while(Some(initial_char), |ch| ch.is_ascii_hexdigit() | (ch == 'x'));
+        let (integer_str, start, end) = self
+            .eat_while(Some(initial_char), |ch| ch.is_ascii_hexdigit() | (ch == 'x') | (ch == '_'));
+        let integer_str = format!(
+            "0x{}",
+            strip_underscores(&integer_str, integer_str.get(2..).unwrap_or_default(), start, end)?
+        );
 
         // TODO: check for sure that we have a correct hex string, eg. 0x56 and not 0x56x34
         let kind = if self.context == Context::CodeTableBody {
@@ -503,10 +676,51 @@ impl<'a> Lexer<'a> {
                 TokenKind::Ident(integer_str)
             }
         } else {
+            // Wider literals can't be pushed
+            let len = (integer_str.len() - 1) / 2;
+            if len > 32 {
+                tracing::error!(target: "lexer", "LITERAL TOO LARGE '{}'", integer_str);
+                return Err(LexicalError::new(
+                    LexicalErrorKind::LiteralTooLarge(len),
+                    Span { start: start as usize, end: end as usize, file: None },
+                ))
+            }
             TokenKind::Literal(str_to_bytes32(integer_str[2..].as_ref()))
         };
 
-        start += 2;
+        let span = Span { start: start as usize, end: end as usize, file: None };
+        Ok(Token { kind, span })
+    }
+
+    fn eat_binary_digit(&mut self, initial_char: char) -> TokenResult {
+        // Eat any alphanumeric characters so that invalid digits are part of the error
+        let (binary_str, start, end) =
+            self.eat_while(Some(initial_char), |ch| ch.is_ascii_alphanumeric() || ch == '_');
+        let digits = &strip_underscores(&binary_str, &binary_str[2..], start, end)?;
+
+        if digits.is_empty() ||
+            digits.len() > 256 ||
+            !digits.chars().all(|ch| ch == '0' || ch == '1')
+        {
+            tracing::error!(target: "lexer", "INVALID BINARY LITERAL '{}'", binary_str);
+            return Err(LexicalError::new(
+                LexicalErrorKind::InvalidBinaryLiteral(binary_str.clone()),
+                Span { start: start as usize, end: end as usize, file: None },
+            ))
+        }
+
+        // Left pad to whole nibbles and convert each nibble to a hex digit
+        let padded = format!("{}{digits}", "0".repeat((4 - digits.len() % 4) % 4));
+        let hex = padded
+            .as_bytes()
+            .chunks(4)
+            .map(|nibble| {
+                let value = nibble.iter().fold(0u8, |acc, bit| (acc << 1) | (bit - b'0'));
+                format!("{value:x}")
+            })
+            .collect::<String>();
+
+        let kind = TokenKind::Literal(str_to_bytes32(&hex));
         let span = Span { start: start as usize, end: end as usize, file: None };
         Ok(Token { kind, span })
     }
@@ -516,12 +730,59 @@ impl<'a> Lexer<'a> {
         self.eat_while(None, |ch| ch.is_whitespace())
     }
 
-    fn eat_string_literal(&mut self) -> Token {
-        let (str_literal, start_span, end_span) =
-            self.eat_while(None, |ch| ch != '"' && ch != '\'');
-        let str_literal_token = TokenKind::Str(str_literal);
+    /// Lexes a string literal up to the closing quote, decoding escape sequences.
+    ///
+    /// Supports the `\n`, `\t`, `\\`, `\"`, `\'` and `\xNN` escapes. Any other backslash is
+    /// kept as-is so that existing paths are unaffected.
+    fn eat_string_literal(&mut self) -> TokenResult {
+        let start = self.position;
+        let mut str_literal = String::new();
+
+        while let Some(ch) = self.peek() {
+            if ch == '"' || ch == '\'' {
+                break
+            }
+            self.consume();
+            if ch != '\\' {
+                str_literal.push(ch);
+                continue
+            }
+
+            let escape_start = self.position;
+            match self.peek() {
+                Some('n') => str_literal.push('\n'),
+                Some('t') => str_literal.push('\t'),
+                Some(c @ ('\\' | '"' | '\'')) => str_literal.push(c),
+                Some('x') => {
+                    self.consume();
+                    let mut digits = String::new();
+                    while digits.len() < 2 && self.peek().is_some_and(|c| c.is_ascii_hexdigit()) {
+                        digits.extend(self.consume());
+                    }
+                    if digits.len() != 2 {
+                        tracing::error!(target: "lexer", "INVALID ESCAPE SEQUENCE '\\x{}'", digits);
+                        return Err(LexicalError::new(
+                            LexicalErrorKind::InvalidEscapeSequence(format!("\\x{digits}")),
+                            Span {
+                                start: escape_start as usize,
+                                end: self.position as usize,
+                                file: None,
+                            },
+                        ))
+                    }
+                    str_literal.push(char::from(u8::from_str_radix(&digits, 16).unwrap()));
+                    continue
+                }
+                _ => {
+                    str_literal.push(ch);
+                    continue
+                }
+            }
+            self.consume();
+        }
+
         self.consume(); // Advance past the closing quote
-        str_literal_token.into_span(start_span, end_span + 1)
+        Ok(TokenKind::Str(str_literal).into_span(start, self.position))
     }
 
     /// Checks the previous token kind against the input.
@@ -533,8 +794,9 @@ impl<'a> Lexer<'a> {
     /// `TokenKind::Ident`.
     ///
     /// Rules:
-    /// - The `macro`, `fn`, `test`, `function`, `constant`, `event`, `jumptable`,
-    ///   `jumptable__packed`, and `table` keywords must be preceded by a `#define` keyword.
+    /// - The `macro`, `fn`, `test`, `function`, `constant`, `event`, `receive`, `fallback`,
+    ///   `jumptable`, `jumptable__packed`, and `table` keywords must be preceded by a `#define`
+    ///   keyword.
     /// - The `takes` keyword must be preceded by an assignment operator: `=`.
     /// - The `nonpayable`, `payable`, `view`, and `pure` keywords must be preceeded by one of these
     ///   keywords or a close paren.
@@ -549,6 +811,8 @@ impl<'a> Lexer<'a> {
             Some(TokenKind::Constant) |
             Some(TokenKind::Error) |
             Some(TokenKind::Event) |
+            Some(TokenKind::Receive) |
+            Some(TokenKind::Fallback) |
             Some(TokenKind::JumpTable) |
             Some(TokenKind::JumpTablePacked) |
             Some(TokenKind::CodeTable) => self.checked_lookback(TokenKind::Define),
@@ -572,14 +836,68 @@ impl<'a> Lexer<'a> {
             }
             Some(TokenKind::Takes) => self.checked_lookback(TokenKind::Assign),
             Some(TokenKind::Returns) => {
-                self.eat_whitespace();
                 // Allow for loose and tight syntax (e.g. `returns   (0)`, `returns(0)`, ...)
-                self.peek().unwrap_or(')') == '(' && !self.checked_lookback(TokenKind::Function)
+                let next = self.chars.clone().map(|(_, c)| c).find(|c| !c.is_whitespace());
+                next.unwrap_or(')') == '(' && !self.checked_lookback(TokenKind::Function)
             }
             _ => true,
         }
     }
 
+    /// Applies a token to the open conditionals
+    ///
+    /// Returns the token if it should be emitted, consuming directives and the tokens of
+    /// disabled branches.
+    fn preprocess(&mut self, token: Token) -> Result<Option<Token>, LexicalError> {
+        let enabled = self.conditionals.last().is_none_or(Conditional::enabled);
+        match &token.kind {
+            TokenKind::IfDef(name) => {
+                self.conditionals.push(Conditional {
+                    name: name.clone(),
+                    span: token.span.clone(),
+                    parent_enabled: enabled,
+                    defined: self.defines.contains(name),
+                    in_else: false,
+                });
+                Ok(None)
+            }
+            TokenKind::Else => match self.conditionals.last_mut() {
+                Some(conditional) if !conditional.in_else => {
+                    conditional.in_else = true;
+                    Ok(None)
+                }
+                _ => {
+                    tracing::error!(target: "lexer", "UNMATCHED '#else'");
+                    Err(LexicalError::new(
+                        LexicalErrorKind::UnmatchedDirective(token.kind.to_string()),
+                        token.span,
+                    ))
+                }
+            },
+            TokenKind::EndIf => {
+                if self.conditionals.pop().is_none() {
+                    tracing::error!(target: "lexer", "UNMATCHED '#endif'");
+                    return Err(LexicalError::new(
+                        LexicalErrorKind::UnmatchedDirective(token.kind.to_string()),
+                        token.span,
+                    ))
+                }
+                Ok(None)
+            }
+            TokenKind::Eof => match self.conditionals.pop() {
+                Some(conditional) => {
+                    tracing::error!(target: "lexer", "UNTERMINATED '#ifdef {}'", conditional.name);
+                    Err(LexicalError::new(
+                        LexicalErrorKind::UnterminatedConditional(conditional.name),
+                        conditional.span,
+                    ))
+                }
+                None => Ok(Some(token)),
+            },
+            _ => Ok(enabled.then_some(token)),
+        }
+    }
+
     /// Lex all imports
     /// Example import: `// #include "./Utils.huff"`
     pub fn lex_imports(source: &str) -> Vec<String> {
@@ -658,10 +976,35 @@ impl<'a> Iterator for Lexer<'a> {
     type Item = TokenResult;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.eof {
-            None
-        } else {
-            Some(self.next_token())
+        while !self.eof {
+            match self.next_token().and_then(|token| self.preprocess(token)) {
+                Ok(Some(token)) => return Some(Ok(token)),
+                Ok(None) => continue,
+                Err(e) => return Some(Err(e)),
+            }
         }
+        None
+    }
+}
+
+/// Removes the underscores separating the digits of a number literal
+///
+/// Underscores are only allowed between two digits, so a leading, trailing or doubled underscore
+/// results in a LexicalError spanning the whole literal.
+fn strip_underscores(
+    literal: &str,
+    digits: &str,
+    start: u32,
+    end: u32,
+) -> Result<String, LexicalError> {
+    if digits.contains('_') &&
+        (digits.starts_with('_') || digits.ends_with('_') || digits.contains("__"))
+    {
+        tracing::error!(target: "lexer", "MISPLACED UNDERSCORE IN LITERAL '{}'", literal);
+        return Err(LexicalError::new(
+            LexicalErrorKind::MisplacedUnderscore(literal.to_string()),
+            Span { start: start as usize, end: end as usize, file: None },
+        ))
     }
+    Ok(digits.replace('_', ""))
 }
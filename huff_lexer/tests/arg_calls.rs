@@ -37,6 +37,7 @@ fn lexes_arg_calls() {
     let _ = lexer.next(); // paren
     let _ = lexer.next(); // Whitespace
     let _ = lexer.next(); // returns
+    let _ = lexer.next(); // Whitespace
     let _ = lexer.next(); // paren
     let _ = lexer.next(); // 3
     let _ = lexer.next(); // paren
@@ -0,0 +1,64 @@
+use huff_lexer::*;
+use huff_utils::prelude::*;
+
+#[test]
+fn parses_binary_literal() {
+    let source = "0b1010";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source.source);
+
+    // The first and only token should be lexed as Literal(0x0a)
+    let tok = lexer.next().unwrap().unwrap();
+    assert_eq!(tok, Token::new(TokenKind::Literal(str_to_bytes32("0a")), Span::new(0..5, None)));
+
+    // We covered the whole source
+    lexer.next();
+    assert!(lexer.eof);
+}
+
+#[test]
+fn parses_binary_literal_in_macro() {
+    let source = "#define macro MASK() = takes(1) returns(1) {\n0b1111000000001111 and\n}";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let lexer = Lexer::new(flattened_source.source);
+    let tokens = lexer
+        .into_iter()
+        .map(|x| x.unwrap())
+        .filter(|x| !matches!(x.kind, TokenKind::Whitespace))
+        .collect::<Vec<Token>>();
+
+    assert_eq!(
+        tokens.get(tokens.len() - 4).unwrap().kind,
+        TokenKind::Literal(str_to_bytes32("f00f"))
+    );
+    assert_eq!(tokens.get(tokens.len() - 3).unwrap().kind, TokenKind::Opcode(Opcode::And));
+}
+
+#[test]
+fn parses_full_width_binary_literal() {
+    let source = format!("0b{}", "1".repeat(256));
+    let flattened_source = FullFileSource { source: &source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source.source);
+
+    let tok = lexer.next().unwrap().unwrap();
+    assert_eq!(tok.kind, TokenKind::Literal([0xff; 32]));
+}
+
+#[test]
+fn rejects_invalid_binary_literals() {
+    for (source, span) in
+        [("0b", 0..1), ("0b102", 0..4), (&*format!("0b{}", "1".repeat(257)), 0..258)]
+    {
+        let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+        let mut lexer = Lexer::new(flattened_source.source);
+
+        let err = lexer.next().unwrap().unwrap_err();
+        assert_eq!(
+            err,
+            LexicalError::new(
+                LexicalErrorKind::InvalidBinaryLiteral(source.to_string()),
+                Span::new(span, None)
+            )
+        );
+    }
+}
@@ -155,3 +155,26 @@ fn multi_line_comments() {
     assert!(lexer.eof);
     assert_eq!(source.len() - 1, 47);
 }
+
+#[test]
+fn region_markers() {
+    let source = "// region: dispatch\n// regional comment\n//endregion\n// endregion: dispatch";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let lexer = Lexer::new(flattened_source.source);
+    let tokens = lexer
+        .into_iter()
+        .map(|x| x.unwrap())
+        .filter(|x| !matches!(x.kind, TokenKind::Whitespace | TokenKind::Eof))
+        .map(|x| x.kind)
+        .collect::<Vec<TokenKind>>();
+
+    assert_eq!(
+        tokens,
+        vec![
+            TokenKind::Region("dispatch".to_string(), true),
+            TokenKind::Comment("// regional comment".to_string()),
+            TokenKind::Region("".to_string(), false),
+            TokenKind::Region("dispatch".to_string(), false),
+        ]
+    );
+}
@@ -0,0 +1,65 @@
+use huff_lexer::*;
+use huff_utils::prelude::*;
+use std::collections::HashSet;
+
+const SOURCE: &str = r#"
+#define macro MAIN() = takes(0) returns(0) {
+    #ifdef DEBUG
+        0x01
+        #ifdef VERBOSE
+            0x02
+        #else
+            0x03
+        #endif
+    #else
+        0x04
+    #endif
+}"#;
+
+fn literals(defines: &[&str]) -> Vec<Literal> {
+    let defines = defines.iter().map(|d| d.to_string()).collect::<HashSet<String>>();
+    Lexer::new(SOURCE)
+        .with_defines(defines)
+        .map(|t| t.unwrap())
+        .filter_map(|t| match t.kind {
+            TokenKind::Literal(l) => Some(l),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn skips_disabled_branches() {
+    let lit = |s| str_to_bytes32(s);
+    assert_eq!(literals(&[]), vec![lit("04")]);
+    assert_eq!(literals(&["VERBOSE"]), vec![lit("04")]);
+    assert_eq!(literals(&["DEBUG"]), vec![lit("01"), lit("03")]);
+    assert_eq!(literals(&["DEBUG", "VERBOSE"]), vec![lit("01"), lit("02")]);
+}
+
+#[test]
+fn directives_are_not_emitted() {
+    let tokens = Lexer::new(SOURCE).map(|t| t.unwrap()).collect::<Vec<Token>>();
+    assert!(!tokens
+        .iter()
+        .any(|t| matches!(t.kind, TokenKind::IfDef(_) | TokenKind::Else | TokenKind::EndIf)));
+}
+
+#[test]
+fn rejects_malformed_conditionals() {
+    let first_error = |source: &str| Lexer::new(source).find_map(|t| t.err()).unwrap();
+
+    let err = first_error("#ifdef DEBUG\n0x01");
+    assert_eq!(err.kind, LexicalErrorKind::UnterminatedConditional("DEBUG".to_string()));
+    assert_eq!((err.span.start, err.span.end), (0, 11));
+
+    let err = first_error("0x01\n#endif");
+    assert_eq!(err.kind, LexicalErrorKind::UnmatchedDirective("#endif".to_string()));
+    assert_eq!((err.span.start, err.span.end), (5, 10));
+
+    let err = first_error("#ifdef DEBUG\n#else\n#else\n#endif");
+    assert_eq!(err.kind, LexicalErrorKind::UnmatchedDirective("#else".to_string()));
+
+    let err = first_error("#ifdef\n#endif");
+    assert_eq!(err.kind, LexicalErrorKind::MissingConditionalName);
+}
@@ -51,7 +51,7 @@ fn parses_decorator() {
         // 0x01
         let tok = lexer.next();
         let unwrapped = tok.unwrap().unwrap();
-        let returns_span = Span::new(26..27, None);
+        let returns_span = Span::new(24..27, None);
         assert_eq!(
             unwrapped,
             Token::new(TokenKind::Literal(str_to_bytes32("01")), returns_span.clone())
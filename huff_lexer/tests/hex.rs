@@ -9,7 +9,7 @@ fn parses_single_hex() {
 
     // The first and only token should be lexed as Literal(0xa57B)
     let tok = lexer.next().unwrap().unwrap();
-    assert_eq!(tok, Token::new(TokenKind::Literal(str_to_bytes32("a57B")), Span::new(2..5, None)));
+    assert_eq!(tok, Token::new(TokenKind::Literal(str_to_bytes32("a57B")), Span::new(0..5, None)));
 
     // We covered the whole source
     lexer.next();
@@ -45,7 +45,7 @@ fn parses_odd_len_hex() {
 
     // The first and only token should be lexed as Literal(0x1)
     let tok = lexer.next().unwrap().unwrap();
-    assert_eq!(tok, Token::new(TokenKind::Literal(str_to_bytes32("1")), Span::new(2..2, None)));
+    assert_eq!(tok, Token::new(TokenKind::Literal(str_to_bytes32("1")), Span::new(0..2, None)));
 
     // We covered the whole source
     lexer.next();
@@ -68,3 +68,55 @@ fn converts_literal_to_hex_string() {
         assert_eq!(format!("0x{source}"), bytes32_to_string(&str_to_bytes32(source), true));
     }
 }
+
+#[test]
+fn parses_hex_with_underscores() {
+    let lex = |source: &str| {
+        let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+        let mut lexer = Lexer::new(flattened_source.source);
+        lexer.next().unwrap().unwrap()
+    };
+
+    // Underscores are stripped before producing the literal
+    assert_eq!(lex("0xdead_beef").kind, lex("0xdeadbeef").kind);
+    assert_eq!(lex("0xdead_beef").kind, TokenKind::Literal(str_to_bytes32("deadbeef")));
+}
+
+#[test]
+fn rejects_misplaced_underscores_in_hex() {
+    for source in ["0x_ff", "0xff_", "0xd__d"] {
+        let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+        let mut lexer = Lexer::new(flattened_source.source);
+
+        let err = lexer.next().unwrap().unwrap_err();
+        assert_eq!(
+            err,
+            LexicalError::new(
+                LexicalErrorKind::MisplacedUnderscore(source.to_string()),
+                Span::new(0..source.len() - 1, None)
+            )
+        );
+    }
+}
+
+#[test]
+fn rejects_hex_wider_than_32_bytes() {
+    let lex = |source: &str| {
+        let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+        let mut lexer = Lexer::new(flattened_source.source);
+        lexer.next().unwrap()
+    };
+
+    let widest = format!("0x{}", "ff".repeat(32));
+    assert_eq!(lex(&widest).unwrap().kind, TokenKind::Literal([0xff; 32]));
+
+    for source in [format!("0x{}", "ff".repeat(33)), format!("0x1{}", "ff".repeat(32))] {
+        assert_eq!(
+            lex(&source).unwrap_err(),
+            LexicalError::new(
+                LexicalErrorKind::LiteralTooLarge(33),
+                Span::new(0..source.len() - 1, None)
+            )
+        );
+    }
+}
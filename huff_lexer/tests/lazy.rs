@@ -0,0 +1,21 @@
+use huff_lexer::*;
+use huff_utils::prelude::*;
+
+#[test]
+fn lexes_tokens_on_demand() {
+    // A few megabytes of macros
+    let source = (0..100_000)
+        .map(|i| format!("#define macro M{i}() = takes(0) returns(0) {{ 0x{i:x} pop }}\n"))
+        .collect::<String>();
+    assert!(source.len() > 4_000_000);
+
+    let mut lexer = Lexer::new(&source);
+    let first = lexer.next().unwrap().unwrap();
+    assert_eq!(first.kind, TokenKind::Define);
+
+    // Only the characters of the first token were read from the source
+    assert_eq!(lexer.chars.peek().map(|(i, _)| *i), Some(first.span.end + 1));
+
+    let tokens = lexer.take_while(|t| t.as_ref().is_ok_and(|t| t.kind != TokenKind::Eof)).count();
+    assert!(tokens > 2_000_000);
+}
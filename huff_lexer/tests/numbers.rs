@@ -32,3 +32,83 @@ fn lexes_large_numbers() {
     // We covered the whole source
     assert!(lexer.eof);
 }
+
+#[test]
+fn lexes_numbers_with_underscores() {
+    let source = "1_000_000";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source.source);
+
+    // Underscores are stripped before parsing the number
+    let tok = lexer.next().unwrap().unwrap();
+    assert_eq!(tok, Token::new(TokenKind::Num(1_000_000), Span::new(0..8, None)));
+
+    // Trailing and doubled underscores are rejected
+    for source in ["1_", "1__0"] {
+        let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+        let mut lexer = Lexer::new(flattened_source.source);
+
+        let err = lexer.next().unwrap().unwrap_err();
+        assert_eq!(err.kind, LexicalErrorKind::MisplacedUnderscore(source.to_string()));
+    }
+}
+
+#[test]
+fn lexes_decimal_literals() {
+    let literal = |source: &str| {
+        Lexer::new(source).map(|t| t.unwrap()).find(|t| matches!(t.kind, TokenKind::Literal(_)))
+    };
+
+    // Decimals are literals in constants and macro bodies, just like hex
+    let decimal = literal("#define constant DECIMALS = 18").unwrap();
+    let hex = literal("#define constant DECIMALS = 0x12").unwrap();
+    assert_eq!(decimal.kind, hex.kind);
+    assert_eq!(decimal.span, Span::new(28..29, None));
+
+    let decimal = literal("#define macro MAIN() = takes(0) returns(0) { 1000000 }").unwrap();
+    assert_eq!(decimal.kind, TokenKind::Literal(str_to_bytes32("0f4240")));
+
+    // The largest word still fits
+    let max = "115792089237316195423570985008687907853269984665640564039457584007913129639935";
+    let source = format!("#define constant MAX = {max}");
+    assert_eq!(literal(&source).unwrap().kind, TokenKind::Literal([0xff; 32]));
+
+    let source = format!("#define constant TOO_LARGE = {max}0");
+    let err = Lexer::new(&source).find_map(|t| t.err()).unwrap();
+    assert_eq!(err.kind, LexicalErrorKind::LiteralTooLarge(33));
+}
+
+#[test]
+fn lexes_negative_decimal_literals() {
+    let literal = |source: &str| {
+        Lexer::new(source).map(|t| t.unwrap()).find(|t| matches!(t.kind, TokenKind::Literal(_)))
+    };
+
+    // Negative decimals are lexed as their 256-bit two's complement
+    let minus_one = literal("#define constant MINUS_ONE = -1").unwrap();
+    assert_eq!(minus_one.kind, TokenKind::Literal([0xff; 32]));
+    assert_eq!(minus_one.span, Span::new(29..30, None));
+
+    let mut minus_two = [0xff; 32];
+    minus_two[31] = 0xfe;
+    let source = "#define macro MAIN() = takes(0) returns(0) { -2 }";
+    assert_eq!(literal(source).unwrap().kind, TokenKind::Literal(minus_two));
+    assert_eq!(literal("#define constant ZERO = -0").unwrap().kind, TokenKind::Literal([0; 32]));
+
+    // The smallest signed value still fits
+    let min = "57896044618658097711785492504343953926634992332820282019728792003956564819968";
+    let mut expected = [0; 32];
+    expected[0] = 0x80;
+    let source = format!("#define constant MIN = -{min}");
+    assert_eq!(literal(&source).unwrap().kind, TokenKind::Literal(expected));
+
+    let source = "#define constant TOO_SMALL = -57896044618658097711785492504343953926634992332820282019728792003956564819969";
+    let err = Lexer::new(source).find_map(|t| t.err()).unwrap();
+    assert!(matches!(err.kind, LexicalErrorKind::NegativeLiteralTooSmall(_)));
+
+    // A minus sign followed by a space is not a negative literal
+    let kinds = Lexer::new("#define macro MAIN() = takes(0) returns(0) { - 1 }")
+        .map(|t| t.unwrap().kind)
+        .collect::<Vec<_>>();
+    assert!(kinds.contains(&TokenKind::Sub));
+}
@@ -32,3 +32,65 @@ fn opcodes() {
         );
     }
 }
+
+#[test]
+fn transient_storage_opcodes() {
+    let source = "#define macro TRANSIENT() = takes(2) returns(1) { tstore 0x01 tload }";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let lexer = Lexer::new(flattened_source.source);
+
+    let tokens = lexer
+        .into_iter()
+        .map(|x| x.unwrap().kind)
+        .filter(|x| !matches!(x, TokenKind::Whitespace))
+        .collect::<Vec<TokenKind>>();
+    assert_eq!(tokens[tokens.len() - 5], TokenKind::Opcode(Opcode::Tstore));
+    assert_eq!(tokens[tokens.len() - 3], TokenKind::Opcode(Opcode::Tload));
+}
+
+#[test]
+fn opcodes_in_any_case() {
+    let source = "#define macro STORE() = takes(2) returns(0) { MSTORE mstore MStore }";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let lexer = Lexer::new(flattened_source.source);
+
+    let tokens = lexer
+        .into_iter()
+        .map(|x| x.unwrap().kind)
+        .filter(|x| !matches!(x, TokenKind::Whitespace))
+        .collect::<Vec<TokenKind>>();
+    assert_eq!(
+        tokens[tokens.len() - 5..tokens.len() - 2],
+        vec![TokenKind::Opcode(Opcode::Mstore); 3]
+    );
+}
+
+#[test]
+fn uppercase_names_are_not_opcodes() {
+    let source =
+        "#define macro STORE(ADDRESS) = takes(0) returns(0) { [BALANCE] <ADDRESS> CALLER() }";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let lexer = Lexer::new(flattened_source.source);
+
+    assert!(!lexer.into_iter().any(|x| matches!(x.unwrap().kind, TokenKind::Opcode(_))));
+}
+
+#[test]
+fn uppercase_labels_are_not_opcodes() {
+    let source = r#"#define macro MAIN() = takes(0) returns(0) {
+        0x00 calldataload RETURN jumpi
+        RETURN:
+            STOP
+    }"#;
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let lexer = Lexer::new(flattened_source.source);
+
+    let tokens = lexer
+        .into_iter()
+        .map(|x| x.unwrap().kind)
+        .filter(|x| !matches!(x, TokenKind::Whitespace))
+        .collect::<Vec<TokenKind>>();
+    assert!(tokens.contains(&TokenKind::Ident("RETURN".to_string())));
+    assert!(!tokens.contains(&TokenKind::Opcode(Opcode::Return)));
+    assert!(tokens.contains(&TokenKind::Opcode(Opcode::Stop)));
+}
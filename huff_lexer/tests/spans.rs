@@ -0,0 +1,52 @@
+use huff_lexer::*;
+use huff_utils::prelude::*;
+
+const SOURCE: &str = r#"/* Block /* nested */ comment */
+#include "./Utils.huff"
+
+#define function transfer(address to, uint256[] amounts) nonpayable returns (bool)
+#define event Transfer(address indexed from, address indexed to, uint256)
+#define constant OWNER = FREE_STORAGE_POINTER()
+#define constant MASK = 0xff_ff
+
+// Dé-duplicated — helpers
+#define jumptable SWITCH {
+    hot cold
+}
+#define table CODE {
+    0xdeadbeef
+}
+
+#[calldata(0x01)]
+#define macro MAIN() = takes (0) returns   (0) {
+    0b1010 0x01 add     // trailing comment
+    [OWNER] sload 42 __FUNC_SIG("transfer(address,uint256)") <arg> HELPER(0x02, true)
+    hot:
+        0x00 0x00 return
+    cold:
+        0x00 dup1 revert
+}"#;
+
+#[test]
+fn spans_are_contiguous() {
+    let tokens = Lexer::new(SOURCE).map(|t| t.unwrap()).collect::<Vec<Token>>();
+    let (eof, tokens) = tokens.split_last().unwrap();
+    assert_eq!(eof.kind, TokenKind::Eof);
+
+    // Every token starts right after the previous one ends
+    assert_eq!(tokens[0].span.start, 0);
+    for pair in tokens.windows(2) {
+        assert_eq!(pair[1].span.start, pair[0].span.end + 1, "gap after {:?}", pair[0]);
+    }
+    assert_eq!(tokens.last().unwrap().span.end, SOURCE.len() - 1);
+}
+
+#[test]
+fn tokens_reconstruct_source() {
+    let reconstructed = Lexer::new(SOURCE)
+        .map(|t| t.unwrap())
+        .filter(|t| t.kind != TokenKind::Eof)
+        .map(|t| &SOURCE[t.span.start..=t.span.end])
+        .collect::<String>();
+    assert_eq!(reconstructed.as_bytes(), SOURCE.as_bytes());
+}
@@ -0,0 +1,55 @@
+use huff_lexer::*;
+use huff_utils::prelude::*;
+
+#[test]
+fn parses_string_literal() {
+    let source = "\"hello\"";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source.source);
+
+    let tok = lexer.next().unwrap().unwrap();
+    assert_eq!(tok, Token::new(TokenKind::Str("hello".to_string()), Span::new(0..6, None)));
+
+    lexer.next();
+    assert!(lexer.eof);
+}
+
+#[test]
+fn parses_string_escapes() {
+    let source = r#""a\tb\nc\x41\x00\\\"""#;
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source.source);
+
+    let tok = lexer.next().unwrap().unwrap();
+    assert_eq!(tok.kind, TokenKind::Str("a\tb\nc\x41\x00\\\"".to_string()));
+    assert_eq!(tok.span, Span::new(0..source.len() - 1, None));
+
+    lexer.next();
+    assert!(lexer.eof);
+}
+
+#[test]
+fn keeps_unknown_escapes() {
+    let source = r#""..\contracts\Ownable.huff""#;
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source.source);
+
+    let tok = lexer.next().unwrap().unwrap();
+    assert_eq!(tok.kind, TokenKind::Str(r"..\contracts\Ownable.huff".to_string()));
+}
+
+#[test]
+fn fails_on_invalid_hex_escape() {
+    let source = r#""abc\x4g""#;
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source.source);
+
+    let err = lexer.next().unwrap().unwrap_err();
+    assert_eq!(
+        err,
+        LexicalError::new(
+            LexicalErrorKind::InvalidEscapeSequence("\\x4".to_string()),
+            Span::new(4..6, None)
+        )
+    );
+}
@@ -29,6 +29,8 @@ pub struct Parser {
     pub spans: Vec<Span>,
     /// Our remapper
     pub remapper: files::Remapper,
+    /// Macro-local constants declared in the parsed macro bodies
+    pub local_constants: Vec<ConstantDefinition>,
 }
 
 impl Parser {
@@ -36,7 +38,15 @@ impl Parser {
     pub fn new(tokens: Vec<Token>, base: Option<String>) -> Self {
         let initial_token = tokens.first().unwrap().clone();
         let remapper = files::Remapper::new("./");
-        Self { tokens, cursor: 0, current_token: initial_token, base, spans: vec![], remapper }
+        Self {
+            tokens,
+            cursor: 0,
+            current_token: initial_token,
+            base,
+            spans: vec![],
+            remapper,
+            local_constants: vec![],
+        }
     }
 
     /// Resets the current token and cursor to the first token in the parser's token vec
@@ -49,9 +59,13 @@ impl Parser {
 
     /// Parse
     pub fn parse(&mut self) -> Result<Contract, ParserError> {
-        // Remove all whitespaces, newlines, and comments first
-        self.tokens
-            .retain(|token| !matches!(token.kind, TokenKind::Whitespace | TokenKind::Comment(_)));
+        // Remove all whitespaces, newlines, comments, and region markers first
+        self.tokens.retain(|token| {
+            !matches!(
+                token.kind,
+                TokenKind::Whitespace | TokenKind::Comment(_) | TokenKind::Region(_, _)
+            )
+        });
 
         // Reset the initial token
         self.reset();
@@ -96,6 +110,16 @@ impl Parser {
                         tracing::info!(target: "parser", "SUCCESSFULLY PARSED CONSTANT {}", c.name);
                         contract.constants.lock().unwrap().push(c);
                     }
+                    TokenKind::Receive => {
+                        self.parse_fallback_function()?;
+                        tracing::info!(target: "parser", "SUCCESSFULLY PARSED RECEIVE FUNCTION");
+                        contract.receive = true;
+                    }
+                    TokenKind::Fallback => {
+                        let fn_type = self.parse_fallback_function()?;
+                        tracing::info!(target: "parser", "SUCCESSFULLY PARSED FALLBACK FUNCTION");
+                        contract.fallback = Some(fn_type);
+                    }
                     TokenKind::Error => {
                         let e = self.parse_custom_error()?;
                         tracing::info!(target: "parser", "SUCCESSFULLY PARSED ERROR {}", e.name);
@@ -139,6 +163,21 @@ impl Parser {
             }
         }
 
+        // Macro-local constants may not shadow global constants
+        if let Some(local) = self
+            .local_constants
+            .iter()
+            .find(|l| contract.constants.lock().unwrap().iter().any(|c| c.name == l.name))
+        {
+            tracing::error!(target: "parser", "MACRO-LOCAL CONSTANT SHADOWS GLOBAL CONSTANT: {}", local.name);
+            return Err(ParserError {
+                kind: ParserErrorKind::ShadowedConstant(local.name.clone()),
+                hint: Some(format!("Rename either the local or the global \"{}\"", local.name)),
+                spans: local.span.clone(),
+                cursor: self.cursor,
+            })
+        }
+
         Ok(contract)
     }
 
@@ -309,6 +348,48 @@ impl Parser {
         })
     }
 
+    /// Parses a receive or fallback function declaration, returning its state mutability.
+    ///
+    /// The mutability may be omitted, in which case a receive function is `payable` and a
+    /// fallback function is `nonpayable`. A receive function must always be `payable`.
+    pub fn parse_fallback_function(&mut self) -> Result<FunctionType, ParserError> {
+        let kind = self.current_token.kind.clone();
+        if !matches!(kind, TokenKind::Receive | TokenKind::Fallback) {
+            return Err(ParserError {
+                kind: ParserErrorKind::UnexpectedType(kind),
+                hint: Some("Expected `receive` or `fallback`.".to_string()),
+                spans: AstSpan(vec![self.current_token.span.clone()]),
+                cursor: self.cursor,
+            })
+        }
+        self.consume();
+        self.match_kind(TokenKind::OpenParen)?;
+        self.match_kind(TokenKind::CloseParen)?;
+
+        let fn_type = match self.current_token.kind.clone() {
+            TokenKind::Payable => FunctionType::Payable,
+            TokenKind::NonPayable if kind == TokenKind::Fallback => FunctionType::NonPayable,
+            tok @ (TokenKind::NonPayable | TokenKind::View | TokenKind::Pure) => {
+                return Err(ParserError {
+                    kind: ParserErrorKind::UnexpectedType(tok),
+                    hint: Some(if kind == TokenKind::Receive {
+                        "A receive function must be `payable`.".to_string()
+                    } else {
+                        "Expected one of: `payable`, `nonpayable`.".to_string()
+                    }),
+                    spans: AstSpan(vec![self.current_token.span.clone()]),
+                    cursor: self.cursor,
+                })
+            }
+            _ if kind == TokenKind::Receive => return Ok(FunctionType::Payable),
+            _ => return Ok(FunctionType::NonPayable),
+        };
+        // consume the function type
+        self.consume();
+
+        Ok(fn_type)
+    }
+
     /// Parse an event.
     pub fn parse_event(&mut self) -> Result<EventDefinition, ParserError> {
         // The event should start with `TokenKind::Event`
@@ -435,7 +516,8 @@ impl Parser {
 
     /// Parses a decorator.
     ///
-    /// Decorators are currently used to add additional flags to a test.
+    /// Decorators are currently used to add additional flags to a test or to mark a macro as
+    /// `view`.
     pub fn parse_decorator(&mut self) -> Result<Decorator, ParserError> {
         self.match_kind(TokenKind::Pound)?;
         self.match_kind(TokenKind::OpenBracket)?;
@@ -444,12 +526,12 @@ impl Parser {
 
         while !self.check(TokenKind::CloseBracket) {
             if let TokenKind::Ident(s) = self.match_kind(TokenKind::Ident(String::default()))? {
-                // Consume the open parenthesis
-                self.consume();
-
                 match DecoratorFlag::try_from(&s) {
+                    // The view flag does not accept any arguments
+                    Ok(DecoratorFlag::View) => flags.push(DecoratorFlag::View),
                     // The calldata flag accepts a single string as an argument
                     Ok(DecoratorFlag::Calldata(_)) => {
+                        self.match_kind(TokenKind::OpenParen)?;
                         if let TokenKind::Str(s) =
                             &self.match_kind(TokenKind::Str(String::default()))?
                         {
@@ -464,9 +546,11 @@ impl Parser {
                                 cursor: self.cursor,
                             });
                         }
+                        self.match_kind(TokenKind::CloseParen)?;
                     }
                     // The value flag accepts a single literal as an argument
                     Ok(DecoratorFlag::Value(_)) => {
+                        self.match_kind(TokenKind::OpenParen)?;
                         if let TokenKind::Literal(l) =
                             self.match_kind(TokenKind::Literal(Literal::default()))?
                         {
@@ -481,6 +565,7 @@ impl Parser {
                                 cursor: self.cursor,
                             });
                         }
+                        self.match_kind(TokenKind::CloseParen)?;
                     }
                     Err(_) => {
                         tracing::error!(target: "parser", "DECORATOR FLAG NOT FOUND: {}", s);
@@ -493,9 +578,6 @@ impl Parser {
                     }
                 }
 
-                // Consume the closing parenthesis
-                self.match_kind(TokenKind::CloseParen)?;
-
                 // Multiple flags are possible
                 if self.check(TokenKind::Comma) {
                     self.consume();
@@ -573,6 +655,7 @@ impl Parser {
     /// Only HEX, OPCODES, labels, builtins, and MACRO calls should be authorized.
     pub fn parse_body(&mut self) -> Result<Vec<Statement>, ParserError> {
         let mut statements: Vec<Statement> = Vec::new();
+        let mut locals: Vec<ConstantDefinition> = Vec::new();
         self.match_kind(TokenKind::OpenBrace)?;
         tracing::info!(target: "parser", "PARSING MACRO BODY");
         while !self.check(TokenKind::CloseBrace) {
@@ -636,6 +719,23 @@ impl Parser {
                         }
                     }
                 }
+                TokenKind::Ident(ident_str)
+                    if ident_str == "const" &&
+                        matches!(self.peek().map(|t| t.kind), Some(TokenKind::Ident(_))) =>
+                {
+                    let local = self.parse_local_constant()?;
+                    tracing::info!(target: "parser", "PARSING MACRO BODY: [LOCAL CONSTANT: {}]", local.name);
+                    if locals.iter().any(|l| l.name == local.name) {
+                        tracing::error!(target: "parser", "DUPLICATE MACRO-LOCAL CONSTANT: {}", local.name);
+                        return Err(ParserError {
+                            kind: ParserErrorKind::ShadowedConstant(local.name.clone()),
+                            hint: Some("Macro-local constant names should be unique".to_string()),
+                            spans: local.span,
+                            cursor: self.cursor,
+                        })
+                    }
+                    locals.push(local);
+                }
                 TokenKind::Ident(ident_str) => {
                     let mut curr_spans = vec![self.current_token.span.clone()];
                     tracing::info!(target: "parser", "PARSING MACRO BODY: [IDENT: {}]", ident_str);
@@ -644,7 +744,7 @@ impl Parser {
                     match self.current_token.kind.clone() {
                         TokenKind::OpenParen => {
                             // Parse Macro Call
-                            let lit_args = self.parse_macro_call()?;
+                            let (lit_args, arg_names) = self.parse_macro_call()?;
                             // Grab all spans following our macro invocation spam
                             if let Some(i) = self.spans.iter().position(|s| s.eq(&curr_spans[0])) {
                                 curr_spans.append(&mut self.spans[(i + 1)..].to_vec());
@@ -653,6 +753,7 @@ impl Parser {
                                 ty: StatementType::MacroInvocation(MacroInvocation {
                                     macro_name: ident_str.to_string(),
                                     args: lit_args,
+                                    arg_names,
                                     span: AstSpan(curr_spans.clone()),
                                 }),
                                 span: AstSpan(curr_spans),
@@ -728,9 +829,103 @@ impl Parser {
         }
         // consume close brace
         self.match_kind(TokenKind::CloseBrace)?;
+
+        // Macro-local constants are resolved in place, so they are invisible outside the macro
+        Parser::resolve_local_constants(&mut statements, &locals);
+        self.local_constants.extend(locals);
         Ok(statements)
     }
 
+    /// Parses a macro-local constant declaration.
+    ///
+    /// ## Examples
+    ///
+    /// ```huff
+    /// const OFFSET = 0x20
+    /// ```
+    pub fn parse_local_constant(&mut self) -> Result<ConstantDefinition, ParserError> {
+        let first_span = self.spans.len();
+
+        // Consume the `const` keyword and parse the constant name
+        self.consume();
+        self.match_kind(TokenKind::Ident("x".to_string()))?;
+        let name = match self.peek_behind().unwrap().kind {
+            TokenKind::Ident(const_name) => const_name,
+            tok => {
+                return Err(ParserError {
+                    kind: ParserErrorKind::UnexpectedType(tok),
+                    hint: Some("Expected constant name.".to_string()),
+                    spans: AstSpan(self.spans[first_span..].to_vec()),
+                    cursor: self.cursor,
+                })
+            }
+        };
+
+        // We must assign a literal value to the constant
+        self.match_kind(TokenKind::Assign)?;
+        let value = match self.current_token.kind.clone() {
+            TokenKind::Literal(l) => {
+                self.consume();
+                ConstVal::Literal(l)
+            }
+            kind => {
+                tracing::error!(target: "parser", "TOKEN MISMATCH - EXPECTED Literal, GOT: {}", kind);
+                return Err(ParserError {
+                    kind: ParserErrorKind::InvalidConstantValue(kind),
+                    hint: Some("Expected macro-local constant value to be a literal".to_string()),
+                    spans: AstSpan(vec![self.current_token.span.clone()]),
+                    cursor: self.cursor,
+                })
+            }
+        };
+
+        Ok(ConstantDefinition { name, value, span: AstSpan(self.spans[first_span..].to_vec()) })
+    }
+
+    /// Replaces pushes of macro-local constants with their literal values, including inside labels
+    /// and in the arguments of macro invocations and builtin function calls
+    fn resolve_local_constants(statements: &mut [Statement], locals: &[ConstantDefinition]) {
+        let local = |name: &str| match locals.iter().find(|c| c.name == name).map(|c| &c.value) {
+            Some(ConstVal::Literal(l)) => Some(*l),
+            _ => None,
+        };
+        // Builtin arguments hold literals as hex in their name
+        let resolve_builtin = |bf: &mut BuiltinFunctionCall| {
+            for arg in bf.args.iter_mut() {
+                if let Some(l) = arg.name.as_deref().and_then(local) {
+                    arg.name = Some(bytes32_to_string(&l, false));
+                }
+            }
+        };
+        for statement in statements.iter_mut() {
+            match &mut statement.ty {
+                StatementType::Constant(name) => {
+                    if let Some(l) = local(name) {
+                        statement.ty = StatementType::Literal(l);
+                    }
+                }
+                StatementType::MacroInvocation(mi) => {
+                    for arg in mi.args.iter_mut() {
+                        match arg {
+                            MacroArg::Ident(name) => {
+                                if let Some(l) = local(name) {
+                                    *arg = MacroArg::Literal(l);
+                                }
+                            }
+                            MacroArg::Builtin(bf) => resolve_builtin(bf),
+                            _ => {}
+                        }
+                    }
+                }
+                StatementType::BuiltinFunctionCall(bf) => resolve_builtin(bf),
+                StatementType::Label(label) => {
+                    Parser::resolve_local_constants(&mut label.inner, locals)
+                }
+                _ => {}
+            }
+        }
+    }
+
     /// Parse the body of a label.
     ///
     /// ## Examples
@@ -775,7 +970,7 @@ impl Parser {
                     match self.current_token.kind.clone() {
                         TokenKind::OpenParen => {
                             // Parse Macro Call
-                            let lit_args = self.parse_macro_call()?;
+                            let (lit_args, arg_names) = self.parse_macro_call()?;
                             // Grab all spans following our macro invocation spam
                             if let Some(i) = self.spans.iter().position(|s| s.eq(&curr_spans[0])) {
                                 curr_spans.append(&mut self.spans[(i + 1)..].to_vec());
@@ -784,6 +979,7 @@ impl Parser {
                                 ty: StatementType::MacroInvocation(MacroInvocation {
                                     macro_name: ident_str.to_string(),
                                     args: lit_args,
+                                    arg_names,
                                     span: AstSpan(curr_spans.clone()),
                                 }),
                                 span: AstSpan(curr_spans),
@@ -878,6 +1074,7 @@ impl Parser {
                         name: Some(s.to_owned()), // Place the string in the "name" field
                         arg_type: None,
                         indexed: false,
+                        default: None,
                         span: AstSpan(vec![self.current_token.span.clone()]),
                         arg_location: None,
                     });
@@ -899,6 +1096,28 @@ impl Parser {
                         arg_location: None,
                         arg_type: None,
                         indexed: false,
+                        default: None,
+                        span: AstSpan(vec![self.current_token.span.clone()]),
+                    });
+                    self.consume();
+
+                    // multiple args possible
+                    if self.check(TokenKind::Comma) {
+                        self.consume();
+                        on_type = true;
+                    }
+                    continue;
+                }
+
+                // Check for decimal numbers
+                if let TokenKind::Num(n) = &self.current_token.kind {
+                    args.push(Argument {
+                        // Place the number as hex in the "name" field, like literals
+                        name: Some(format!("{n:02x}")),
+                        arg_location: None,
+                        arg_type: None,
+                        indexed: false,
+                        default: None,
                         span: AstSpan(vec![self.current_token.span.clone()]),
                     });
                     self.consume();
@@ -987,6 +1206,13 @@ impl Parser {
                 arg_spans.push(self.current_token.span.clone());
                 arg.name = Some(self.match_kind(TokenKind::Ident("x".to_string()))?.to_string());
                 on_type = !on_type;
+
+                // Macro parameters can declare a default value like `FOO(x = 0x20)`
+                if !select_type && self.check(TokenKind::Assign) {
+                    self.consume();
+                    arg_spans.push(self.current_token.span.clone());
+                    arg.default = Some(self.parse_macro_arg()?);
+                }
             }
 
             // multiple args possible
@@ -1035,64 +1261,115 @@ impl Parser {
     }
 
     /// Parse call to a macro.
-    pub fn parse_macro_call(&mut self) -> Result<Vec<MacroArg>, ParserError> {
+    pub fn parse_macro_call(&mut self) -> Result<(Vec<MacroArg>, Vec<String>), ParserError> {
         self.parse_macro_call_args()
     }
 
     /// Parse the arguments of a macro call.
-    pub fn parse_macro_call_args(&mut self) -> Result<Vec<MacroArg>, ParserError> {
+    ///
+    /// Returns the arguments along with their parameter names, which are empty unless the
+    /// arguments are named like `TRANSFER(to: 0x01, amount: 0x02)`.
+    pub fn parse_macro_call_args(&mut self) -> Result<(Vec<MacroArg>, Vec<String>), ParserError> {
         let mut args = vec![];
+        let mut arg_names = vec![];
         self.match_kind(TokenKind::OpenParen)?;
         while !self.check(TokenKind::CloseParen) {
-            // We can pass either directly hex values or labels (without the ":")
-            match self.current_token.kind.clone() {
-                TokenKind::Literal(lit) => {
-                    args.push(MacroArg::Literal(lit));
-                    self.consume();
-                }
-                TokenKind::Ident(ident) => {
-                    args.push(MacroArg::Ident(ident));
-                    self.consume();
-                }
-                TokenKind::Calldata => {
-                    args.push(MacroArg::Ident("calldata".to_string()));
-                    self.consume();
-                }
-                TokenKind::LeftAngle => {
-                    // Passed into the Macro Call like:
-                    // GET_SLOT_FROM_KEY(<mem_ptr>)  // [slot]
-                    self.consume();
-                    let arg_name =
-                        self.match_kind(TokenKind::Ident("ARG_CALL".to_string()))?.to_string();
-                    args.push(MacroArg::ArgCall(arg_name));
-                    self.match_kind(TokenKind::RightAngle)?;
-                }
-                arg => {
-                    tracing::error!(
-                        target: "parser",
-                        "Invalid macro call arguments. Must be of kind Ident or Literal. Got: {}",
-                        self.current_token.kind
-                    );
-                    let new_spans = self.spans.clone();
-                    self.spans = vec![];
-                    return Err(ParserError {
-                        kind: ParserErrorKind::InvalidMacroArgs(arg),
-                        hint: Some(
-                            "Expected literal, identifier (string), or an argument call"
-                                .to_string(),
-                        ),
-                        spans: AstSpan(new_spans),
-                        cursor: self.cursor,
-                    });
+            // A name followed by a colon is lexed as a label
+            let name = match self.current_token.kind.clone() {
+                TokenKind::Label(name) => Some(name),
+                TokenKind::Ident(name)
+                    if self.peek().is_some_and(|t| t.kind == TokenKind::Colon) =>
+                {
+                    Some(name)
                 }
+                _ => None,
+            };
+            if !args.is_empty() && name.is_some() == arg_names.is_empty() {
+                tracing::error!(target: "parser", "MIXED NAMED AND POSITIONAL MACRO ARGUMENTS");
+                return Err(ParserError {
+                    kind: ParserErrorKind::MixedMacroArgs,
+                    hint: Some("Either name every argument or none of them".to_string()),
+                    spans: AstSpan(vec![self.current_token.span.clone()]),
+                    cursor: self.cursor,
+                })
+            }
+            if let Some(name) = name {
+                self.consume();
+                self.match_kind(TokenKind::Colon)?;
+                arg_names.push(name);
             }
+            args.push(self.parse_macro_arg()?);
             if self.check(TokenKind::Comma) {
                 self.consume();
             }
         }
         // consume close parenthesis
         self.consume();
-        Ok(args)
+        Ok((args, arg_names))
+    }
+
+    /// Parses a single argument of a macro call, or the default value of a macro parameter.
+    pub fn parse_macro_arg(&mut self) -> Result<MacroArg, ParserError> {
+        // We can pass either directly hex values or labels (without the ":")
+        let arg = match self.current_token.kind.clone() {
+            TokenKind::Literal(lit) => {
+                self.consume();
+                MacroArg::Literal(lit)
+            }
+            TokenKind::Num(n) => {
+                self.consume();
+                MacroArg::Literal(str_to_bytes32(&format!("{n:x}")))
+            }
+            TokenKind::Ident(ident) => {
+                self.consume();
+                MacroArg::Ident(ident)
+            }
+            TokenKind::Calldata => {
+                self.consume();
+                MacroArg::Ident("calldata".to_string())
+            }
+            TokenKind::LeftAngle => {
+                // Passed into the Macro Call like:
+                // GET_SLOT_FROM_KEY(<mem_ptr>)  // [slot]
+                self.consume();
+                let arg_name =
+                    self.match_kind(TokenKind::Ident("ARG_CALL".to_string()))?.to_string();
+                self.match_kind(TokenKind::RightAngle)?;
+                MacroArg::ArgCall(arg_name)
+            }
+            TokenKind::BuiltinFunction(f) => {
+                // Passed into the Macro Call like:
+                // REQUIRE(__FUNC_SIG("transfer(address,uint256)"))
+                let mut curr_spans = vec![self.current_token.span.clone()];
+                self.match_kind(TokenKind::BuiltinFunction(String::default()))?;
+                let builtin_args = self.parse_args(true, false, false, true)?;
+                builtin_args.iter().for_each(|a| curr_spans.extend_from_slice(a.span.inner_ref()));
+                MacroArg::Builtin(BuiltinFunctionCall {
+                    kind: BuiltinFunctionKind::from(f),
+                    args: builtin_args,
+                    span: AstSpan(curr_spans),
+                })
+            }
+            arg => {
+                tracing::error!(
+                    target: "parser",
+                    "Invalid macro call arguments. Must be of kind Ident or Literal. Got: {}",
+                    self.current_token.kind
+                );
+                let new_spans = self.spans.clone();
+                self.spans = vec![];
+                return Err(ParserError {
+                    kind: ParserErrorKind::InvalidMacroArgs(arg),
+                    hint: Some(
+                        "Expected literal, identifier (string), argument call, or builtin function call"
+                            .to_string(),
+                    ),
+                    spans: AstSpan(new_spans),
+                    cursor: self.cursor,
+                });
+            }
+        };
+        Ok(arg)
     }
 
     /// Parses a table (JumpTable, JumpTablePacked, or CodeTable).
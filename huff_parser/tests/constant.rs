@@ -55,7 +55,7 @@ fn test_parses_literal_constant() {
                 Span { start: 8, end: 15, file: None },
                 Span { start: 17, end: 23, file: None },
                 Span { start: 25, end: 25, file: None },
-                Span { start: 29, end: 92, file: None }
+                Span { start: 27, end: 92, file: None }
             ])
         }
     );
@@ -22,6 +22,7 @@ fn test_parses_custom_error() {
                 arg_type: Some(String::from("uint256")),
                 name: None,
                 indexed: false,
+                default: None,
                 span: AstSpan(vec![Span { start: 24, end: 30, file: None }]),
                 arg_location: None,
             }],
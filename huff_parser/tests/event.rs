@@ -82,6 +82,7 @@ fn test_parse_event() {
                         arg_type: Some(String::from("uint256")),
                         name: Some(String::from("a")),
                         indexed: true,
+                        default: None,
                         arg_location: None,
                         span: AstSpan(vec![
                             // "uint256"
@@ -96,6 +97,7 @@ fn test_parse_event() {
                         arg_type: Some(String::from("uint8")),
                         name: None,
                         indexed: true,
+                        default: None,
                         arg_location: None,
                         span: AstSpan(vec![
                             // "uint8"
@@ -144,6 +146,7 @@ fn test_parse_event() {
                         arg_type: Some(String::from("uint256")),
                         name: None,
                         indexed: false,
+                        default: None,
                         arg_location: None,
                         span: AstSpan(vec![
                             // "uint256"
@@ -154,6 +157,7 @@ fn test_parse_event() {
                         arg_type: Some(String::from("uint8")),
                         name: Some(String::from("b")),
                         indexed: false,
+                        default: None,
                         arg_location: None,
                         span: AstSpan(vec![
                             // "uint8"
@@ -198,6 +202,7 @@ fn test_parse_event() {
                         arg_type: Some(String::from("uint256")),
                         name: None,
                         indexed: true,
+                        default: None,
                         arg_location: None,
                         span: AstSpan(vec![
                             // "uint256"
@@ -210,6 +215,7 @@ fn test_parse_event() {
                         arg_type: Some(String::from("uint8")),
                         name: None,
                         indexed: false,
+                        default: None,
                         arg_location: None,
                         span: AstSpan(vec![
                             // "uint8"
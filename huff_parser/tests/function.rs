@@ -24,6 +24,7 @@ fn parses_valid_function_definition() {
                         name: None,
                         arg_type: Some(String::from("uint256")),
                         indexed: false,
+                        default: None,
                         arg_location: None,
                         span: AstSpan(vec![Span { start: 22, end: 28, file: None }]),
                     },
@@ -31,6 +32,7 @@ fn parses_valid_function_definition() {
                         name: Some(String::from("b")),
                         arg_type: Some(String::from("bool")),
                         indexed: false,
+                        default: None,
                         arg_location: None,
                         span: AstSpan(vec![
                             Span { start: 30, end: 33, file: None },
@@ -43,6 +45,7 @@ fn parses_valid_function_definition() {
                     name: None,
                     arg_type: Some(String::from("uint256")),
                     indexed: false,
+                    default: None,
                     arg_location: None,
                     span: AstSpan(vec![Span { start: 51, end: 57, file: None }]),
                 }],
@@ -73,6 +76,7 @@ fn parses_valid_function_definition() {
                     name: None,
                     arg_type: Some(String::from("uint256")),
                     indexed: false,
+                    default: None,
                     arg_location: None,
                     span: AstSpan(vec![Span { start: 22, end: 28, file: None }]),
                 }],
@@ -81,6 +85,7 @@ fn parses_valid_function_definition() {
                     name: None,
                     arg_type: Some(String::from("uint256")),
                     indexed: false,
+                    default: None,
                     arg_location: None,
                     span: AstSpan(vec![Span { start: 44, end: 50, file: None }]),
                 }],
@@ -108,6 +113,7 @@ fn parses_valid_function_definition() {
                     name: None,
                     arg_type: Some(String::from("uint256")),
                     indexed: false,
+                    default: None,
                     arg_location: None,
                     span: AstSpan(vec![Span { start: 22, end: 28, file: None }]),
                 }],
@@ -116,6 +122,7 @@ fn parses_valid_function_definition() {
                     name: None,
                     arg_type: Some(String::from("uint256")),
                     indexed: false,
+                    default: None,
                     arg_location: None,
                     span: AstSpan(vec![Span { start: 50, end: 56, file: None }]),
                 }],
@@ -143,6 +150,7 @@ fn parses_valid_function_definition() {
                     name: None,
                     arg_type: Some(String::from("uint256")),
                     indexed: false,
+                    default: None,
                     arg_location: None,
                     span: AstSpan(vec![Span { start: 22, end: 28, file: None }]),
                 }],
@@ -151,6 +159,7 @@ fn parses_valid_function_definition() {
                     name: None,
                     arg_type: Some(String::from("uint256")),
                     indexed: false,
+                    default: None,
                     arg_location: None,
                     span: AstSpan(vec![Span { start: 47, end: 53, file: None }]),
                 }],
@@ -178,6 +187,7 @@ fn parses_valid_function_definition() {
                     name: None,
                     arg_type: Some(String::from("uint256[], bool[5]")),
                     indexed: false,
+                    default: None,
                     arg_location: None,
                     span: AstSpan(vec![]),
                 }],
@@ -186,6 +196,7 @@ fn parses_valid_function_definition() {
                     name: None,
                     arg_type: Some(String::from("uint256")),
                     indexed: false,
+                    default: None,
                     arg_location: None,
                     span: AstSpan(vec![]),
                 }],
@@ -275,6 +286,7 @@ fn test_can_prefix_function_arg_names_with_reserved_keywords() {
         Token { kind: TokenKind::View, span: Span { start: 55, end: 58, file: None } },
         Token { kind: TokenKind::Whitespace, span: Span { start: 59, end: 59, file: None } },
         Token { kind: TokenKind::Returns, span: Span { start: 60, end: 66, file: None } },
+        Token { kind: TokenKind::Whitespace, span: Span { start: 67, end: 67, file: None } },
         Token { kind: TokenKind::OpenParen, span: Span { start: 68, end: 68, file: None } },
         Token {
             kind: TokenKind::PrimitiveType(PrimitiveEVMType::Bool),
@@ -27,7 +27,7 @@ fn multiline_labels() {
         statements: vec![
             Statement {
                 ty: StatementType::Literal(str_to_bytes32("00")),
-                span: AstSpan(vec![Span { start: 65, end: 66, file: None }]),
+                span: AstSpan(vec![Span { start: 63, end: 66, file: None }]),
             },
             Statement {
                 ty: StatementType::Opcode(Opcode::Mstore),
@@ -35,11 +35,11 @@ fn multiline_labels() {
             },
             Statement {
                 ty: StatementType::Literal(str_to_bytes32("01")),
-                span: AstSpan(vec![Span { start: 83, end: 84, file: None }]),
+                span: AstSpan(vec![Span { start: 81, end: 84, file: None }]),
             },
             Statement {
                 ty: StatementType::Literal(str_to_bytes32("02")),
-                span: AstSpan(vec![Span { start: 88, end: 89, file: None }]),
+                span: AstSpan(vec![Span { start: 86, end: 89, file: None }]),
             },
             Statement {
                 ty: StatementType::Opcode(Opcode::Add),
@@ -53,6 +53,7 @@ fn multiline_labels() {
                             ty: StatementType::MacroInvocation(MacroInvocation {
                                 macro_name: "HELLO".to_string(),
                                 args: vec![],
+                                arg_names: vec![],
                                 span: AstSpan(vec![
                                     Span { start: 121, end: 125, file: None },
                                     Span { start: 126, end: 126, file: None },
@@ -67,11 +68,11 @@ fn multiline_labels() {
                         },
                         Statement {
                             ty: StatementType::Literal(str_to_bytes32("00")),
-                            span: AstSpan(vec![Span { start: 139, end: 140, file: None }]),
+                            span: AstSpan(vec![Span { start: 137, end: 140, file: None }]),
                         },
                         Statement {
                             ty: StatementType::Literal(str_to_bytes32("00")),
-                            span: AstSpan(vec![Span { start: 144, end: 145, file: None }]),
+                            span: AstSpan(vec![Span { start: 142, end: 145, file: None }]),
                         },
                         Statement {
                             ty: StatementType::Opcode(Opcode::Revert),
@@ -83,8 +84,8 @@ fn multiline_labels() {
                         Span { start: 121, end: 125, file: None },
                         Span { start: 126, end: 126, file: None },
                         Span { start: 127, end: 127, file: None },
-                        Span { start: 139, end: 140, file: None },
-                        Span { start: 144, end: 145, file: None },
+                        Span { start: 137, end: 140, file: None },
+                        Span { start: 142, end: 145, file: None },
                         Span { start: 147, end: 152, file: None },
                     ]),
                 }),
@@ -93,8 +94,8 @@ fn multiline_labels() {
                     Span { start: 121, end: 125, file: None },
                     Span { start: 126, end: 126, file: None },
                     Span { start: 127, end: 127, file: None },
-                    Span { start: 139, end: 140, file: None },
-                    Span { start: 144, end: 145, file: None },
+                    Span { start: 137, end: 140, file: None },
+                    Span { start: 142, end: 145, file: None },
                     Span { start: 147, end: 152, file: None },
                 ]),
             },
@@ -133,13 +134,13 @@ fn multiline_labels() {
             // "{"
             Span { start: 55, end: 55, file: None },
             // "0x00"
-            Span { start: 65, end: 66, file: None },
+            Span { start: 63, end: 66, file: None },
             // "mstore"
             Span { start: 68, end: 73, file: None },
             // "0x01"
-            Span { start: 83, end: 84, file: None },
+            Span { start: 81, end: 84, file: None },
             // "0x02"
-            Span { start: 88, end: 89, file: None },
+            Span { start: 86, end: 89, file: None },
             // "add"
             Span { start: 91, end: 93, file: None },
             // "cool_label"
@@ -153,9 +154,9 @@ fn multiline_labels() {
             // ")"
             Span { start: 127, end: 127, file: None },
             // "0x00"
-            Span { start: 139, end: 140, file: None },
+            Span { start: 137, end: 140, file: None },
             // "0x00"
-            Span { start: 144, end: 145, file: None },
+            Span { start: 142, end: 145, file: None },
             // "revert"
             Span { start: 147, end: 152, file: None },
             // "}"
@@ -225,6 +226,7 @@ pub fn builtins_under_labels() {
                                 arg_type: None,
                                 name: Some(String::from("TEST_TABLE")),
                                 indexed: false,
+                                default: None,
                                 arg_location: None,
                                 span: AstSpan(vec![Span { start: 342, end: 351, file: None }]),
                             }],
@@ -245,6 +247,7 @@ pub fn builtins_under_labels() {
                                 arg_type: None,
                                 name: Some(String::from("TEST_TABLE")),
                                 indexed: false,
+                                default: None,
                                 arg_location: None,
                                 span: AstSpan(vec![Span { start: 378, end: 387, file: None }]),
                             }],
@@ -265,6 +268,7 @@ pub fn builtins_under_labels() {
                                 arg_type: None,
                                 name: Some(String::from("SMALL_MACRO")),
                                 indexed: false,
+                                default: None,
                                 arg_location: None,
                                 span: AstSpan(vec![Span { start: 413, end: 423, file: None }]),
                             }],
@@ -285,6 +289,7 @@ pub fn builtins_under_labels() {
                                 arg_type: None,
                                 name: Some(String::from("myFunc")),
                                 indexed: false,
+                                default: None,
                                 arg_location: None,
                                 span: AstSpan(vec![Span { start: 449, end: 454, file: None }]),
                             }],
@@ -305,6 +310,7 @@ pub fn builtins_under_labels() {
                                 arg_type: None,
                                 name: Some(String::from("TestError")),
                                 indexed: false,
+                                default: None,
                                 arg_location: None,
                                 span: AstSpan(vec![Span { start: 477, end: 485, file: None }]),
                             }],
@@ -325,17 +331,18 @@ pub fn builtins_under_labels() {
                                 arg_type: None,
                                 name: Some(String::from("bb")),
                                 indexed: false,
+                                default: None,
                                 arg_location: None,
-                                span: AstSpan(vec![Span { start: 513, end: 514, file: None }]),
+                                span: AstSpan(vec![Span { start: 511, end: 514, file: None }]),
                             }],
                             span: AstSpan(vec![
                                 Span { start: 500, end: 509, file: None },
-                                Span { start: 513, end: 514, file: None },
+                                Span { start: 511, end: 514, file: None },
                             ]),
                         }),
                         span: AstSpan(vec![
                             Span { start: 500, end: 509, file: None },
-                            Span { start: 513, end: 514, file: None },
+                            Span { start: 511, end: 514, file: None },
                         ]),
                     },
                 ],
@@ -352,7 +359,7 @@ pub fn builtins_under_labels() {
                     Span { start: 469, end: 475, file: None },
                     Span { start: 477, end: 485, file: None },
                     Span { start: 500, end: 509, file: None },
-                    Span { start: 513, end: 514, file: None },
+                    Span { start: 511, end: 514, file: None },
                 ]),
             }),
             span: AstSpan(vec![
@@ -368,7 +375,7 @@ pub fn builtins_under_labels() {
                 Span { start: 469, end: 475, file: None },
                 Span { start: 477, end: 485, file: None },
                 Span { start: 500, end: 509, file: None },
-                Span { start: 513, end: 514, file: None },
+                Span { start: 511, end: 514, file: None },
             ]),
         }],
         takes: 3,
@@ -413,7 +420,7 @@ pub fn builtins_under_labels() {
             Span { start: 486, end: 486, file: None },
             Span { start: 500, end: 509, file: None },
             Span { start: 510, end: 510, file: None },
-            Span { start: 513, end: 514, file: None },
+            Span { start: 511, end: 514, file: None },
             Span { start: 515, end: 515, file: None },
             Span { start: 521, end: 521, file: None },
         ]),
@@ -172,7 +172,7 @@ fn macro_with_simple_body() {
         statements: vec![
             Statement {
                 ty: StatementType::Literal(str_to_bytes32("00")),
-                span: AstSpan(vec![Span { start: 54, end: 55, file: None }]),
+                span: AstSpan(vec![Span { start: 52, end: 55, file: None }]),
             },
             Statement {
                 ty: StatementType::Opcode(Opcode::Mstore),
@@ -180,11 +180,11 @@ fn macro_with_simple_body() {
             },
             Statement {
                 ty: StatementType::Literal(str_to_bytes32("01")),
-                span: AstSpan(vec![Span { start: 67, end: 68, file: None }]),
+                span: AstSpan(vec![Span { start: 65, end: 68, file: None }]),
             },
             Statement {
                 ty: StatementType::Literal(str_to_bytes32("02")),
-                span: AstSpan(vec![Span { start: 72, end: 73, file: None }]),
+                span: AstSpan(vec![Span { start: 70, end: 73, file: None }]),
             },
             Statement {
                 ty: StatementType::Opcode(Opcode::Add),
@@ -209,10 +209,10 @@ fn macro_with_simple_body() {
             Span { start: 47, end: 47, file: None },
             Span { start: 48, end: 48, file: None },
             Span { start: 50, end: 50, file: None },
-            Span { start: 54, end: 55, file: None },
+            Span { start: 52, end: 55, file: None },
             Span { start: 57, end: 62, file: None },
-            Span { start: 67, end: 68, file: None },
-            Span { start: 72, end: 73, file: None },
+            Span { start: 65, end: 68, file: None },
+            Span { start: 70, end: 73, file: None },
             Span { start: 75, end: 77, file: None },
             Span { start: 79, end: 79, file: None },
         ]),
@@ -261,6 +261,7 @@ fn macro_with_arg_calls() {
             arg_type: None,
             name: Some("error".to_string()),
             indexed: false,
+            default: None,
             arg_location: None,
             span: AstSpan(vec![Span { start: 67, end: 71, file: None }]),
         }],
@@ -278,17 +279,18 @@ fn macro_with_arg_calls() {
                 ty: StatementType::MacroInvocation(MacroInvocation {
                     macro_name: "LOAD_ELEMENT_FROM_KEYS".to_string(),
                     args: vec![MacroArg::Literal(str_to_bytes32("00"))],
+                    arg_names: vec![],
                     span: AstSpan(vec![
                         Span { start: 283, end: 304, file: None },
                         Span { start: 305, end: 305, file: None },
-                        Span { start: 308, end: 309, file: None },
+                        Span { start: 306, end: 309, file: None },
                         Span { start: 310, end: 310, file: None },
                     ]),
                 }),
                 span: AstSpan(vec![
                     Span { start: 283, end: 304, file: None },
                     Span { start: 305, end: 305, file: None },
-                    Span { start: 308, end: 309, file: None },
+                    Span { start: 306, end: 309, file: None },
                     Span { start: 310, end: 310, file: None },
                 ]),
             },
@@ -336,17 +338,18 @@ fn macro_with_arg_calls() {
                 ty: StatementType::MacroInvocation(MacroInvocation {
                     macro_name: "STORE_ELEMENT_FROM_KEYS".to_string(),
                     args: vec![MacroArg::Literal(str_to_bytes32("00"))],
+                    arg_names: vec![],
                     span: AstSpan(vec![
                         Span { start: 1000, end: 1022, file: None },
                         Span { start: 1023, end: 1023, file: None },
-                        Span { start: 1026, end: 1027, file: None },
+                        Span { start: 1024, end: 1027, file: None },
                         Span { start: 1028, end: 1028, file: None },
                     ]),
                 }),
                 span: AstSpan(vec![
                     Span { start: 1000, end: 1022, file: None },
                     Span { start: 1023, end: 1023, file: None },
-                    Span { start: 1026, end: 1027, file: None },
+                    Span { start: 1024, end: 1027, file: None },
                     Span { start: 1028, end: 1028, file: None },
                 ]),
             },
@@ -376,7 +379,7 @@ fn macro_with_arg_calls() {
             Span { start: 281, end: 281, file: None },
             Span { start: 283, end: 304, file: None },
             Span { start: 305, end: 305, file: None },
-            Span { start: 308, end: 309, file: None },
+            Span { start: 306, end: 309, file: None },
             Span { start: 310, end: 310, file: None },
             Span { start: 351, end: 354, file: None },
             Span { start: 418, end: 421, file: None },
@@ -394,7 +397,7 @@ fn macro_with_arg_calls() {
             Span { start: 998, end: 998, file: None },
             Span { start: 1000, end: 1022, file: None },
             Span { start: 1023, end: 1023, file: None },
-            Span { start: 1026, end: 1027, file: None },
+            Span { start: 1024, end: 1027, file: None },
             Span { start: 1028, end: 1028, file: None },
             Span { start: 1055, end: 1055, file: None },
         ]),
@@ -440,6 +443,7 @@ fn macro_labels() {
                             ty: StatementType::MacroInvocation(MacroInvocation {
                                 macro_name: "TRANSFER_GIVE_TO".to_string(),
                                 args: vec![],
+                                arg_names: vec![],
                                 span: AstSpan(vec![
                                     Span { start: 89, end: 104, file: None },
                                     Span { start: 105, end: 105, file: None },
@@ -454,11 +458,11 @@ fn macro_labels() {
                         },
                         Statement {
                             ty: StatementType::Literal(str_to_bytes32("00")),
-                            span: AstSpan(vec![Span { start: 122, end: 123, file: None }]),
+                            span: AstSpan(vec![Span { start: 120, end: 123, file: None }]),
                         },
                         Statement {
                             ty: StatementType::Literal(str_to_bytes32("00")),
-                            span: AstSpan(vec![Span { start: 127, end: 128, file: None }]),
+                            span: AstSpan(vec![Span { start: 125, end: 128, file: None }]),
                         },
                         Statement {
                             ty: StatementType::Opcode(Opcode::Revert),
@@ -470,8 +474,8 @@ fn macro_labels() {
                         Span { start: 89, end: 104, file: None },
                         Span { start: 105, end: 105, file: None },
                         Span { start: 106, end: 106, file: None },
-                        Span { start: 122, end: 123, file: None },
-                        Span { start: 127, end: 128, file: None },
+                        Span { start: 120, end: 123, file: None },
+                        Span { start: 125, end: 128, file: None },
                         Span { start: 130, end: 135, file: None },
                     ]),
                 }),
@@ -480,8 +484,8 @@ fn macro_labels() {
                     Span { start: 89, end: 104, file: None },
                     Span { start: 105, end: 105, file: None },
                     Span { start: 106, end: 106, file: None },
-                    Span { start: 122, end: 123, file: None },
-                    Span { start: 127, end: 128, file: None },
+                    Span { start: 120, end: 123, file: None },
+                    Span { start: 125, end: 128, file: None },
                     Span { start: 130, end: 135, file: None },
                 ]),
             },
@@ -493,6 +497,7 @@ fn macro_labels() {
                             ty: StatementType::MacroInvocation(MacroInvocation {
                                 macro_name: "TRANSFER_GIVE_TO".to_string(),
                                 args: vec![],
+                                arg_names: vec![],
                                 span: AstSpan(vec![
                                     Span { start: 164, end: 179, file: None },
                                     Span { start: 180, end: 180, file: None },
@@ -507,11 +512,11 @@ fn macro_labels() {
                         },
                         Statement {
                             ty: StatementType::Literal(str_to_bytes32("00")),
-                            span: AstSpan(vec![Span { start: 197, end: 198, file: None }]),
+                            span: AstSpan(vec![Span { start: 195, end: 198, file: None }]),
                         },
                         Statement {
                             ty: StatementType::Literal(str_to_bytes32("00")),
-                            span: AstSpan(vec![Span { start: 202, end: 203, file: None }]),
+                            span: AstSpan(vec![Span { start: 200, end: 203, file: None }]),
                         },
                         Statement {
                             ty: StatementType::Opcode(Opcode::Revert),
@@ -523,8 +528,8 @@ fn macro_labels() {
                         Span { start: 164, end: 179, file: None },
                         Span { start: 180, end: 180, file: None },
                         Span { start: 181, end: 181, file: None },
-                        Span { start: 197, end: 198, file: None },
-                        Span { start: 202, end: 203, file: None },
+                        Span { start: 195, end: 198, file: None },
+                        Span { start: 200, end: 203, file: None },
                         Span { start: 205, end: 210, file: None },
                     ]),
                 }),
@@ -533,8 +538,8 @@ fn macro_labels() {
                     Span { start: 164, end: 179, file: None },
                     Span { start: 180, end: 180, file: None },
                     Span { start: 181, end: 181, file: None },
-                    Span { start: 197, end: 198, file: None },
-                    Span { start: 202, end: 203, file: None },
+                    Span { start: 195, end: 198, file: None },
+                    Span { start: 200, end: 203, file: None },
                     Span { start: 205, end: 210, file: None },
                 ]),
             },
@@ -562,16 +567,16 @@ fn macro_labels() {
             Span { start: 89, end: 104, file: None },
             Span { start: 105, end: 105, file: None },
             Span { start: 106, end: 106, file: None },
-            Span { start: 122, end: 123, file: None },
-            Span { start: 127, end: 128, file: None },
+            Span { start: 120, end: 123, file: None },
+            Span { start: 125, end: 128, file: None },
             Span { start: 130, end: 135, file: None },
             Span { start: 145, end: 149, file: None },
             Span { start: 150, end: 150, file: None },
             Span { start: 164, end: 179, file: None },
             Span { start: 180, end: 180, file: None },
             Span { start: 181, end: 181, file: None },
-            Span { start: 197, end: 198, file: None },
-            Span { start: 202, end: 203, file: None },
+            Span { start: 195, end: 198, file: None },
+            Span { start: 200, end: 203, file: None },
             Span { start: 205, end: 210, file: None },
             Span { start: 216, end: 216, file: None },
         ]),
@@ -609,6 +614,7 @@ fn macro_invocation_with_arg_call() {
             arg_type: None,
             name: Some("error".to_string()),
             indexed: false,
+            default: None,
             arg_location: None,
             span: AstSpan(vec![Span { start: 28, end: 32, file: None }]),
         }],
@@ -617,6 +623,7 @@ fn macro_invocation_with_arg_call() {
                 ty: StatementType::MacroInvocation(MacroInvocation {
                     macro_name: "TRANSFER_TAKE_FROM".to_string(),
                     args: vec![MacroArg::ArgCall("error".to_string())],
+                    arg_names: vec![],
                     span: AstSpan(vec![
                         Span { start: 67, end: 84, file: None },
                         Span { start: 85, end: 85, file: None },
@@ -639,6 +646,7 @@ fn macro_invocation_with_arg_call() {
                 ty: StatementType::MacroInvocation(MacroInvocation {
                     macro_name: "TRANSFER_GIVE_TO".to_string(),
                     args: vec![MacroArg::ArgCall("error".to_string())],
+                    arg_names: vec![],
                     span: AstSpan(vec![
                         Span { start: 103, end: 118, file: None },
                         Span { start: 119, end: 119, file: None },
@@ -659,11 +667,11 @@ fn macro_invocation_with_arg_call() {
             },
             Statement {
                 ty: StatementType::Literal(str_to_bytes32("01")),
-                span: AstSpan(vec![Span { start: 140, end: 141, file: None }]),
+                span: AstSpan(vec![Span { start: 138, end: 141, file: None }]),
             },
             Statement {
                 ty: StatementType::Literal(str_to_bytes32("00")),
-                span: AstSpan(vec![Span { start: 145, end: 146, file: None }]),
+                span: AstSpan(vec![Span { start: 143, end: 146, file: None }]),
             },
             Statement {
                 ty: StatementType::Opcode(Opcode::Mstore),
@@ -671,11 +679,11 @@ fn macro_invocation_with_arg_call() {
             },
             Statement {
                 ty: StatementType::Literal(str_to_bytes32("20")),
-                span: AstSpan(vec![Span { start: 165, end: 166, file: None }]),
+                span: AstSpan(vec![Span { start: 163, end: 166, file: None }]),
             },
             Statement {
                 ty: StatementType::Literal(str_to_bytes32("00")),
-                span: AstSpan(vec![Span { start: 170, end: 171, file: None }]),
+                span: AstSpan(vec![Span { start: 168, end: 171, file: None }]),
             },
             Statement {
                 ty: StatementType::Opcode(Opcode::Return),
@@ -713,11 +721,11 @@ fn macro_invocation_with_arg_call() {
             Span { start: 121, end: 125, file: None },
             Span { start: 126, end: 126, file: None },
             Span { start: 127, end: 127, file: None },
-            Span { start: 140, end: 141, file: None },
-            Span { start: 145, end: 146, file: None },
+            Span { start: 138, end: 141, file: None },
+            Span { start: 143, end: 146, file: None },
             Span { start: 148, end: 153, file: None },
-            Span { start: 165, end: 166, file: None },
-            Span { start: 170, end: 171, file: None },
+            Span { start: 163, end: 166, file: None },
+            Span { start: 168, end: 171, file: None },
             Span { start: 173, end: 178, file: None },
             Span { start: 184, end: 184, file: None },
         ]),
@@ -752,6 +760,7 @@ fn test_macro_opcode_arguments() {
             ty: StatementType::MacroInvocation(MacroInvocation {
                 macro_name: "RETURN1".to_string(),
                 args: vec![MacroArg::Ident("returndatasize".to_string())],
+                arg_names: vec![],
                 span: AstSpan(vec![
                     Span { start: 58, end: 64, file: None },
                     Span { start: 65, end: 65, file: None },
@@ -826,6 +835,7 @@ fn macro_with_builtin_fn_call() {
                     arg_type: None,
                     name: Some("TEST".to_string()),
                     indexed: false,
+                    default: None,
                     arg_location: None,
                     span: AstSpan(vec![Span { start: 77, end: 80, file: None }]),
                 }],
@@ -932,7 +942,7 @@ fn outlined_macro_with_simple_body() {
         statements: vec![
             Statement {
                 ty: StatementType::Literal(str_to_bytes32("00")),
-                span: AstSpan(vec![Span { start: 51, end: 52, file: None }]),
+                span: AstSpan(vec![Span { start: 49, end: 52, file: None }]),
             },
             Statement {
                 ty: StatementType::Opcode(Opcode::Mstore),
@@ -940,11 +950,11 @@ fn outlined_macro_with_simple_body() {
             },
             Statement {
                 ty: StatementType::Literal(str_to_bytes32("01")),
-                span: AstSpan(vec![Span { start: 64, end: 65, file: None }]),
+                span: AstSpan(vec![Span { start: 62, end: 65, file: None }]),
             },
             Statement {
                 ty: StatementType::Literal(str_to_bytes32("02")),
-                span: AstSpan(vec![Span { start: 69, end: 70, file: None }]),
+                span: AstSpan(vec![Span { start: 67, end: 70, file: None }]),
             },
             Statement {
                 ty: StatementType::Opcode(Opcode::Add),
@@ -969,10 +979,10 @@ fn outlined_macro_with_simple_body() {
             Span { start: 44, end: 44, file: None },
             Span { start: 45, end: 45, file: None },
             Span { start: 47, end: 47, file: None },
-            Span { start: 51, end: 52, file: None },
+            Span { start: 49, end: 52, file: None },
             Span { start: 54, end: 59, file: None },
-            Span { start: 64, end: 65, file: None },
-            Span { start: 69, end: 70, file: None },
+            Span { start: 62, end: 65, file: None },
+            Span { start: 67, end: 70, file: None },
             Span { start: 72, end: 74, file: None },
             Span { start: 76, end: 76, file: None },
         ]),
@@ -1046,14 +1056,14 @@ fn test_with_simple_body() {
                     0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
                     0, 0, 0, 0, 0, 0,
                 ]),
-                span: AstSpan(vec![Span { start: 53, end: 54, file: None }]),
+                span: AstSpan(vec![Span { start: 51, end: 54, file: None }]),
             },
             Statement {
                 ty: StatementType::Literal([
                     0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
                     0, 0, 0, 0, 0, 0,
                 ]),
-                span: AstSpan(vec![Span { start: 58, end: 59, file: None }]),
+                span: AstSpan(vec![Span { start: 56, end: 59, file: None }]),
             },
             Statement {
                 ty: StatementType::Opcode(Opcode::Mstore),
@@ -1064,14 +1074,14 @@ fn test_with_simple_body() {
                     0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
                     0, 0, 0, 0, 0, 1,
                 ]),
-                span: AstSpan(vec![Span { start: 71, end: 72, file: None }]),
+                span: AstSpan(vec![Span { start: 69, end: 72, file: None }]),
             },
             Statement {
                 ty: StatementType::Literal([
                     0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
                     0, 0, 0, 0, 0, 2,
                 ]),
-                span: AstSpan(vec![Span { start: 76, end: 77, file: None }]),
+                span: AstSpan(vec![Span { start: 74, end: 77, file: None }]),
             },
             Statement {
                 ty: StatementType::Opcode(Opcode::Add),
@@ -1096,11 +1106,11 @@ fn test_with_simple_body() {
             Span { start: 46, end: 46, file: None },
             Span { start: 47, end: 47, file: None },
             Span { start: 49, end: 49, file: None },
-            Span { start: 53, end: 54, file: None },
-            Span { start: 58, end: 59, file: None },
+            Span { start: 51, end: 54, file: None },
+            Span { start: 56, end: 59, file: None },
             Span { start: 61, end: 66, file: None },
-            Span { start: 71, end: 72, file: None },
-            Span { start: 76, end: 77, file: None },
+            Span { start: 69, end: 72, file: None },
+            Span { start: 74, end: 77, file: None },
             Span { start: 79, end: 81, file: None },
             Span { start: 83, end: 83, file: None },
         ]),
@@ -1141,7 +1151,7 @@ fn empty_test_with_decorator() {
             Span { start: 6, end: 6, file: None },
             Span { start: 7, end: 11, file: None },
             Span { start: 12, end: 12, file: None },
-            Span { start: 15, end: 16, file: None },
+            Span { start: 13, end: 16, file: None },
             Span { start: 17, end: 17, file: None },
             Span { start: 18, end: 18, file: None },
             Span { start: 24, end: 30, file: None },
@@ -1206,7 +1216,7 @@ fn empty_test_with_multi_flag_decorator() {
             Span { start: 23, end: 23, file: None },
             Span { start: 25, end: 29, file: None },
             Span { start: 30, end: 30, file: None },
-            Span { start: 33, end: 34, file: None },
+            Span { start: 31, end: 34, file: None },
             Span { start: 35, end: 35, file: None },
             Span { start: 36, end: 36, file: None },
             Span { start: 42, end: 48, file: None },
@@ -1233,6 +1243,25 @@ fn empty_test_with_multi_flag_decorator() {
     assert_eq!(parser.current_token.kind, TokenKind::Eof);
 }
 
+#[test]
+fn empty_macro_with_view_decorator() {
+    let source = r#"
+    #[view]
+    #define macro GETTER() = takes(0) returns(0) {}
+    "#;
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let lexer = Lexer::new(flattened_source.source);
+    let tokens = lexer.into_iter().map(|x| x.unwrap()).collect::<Vec<Token>>();
+    let mut parser = Parser::new(tokens, None);
+
+    // Grab the first macro
+    let macro_definition = parser.parse().unwrap().macros[0].clone();
+    assert_eq!(macro_definition.decorator, Some(Decorator { flags: vec![DecoratorFlag::View] }));
+    assert!(macro_definition.is_view());
+    assert!(!macro_definition.test);
+    assert_eq!(parser.current_token.kind, TokenKind::Eof);
+}
+
 #[test]
 fn test_duplicate_macro_error() {
     let source = r#"
@@ -1297,3 +1326,32 @@ fn test_duplicate_macro_error() {
         }
     }
 }
+
+#[test]
+fn test_region_markers_are_ignored() {
+    let parse_statements = |source: &str| {
+        let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+        let lexer = Lexer::new(flattened_source.source);
+        let tokens = lexer.into_iter().map(|x| x.unwrap()).collect::<Vec<Token>>();
+        let mut parser = Parser::new(tokens, None);
+        let contract = parser.parse().unwrap();
+        contract.macros[0].statements.iter().map(|s| s.ty.clone()).collect::<Vec<StatementType>>()
+    };
+
+    let with_regions = r#"
+    // region: main
+    #define macro MAIN() = takes(0) returns(0) {
+        // region: store
+        0x01 0x00 mstore
+        // endregion
+    }
+    // endregion: main
+    "#;
+    let without_regions = r#"
+    #define macro MAIN() = takes(0) returns(0) {
+        0x01 0x00 mstore
+    }
+    "#;
+
+    assert_eq!(parse_statements(with_regions), parse_statements(without_regions));
+}
@@ -99,7 +99,7 @@ fn test_push_literals() {
                 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
                 0, 0, 0, 16,
             ]),
-            span: Span { start: 54, end: 55, file: None },
+            span: Span { start: 52, end: 55, file: None },
         },
         Token { kind: TokenKind::Whitespace, span: Span { start: 56, end: 68, file: None } },
         Token {
@@ -112,7 +112,7 @@ fn test_push_literals() {
                 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
                 0, 0, 1, 8,
             ]),
-            span: Span { start: 78, end: 80, file: None },
+            span: Span { start: 76, end: 80, file: None },
         },
         Token { kind: TokenKind::Whitespace, span: Span { start: 81, end: 93, file: None } },
         Token {
@@ -125,7 +125,7 @@ fn test_push_literals() {
                 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
                 0, 0, 0, 16,
             ]),
-            span: Span { start: 102, end: 103, file: None },
+            span: Span { start: 100, end: 103, file: None },
         },
         Token { kind: TokenKind::Whitespace, span: Span { start: 104, end: 104, file: None } },
         Token {
@@ -133,7 +133,7 @@ fn test_push_literals() {
                 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
                 0, 0, 0, 16,
             ]),
-            span: Span { start: 107, end: 108, file: None },
+            span: Span { start: 105, end: 108, file: None },
         },
         Token { kind: TokenKind::Whitespace, span: Span { start: 109, end: 117, file: None } },
         Token { kind: TokenKind::CloseBrace, span: Span { start: 118, end: 118, file: None } },
@@ -190,7 +190,7 @@ fn test_push0() {
                 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
                 0, 0, 0, 16,
             ]),
-            span: Span { start: 54, end: 55, file: None },
+            span: Span { start: 52, end: 55, file: None },
         },
         Token { kind: TokenKind::Whitespace, span: Span { start: 56, end: 68, file: None } },
         Token {
@@ -203,7 +203,7 @@ fn test_push0() {
                 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
                 0, 0, 1, 8,
             ]),
-            span: Span { start: 78, end: 80, file: None },
+            span: Span { start: 76, end: 80, file: None },
         },
         Token { kind: TokenKind::Whitespace, span: Span { start: 81, end: 93, file: None } },
         Token {
@@ -216,7 +216,7 @@ fn test_push0() {
                 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
                 0, 0, 0, 16,
             ]),
-            span: Span { start: 102, end: 103, file: None },
+            span: Span { start: 100, end: 103, file: None },
         },
         Token { kind: TokenKind::Whitespace, span: Span { start: 104, end: 104, file: None } },
         Token {
@@ -224,7 +224,7 @@ fn test_push0() {
                 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
                 0, 0, 0, 16,
             ]),
-            span: Span { start: 107, end: 108, file: None },
+            span: Span { start: 105, end: 108, file: None },
         },
         Token { kind: TokenKind::Whitespace, span: Span { start: 109, end: 121, file: None } },
         Token {
@@ -58,7 +58,7 @@ fn derives_storage_pointers() {
                 Span { start: 120, end: 127, file: None },
                 Span { start: 129, end: 131, file: None },
                 Span { start: 133, end: 133, file: None },
-                Span { start: 137, end: 140, file: None }
+                Span { start: 135, end: 140, file: None }
             ])
         }
     );
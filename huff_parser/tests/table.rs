@@ -34,6 +34,7 @@ fn table_with_no_body() {
                     Span { start: kind_offset + 16, end: kind_offset + 16, file: None },
                     Span { start: kind_offset + 17, end: kind_offset + 17, file: None }
                 ]),
+                placement: TablePlacement::After,
             }
         );
         assert_eq!(parser.current_token.kind, TokenKind::Eof);
@@ -108,6 +109,7 @@ fn table_with_body() {
                     Span { start: kind_offset + 44, end: kind_offset + 55, file: None },
                     Span { start: kind_offset + 57, end: kind_offset + 57, file: None }
                 ]),
+                placement: TablePlacement::After,
             }
         );
         assert_eq!(parser.current_token.kind, TokenKind::Eof);
@@ -207,6 +207,7 @@ impl TestRunner {
                                     };
                                 }
                                 DecoratorFlag::Value(v) => value = U256::from(v),
+                                DecoratorFlag::View => {}
                             }
                         }
                     }
@@ -30,6 +30,8 @@
 //!     }],
 //!     events: vec![],
 //!     tables: vec![],
+//!     receive: false,
+//!     fallback: None,
 //! };
 //!
 //! // Create an ABI using that generate contract
@@ -37,13 +39,23 @@
 //! ```
 
 use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
 use std::{collections::BTreeMap, fmt};
 
-use crate::ast::{self, FunctionType};
+use crate::{
+    ast::{self, AstSpan, FunctionType},
+    bytes_util::hash_bytes,
+    error::{CodegenError, CodegenErrorKind},
+    types::PrimitiveEVMType,
+};
 
 /// #### Abi
 ///
 /// The ABI of the generated code.
+///
+/// Functions, events, and errors are keyed by name, so they are serialized sorted by name
+/// regardless of the order they are defined in the source. Overloaded definitions sharing a name
+/// are each keyed by their full signature instead, e.g. `transfer(address,uint256)`.
 #[derive(Default, Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
 pub struct Abi {
     /// The constructor
@@ -58,6 +70,9 @@ pub struct Abi {
     pub receive: bool,
     /// If the contract defines fallback logic
     pub fallback: bool,
+    /// If the fallback function is payable
+    #[serde(default)]
+    pub fallback_payable: bool,
 }
 
 impl Abi {
@@ -65,6 +80,95 @@ impl Abi {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Generates a TypeScript module exporting the ABI as an `as const` array.
+    ///
+    /// The array follows the standard JSON ABI format, so libraries like viem and wagmi can infer
+    /// the TypeScript types of function arguments and return values from it.
+    pub fn to_typescript(&self) -> String {
+        let mut entries: Vec<Value> = vec![];
+        if let Some(constructor) = &self.constructor {
+            entries.push(json!({
+                "type": "constructor",
+                "inputs": json_params(&constructor.inputs),
+                "stateMutability": "nonpayable",
+            }));
+        }
+        entries.extend(self.functions.values().map(|function| {
+            json!({
+                "type": "function",
+                "name": function.name,
+                "inputs": json_params(&function.inputs),
+                "outputs": json_params(&function.outputs),
+                "stateMutability": match function.state_mutability {
+                    FunctionType::View => "view",
+                    FunctionType::Payable => "payable",
+                    FunctionType::NonPayable => "nonpayable",
+                    FunctionType::Pure => "pure",
+                },
+            })
+        }));
+        entries.extend(self.events.values().map(|event| {
+            json!({
+                "type": "event",
+                "name": event.name,
+                "inputs": event
+                    .inputs
+                    .iter()
+                    .map(|input| {
+                        let mut param = json_param(&input.name, &input.kind);
+                        param["indexed"] = json!(input.indexed);
+                        param
+                    })
+                    .collect::<Vec<Value>>(),
+                "anonymous": event.anonymous,
+            })
+        }));
+        entries.extend(self.errors.values().map(|error| {
+            json!({ "type": "error", "name": error.name, "inputs": json_params(&error.inputs) })
+        }));
+        if self.receive {
+            entries.push(json!({ "type": "receive", "stateMutability": "payable" }));
+        }
+        if self.fallback {
+            entries.push(json!({
+                "type": "fallback",
+                "stateMutability": if self.fallback_payable { "payable" } else { "nonpayable" },
+            }));
+        }
+
+        format!(
+            "export const abi = {} as const;\n",
+            serde_json::to_string_pretty(&entries).unwrap_or_else(|_| "[]".to_string())
+        )
+    }
+}
+
+/// Formats function parameters as JSON ABI parameters
+fn json_params(params: &[FunctionParam]) -> Vec<Value> {
+    params.iter().map(|param| json_param(&param.name, &param.kind)).collect()
+}
+
+/// Formats a single JSON ABI parameter, expanding tuples into their components
+fn json_param(name: &str, kind: &FunctionParamType) -> Value {
+    let (inner, suffix) = match kind {
+        FunctionParamType::Array(inner, sizes) => (
+            inner.as_ref(),
+            sizes
+                .iter()
+                .map(|s| if *s == 0 { "[]".to_string() } else { format!("[{s}]") })
+                .collect::<String>(),
+        ),
+        _ => (kind, String::default()),
+    };
+    match inner {
+        FunctionParamType::Tuple(components) => json!({
+            "name": name,
+            "type": format!("tuple{suffix}"),
+            "components": components.iter().map(|c| json_param("", c)).collect::<Vec<Value>>(),
+        }),
+        _ => json!({ "name": name, "type": kind.to_string() }),
+    }
 }
 
 // Allows for simple ABI Generation by directly translating the AST
@@ -72,53 +176,30 @@ impl From<ast::Contract> for Abi {
     fn from(contract: ast::Contract) -> Self {
         // Try to get the constructor inputs from an overriden function
         // Otherwise, use the CONSTRUCTOR macro if one exists
-        let constructor = contract
-            .functions
-            .iter()
-            .filter(|m| m.name.to_lowercase() == "constructor")
-            .cloned()
-            .collect::<Vec<ast::FunctionDefinition>>()
-            .first()
-            .map(|func| Constructor {
-                inputs: func
-                    .inputs
-                    .iter()
-                    .map(|argument| FunctionParam {
-                        name: argument.name.clone().unwrap_or_default(),
-                        kind: argument.arg_type.clone().unwrap_or_default().into(),
-                        internal_type: None,
-                    })
-                    .collect(),
-            })
-            .or_else(|| {
-                contract
-                    .macros
-                    .iter()
-                    .filter(|m| m.name == "CONSTRUCTOR")
-                    .cloned()
-                    .collect::<Vec<ast::MacroDefinition>>()
-                    .first()
-                    .map(|func| Constructor {
-                        inputs: func
-                            .parameters
-                            .iter()
-                            .map(|argument| FunctionParam {
-                                name: argument.name.clone().unwrap_or_default(),
-                                kind: argument.name.clone().unwrap_or_default().into(),
-                                internal_type: None,
-                            })
-                            .collect(),
-                    })
-            });
-
-        // Instantiate functions and events
-        let mut functions = BTreeMap::new();
-        let mut events = BTreeMap::new();
-        let mut errors = BTreeMap::new();
+        let constructor = Constructor::declared(&contract).or_else(|| {
+            contract
+                .macros
+                .iter()
+                .filter(|m| m.name == "CONSTRUCTOR")
+                .cloned()
+                .collect::<Vec<ast::MacroDefinition>>()
+                .first()
+                .map(|func| Constructor {
+                    inputs: func
+                        .parameters
+                        .iter()
+                        .map(|argument| FunctionParam {
+                            name: argument.name.clone().unwrap_or_default(),
+                            kind: argument.name.clone().unwrap_or_default().into(),
+                            internal_type: None,
+                        })
+                        .collect(),
+                })
+        });
 
         // Translate contract functions
         // Excluding constructor
-        functions.extend(
+        let functions = key_definitions(
             contract
                 .functions
                 .iter()
@@ -151,49 +232,187 @@ impl From<ast::Contract> for Abi {
                         },
                     )
                 }),
+            Function::signature,
         );
 
         // Translate contract events
-        events.extend(contract.events.iter().map(|event| {
-            (
-                event.name.to_string(),
-                Event {
-                    name: event.name.to_string(),
-                    inputs: event
-                        .parameters
-                        .iter()
-                        .map(|argument| EventParam {
-                            name: argument.name.clone().unwrap_or_default(),
-                            kind: argument.arg_type.clone().unwrap_or_default().into(),
-                            indexed: argument.indexed,
-                        })
-                        .collect(),
-                    anonymous: false,
-                },
-            )
-        }));
+        let events = key_definitions(
+            contract.events.iter().map(|event| {
+                (
+                    event.name.to_string(),
+                    Event {
+                        name: event.name.to_string(),
+                        inputs: event
+                            .parameters
+                            .iter()
+                            .map(|argument| EventParam {
+                                name: argument.name.clone().unwrap_or_default(),
+                                kind: argument.arg_type.clone().unwrap_or_default().into(),
+                                indexed: argument.indexed,
+                            })
+                            .collect(),
+                        anonymous: false,
+                    },
+                )
+            }),
+            Event::signature,
+        );
 
         // Translate contract errors
-        errors.extend(contract.errors.iter().map(|error| {
-            (
-                error.name.to_string(),
-                Error {
-                    name: error.name.to_string(),
-                    inputs: error
-                        .parameters
-                        .iter()
-                        .map(|argument| FunctionParam {
-                            name: argument.name.clone().unwrap_or_default(),
-                            kind: argument.arg_type.clone().unwrap_or_default().into(),
-                            internal_type: None,
-                        })
-                        .collect(),
-                },
-            )
-        }));
+        let errors = key_definitions(
+            contract.errors.iter().map(|error| {
+                (
+                    error.name.to_string(),
+                    Error {
+                        name: error.name.to_string(),
+                        inputs: error
+                            .parameters
+                            .iter()
+                            .map(|argument| FunctionParam {
+                                name: argument.name.clone().unwrap_or_default(),
+                                kind: argument.arg_type.clone().unwrap_or_default().into(),
+                                internal_type: None,
+                            })
+                            .collect(),
+                    },
+                )
+            }),
+            Error::signature,
+        );
+
+        Self {
+            constructor,
+            functions,
+            events,
+            errors,
+            receive: contract.receive,
+            fallback: contract.fallback.is_some(),
+            fallback_payable: contract.fallback == Some(FunctionType::Payable),
+        }
+    }
+}
 
-        Self { constructor, functions, events, errors, receive: false, fallback: false }
+/// Keys ABI definitions by name, falling back to the full signature for overloaded names
+fn key_definitions<T>(
+    definitions: impl Iterator<Item = (String, T)>,
+    signature: impl Fn(&T) -> String,
+) -> BTreeMap<String, T> {
+    let definitions = definitions.collect::<Vec<(String, T)>>();
+    let mut name_counts: BTreeMap<String, usize> = BTreeMap::new();
+    definitions.iter().for_each(|(name, _)| *name_counts.entry(name.clone()).or_default() += 1);
+    definitions
+        .into_iter()
+        .map(
+            |(name, def)| if name_counts[&name] > 1 { (signature(&def), def) } else { (name, def) },
+        )
+        .collect()
+}
+
+/// Normalizes a function, event or error signature into its canonical form
+///
+/// Whitespace is removed and the `uint`, `int` and `byte` aliases are expanded, so
+/// `transfer(address, uint )` becomes `transfer(address,uint256)`. Tuple and array parameter
+/// types are normalized recursively.
+pub fn normalize_signature(sig: &str) -> Result<String, CodegenError> {
+    let invalid = || {
+        tracing::error!(target: "abi", "INVALID SIGNATURE: \"{}\"", sig);
+        CodegenError {
+            kind: CodegenErrorKind::InvalidSignature(sig.to_string()),
+            span: AstSpan(vec![]),
+            token: None,
+            call_stack: vec![],
+        }
+    };
+
+    let compact: String = sig.chars().filter(|c| !c.is_whitespace()).collect();
+    let (name, params) = compact.split_once('(').ok_or_else(invalid)?;
+    let valid_name = name.chars().next().is_some_and(|c| c.is_alphabetic() || c == '_') &&
+        name.chars().all(|c| c.is_alphanumeric() || c == '_');
+    if !valid_name {
+        return Err(invalid())
     }
+    let params = params.strip_suffix(')').and_then(normalize_params).ok_or_else(invalid)?;
+    Ok(format!("{name}({params})"))
+}
+
+/// Hashes a function, event or error signature with Keccak256 after normalizing it
+///
+/// Equivalent signatures hash the same, e.g. `Transfer(address, address, uint)` and
+/// `Transfer(address,address,uint256)`.
+pub fn keccak256_hash(signature: &str) -> Result<[u8; 32], CodegenError> {
+    let mut hash = [0u8; 32];
+    hash_bytes(&mut hash, &normalize_signature(signature)?);
+    Ok(hash)
+}
+
+/// Returns the 4 byte selector of a function or error signature after normalizing it
+pub fn keccak256_selector(signature: &str) -> Result<[u8; 4], CodegenError> {
+    let mut selector = [0u8; 4];
+    selector.copy_from_slice(&keccak256_hash(signature)?[..4]);
+    Ok(selector)
+}
+
+/// Normalizes a comma separated list of parameter types
+fn normalize_params(params: &str) -> Option<String> {
+    if params.is_empty() {
+        return Some(String::new())
+    }
+
+    // Split on the commas outside of tuples
+    let mut types = vec![];
+    let (mut depth, mut start) = (0usize, 0);
+    for (i, c) in params.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.checked_sub(1)?,
+            ',' if depth == 0 => {
+                types.push(normalize_type(&params[start..i])?);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    if depth != 0 {
+        return None
+    }
+    types.push(normalize_type(&params[start..])?);
+    Some(types.join(","))
+}
+
+/// Normalizes a single parameter type, including any array dimensions
+fn normalize_type(ty: &str) -> Option<String> {
+    let (base, mut dims) = match ty.strip_prefix('(') {
+        Some(tuple) => {
+            let close = tuple.rfind(')')?;
+            (format!("({})", normalize_params(&tuple[..close])?), &tuple[close + 1..])
+        }
+        None => {
+            let (base, dims) = ty.split_at(ty.find('[').unwrap_or(ty.len()));
+            let base = match base {
+                "uint" => "uint256",
+                "int" => "int256",
+                "byte" => "bytes1",
+                base => base,
+            };
+            PrimitiveEVMType::try_from(base.to_string()).ok()?;
+            (base.to_string(), dims)
+        }
+    };
+
+    let suffix = dims;
+    while !dims.is_empty() {
+        let (size, rest) = dims.strip_prefix('[')?.split_once(']')?;
+        if !size.chars().all(|c| c.is_ascii_digit()) {
+            return None
+        }
+        dims = rest;
+    }
+    Some(format!("{base}{suffix}"))
+}
+
+/// Formats a signature from a name and its parameter types
+fn format_signature<'a>(name: &str, kinds: impl Iterator<Item = &'a FunctionParamType>) -> String {
+    format!("{name}({})", kinds.map(|kind| kind.to_string()).collect::<Vec<String>>().join(","))
 }
 
 /// #### Function
@@ -213,6 +432,13 @@ pub struct Function {
     pub state_mutability: FunctionType,
 }
 
+impl Function {
+    /// Returns the function signature, e.g. `transfer(address,uint256)`
+    pub fn signature(&self) -> String {
+        format_signature(&self.name, self.inputs.iter().map(|input| &input.kind))
+    }
+}
+
 /// #### Event
 ///
 /// An Event definition.
@@ -226,6 +452,13 @@ pub struct Event {
     pub anonymous: bool,
 }
 
+impl Event {
+    /// Returns the event signature, e.g. `Transfer(address,address,uint256)`
+    pub fn signature(&self) -> String {
+        format_signature(&self.name, self.inputs.iter().map(|input| &input.kind))
+    }
+}
+
 /// #### EventParam
 ///
 /// Event parameters.
@@ -250,6 +483,13 @@ pub struct Error {
     pub inputs: Vec<FunctionParam>,
 }
 
+impl Error {
+    /// Returns the error signature, e.g. `Unauthorized(address)`
+    pub fn signature(&self) -> String {
+        format_signature(&self.name, self.inputs.iter().map(|input| &input.kind))
+    }
+}
+
 /// #### Constructor
 ///
 /// The contract constructor
@@ -259,6 +499,27 @@ pub struct Constructor {
     pub inputs: Vec<FunctionParam>,
 }
 
+impl Constructor {
+    /// The constructor declared with a `constructor` function definition, if any
+    ///
+    /// Unlike the constructor derived from the `CONSTRUCTOR` macro, its inputs are typed.
+    pub fn declared(contract: &ast::Contract) -> Option<Self> {
+        contract.functions.iter().find(|m| m.name.to_lowercase() == "constructor").map(|func| {
+            Constructor {
+                inputs: func
+                    .inputs
+                    .iter()
+                    .map(|argument| FunctionParam {
+                        name: argument.name.clone().unwrap_or_default(),
+                        kind: argument.arg_type.clone().unwrap_or_default().into(),
+                        internal_type: None,
+                    })
+                    .collect(),
+            }
+        })
+    }
+}
+
 /// #### FunctionParam
 ///
 /// A generic function parameter
@@ -6,7 +6,7 @@ use serde::{Deserialize, Serialize};
 use std::{fs, path::Path, sync::Arc};
 
 pub use crate::abi::Abi;
-use crate::prelude::FileSource;
+use crate::prelude::{FileSource, MacroMap, SourceMap};
 
 /// A Codegen Artifact
 #[derive(Default, Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
@@ -19,9 +19,22 @@ pub struct Artifact {
     pub runtime: String,
     /// The abi
     pub abi: Option<Abi>,
+    /// The source spans of the runtime bytecode instructions
+    #[serde(skip)]
+    pub source_map: Option<SourceMap>,
+    /// The starting offset and byte length of every macro in the runtime bytecode
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub macro_map: Option<MacroMap>,
 }
 
 impl Artifact {
+    /// Creates an artifact holding only runtime bytecode
+    ///
+    /// The deploy `bytecode` is left empty since no constructor or bootstrap code is generated.
+    pub fn from_runtime(main_bytecode: &str) -> Self {
+        Self { runtime: main_bytecode.to_string(), ..Default::default() }
+    }
+
     /// Exports an artifact to a json file
     pub fn export(&self, out: &str) -> std::result::Result<(), std::io::Error> {
         let serialized_artifact = serde_json::to_string_pretty(self)?;
@@ -1,3 +1,4 @@
+use ethers_core::types::U256;
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 use std::ops::Index;
@@ -5,13 +6,13 @@ use std::ops::Index;
 use crate::{
     bytecode::*,
     bytes_util::*,
-    error::CodegenError,
+    error::{CodegenError, CodegenErrorKind},
     evm::Opcode,
     evm_version::EVMVersion,
     prelude::{MacroArg::Ident, Span, TokenKind},
 };
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, BTreeSet},
     fmt::{Display, Formatter},
     path::PathBuf,
     sync::{Arc, Mutex},
@@ -49,12 +50,17 @@ impl AstSpan {
                 if start.eq(&0) && end.eq(&0) {
                     format!("{newline_s}-> {}:{start}\n   > 0|", fs.0)
                 } else {
+                    // Point at the line and column of the start if the source is loaded
+                    let location =
+                        fs.1.iter()
+                            .find_map(|sp| sp.file.as_ref()?.line_col(start))
+                            .map(|(line, col)| format!("{line}:{col}"))
+                            .unwrap_or_else(|| format!("{start}-{end}"));
                     format!(
-                        "{}-> {}:{}-{}{}",
+                        "{}-> {}:{}{}",
                         newline_s,
                         fs.0,
-                        start,
-                        end,
+                        location,
                         fs.1.iter()
                             .map(|sp| sp.source_seg())
                             .filter(|ss| !ss.is_empty())
@@ -116,6 +122,10 @@ pub struct Contract {
     pub events: Vec<EventDefinition>,
     /// Tables
     pub tables: Vec<TableDefinition>,
+    /// Whether a receive function is declared
+    pub receive: bool,
+    /// The state mutability of the declared fallback function, if any
+    pub fallback: Option<FunctionType>,
 }
 
 impl Contract {
@@ -139,7 +149,219 @@ impl Contract {
         }
     }
 
+    /// Links another separately parsed contract into this one
+    ///
+    /// Merges the macros, constants, tables and ABI definitions of both contracts. Macros and
+    /// tables may only be defined by one of the contracts. Constants and ABI definitions that are
+    /// declared identically in both contracts, such as a shared interface, are only kept once,
+    /// while conflicting declarations of the same name or selector are rejected.
+    pub fn link(mut self, other: Contract) -> Result<Contract, CodegenError> {
+        let collision = |name: &str, span: &AstSpan| {
+            tracing::error!(target: "ast", "CONFLICTING DEFINITIONS OF \"{}\" WHILE LINKING", name);
+            Err(CodegenError {
+                kind: CodegenErrorKind::LinkCollision(name.to_string()),
+                span: span.clone(),
+                token: None,
+                call_stack: vec![],
+            })
+        };
+        let arg_types = |args: &[Argument]| {
+            args.iter().map(|a| a.arg_type.clone()).collect::<Vec<Option<String>>>()
+        };
+
+        for m in other.macros {
+            if self.macros.iter().any(|existing| existing.name == m.name) {
+                return collision(&m.name, &m.span)
+            }
+            self.macros.push(m);
+        }
+
+        for t in other.tables {
+            if self.tables.iter().any(|existing| existing.name == t.name) {
+                return collision(&t.name, &t.span)
+            }
+            self.tables.push(t);
+        }
+
+        {
+            let mut constants = self.constants.lock().unwrap();
+            for c in other.constants.lock().unwrap().iter() {
+                match constants.iter().find(|existing| existing.name == c.name) {
+                    Some(existing) if existing.value != c.value => {
+                        return collision(&c.name, &c.span)
+                    }
+                    Some(_) => {}
+                    None => constants.push(c.clone()),
+                }
+            }
+        }
+
+        for f in other.functions {
+            match self.functions.iter().find(|existing| existing.signature == f.signature) {
+                Some(existing)
+                    if existing.name != f.name ||
+                        existing.fn_type != f.fn_type ||
+                        arg_types(&existing.outputs) != arg_types(&f.outputs) =>
+                {
+                    return collision(&f.name, &f.span)
+                }
+                Some(_) => {}
+                None => self.functions.push(f),
+            }
+        }
+
+        for e in other.events {
+            match self.events.iter().find(|existing| existing.hash == e.hash) {
+                Some(existing)
+                    if existing.parameters.iter().map(|p| p.indexed).collect::<Vec<bool>>() !=
+                        e.parameters.iter().map(|p| p.indexed).collect::<Vec<bool>>() =>
+                {
+                    return collision(&e.name, &e.span)
+                }
+                Some(_) => {}
+                None => self.events.push(e),
+            }
+        }
+
+        for e in other.errors {
+            match self.errors.iter().find(|existing| existing.selector == e.selector) {
+                Some(existing) if existing.name != e.name => return collision(&e.name, &e.span),
+                Some(_) => {}
+                None => self.errors.push(e),
+            }
+        }
+
+        match (&self.fallback, other.fallback) {
+            (Some(existing), Some(fallback)) if *existing != fallback => {
+                return collision("fallback", &AstSpan(vec![]))
+            }
+            (None, fallback) => self.fallback = fallback,
+            _ => {}
+        }
+        self.receive |= other.receive;
+
+        self.invocations.extend(other.invocations);
+        for import in other.imports {
+            if !self.imports.contains(&import) {
+                self.imports.push(import);
+            }
+        }
+
+        Ok(self)
+    }
+
+    /// Returns the literal constants that are used as explicit storage slots
+    ///
+    /// A literal constant is treated as a storage slot when it is pushed directly before an
+    /// `SLOAD` or `SSTORE`, e.g. `[IMPL_SLOT] sload`. Free storage pointers are never assigned to
+    /// these slots.
+    pub fn explicit_storage_slots(&self) -> Vec<(String, Literal)> {
+        let mut slot_names = BTreeSet::new();
+        for macro_def in self.macros.iter() {
+            collect_slot_constants(&macro_def.statements, &mut None, &mut slot_names);
+        }
+        self.constants
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|c| slot_names.contains(&c.name))
+            .filter_map(|c| match c.value {
+                ConstVal::Literal(l) => Some((c.name.clone(), l)),
+                ConstVal::FreeStoragePointer(_) => None,
+            })
+            .collect()
+    }
+
+    /// Checks that no two constants used as explicit storage slots hold the same slot
+    ///
+    /// Free storage pointers are assigned around explicit slots, so only explicit slots can
+    /// collide with each other, e.g. when files included into the same contract each pick the
+    /// same slot by hand.
+    pub fn check_storage_slots(&self) -> Result<(), CodegenError> {
+        let mut seen: BTreeMap<Literal, String> = BTreeMap::new();
+        for (name, slot) in self.explicit_storage_slots() {
+            if let Some(first) = seen.get(&slot) {
+                tracing::error!(target: "ast", "STORAGE SLOT COLLISION BETWEEN \"{}\" AND \"{}\"", first, name);
+                let span = self
+                    .constants
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .find(|c| c.name == name)
+                    .map(|c| c.span.clone())
+                    .unwrap_or_default();
+                return Err(CodegenError {
+                    kind: CodegenErrorKind::StorageSlotCollision(first.clone(), name),
+                    span,
+                    token: None,
+                    call_stack: vec![],
+                })
+            }
+            seen.insert(slot, name);
+        }
+        Ok(())
+    }
+
+    /// Checks that no macro, constant, table or function is defined more than once
+    ///
+    /// Lookups by name return the first definition, so a redefinition, e.g. in an included file,
+    /// would otherwise be silently ignored. Functions may be overloaded, so only functions with
+    /// the same selector are duplicates.
+    pub fn check_duplicate_definitions(&self) -> Result<(), CodegenError> {
+        match self.duplicate_definitions().into_iter().next() {
+            Some(e) => {
+                tracing::error!(target: "ast", "DUPLICATE DEFINITION: {:?}", e.kind);
+                Err(e)
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Lists an error for every macro, constant, table or function that is defined more than
+    /// once, in the order checked by `check_duplicate_definitions`
+    pub fn duplicate_definitions(&self) -> Vec<CodegenError> {
+        let joined = |spans: &[AstSpan]| AstSpan(spans.iter().flat_map(|s| s.0.clone()).collect());
+        let constants = self.constants.lock().unwrap();
+        let mut duplicates = vec![];
+        duplicates.extend(
+            find_duplicates(self.macros.iter().map(|m| (&m.name, &m.name, &m.span)))
+                .into_iter()
+                .map(|(name, spans)| {
+                    (joined(&spans), CodegenErrorKind::DuplicateMacroDefinition { name, spans })
+                }),
+        );
+        duplicates.extend(
+            find_duplicates(constants.iter().map(|c| (&c.name, &c.name, &c.span))).into_iter().map(
+                |(name, spans)| {
+                    (joined(&spans), CodegenErrorKind::DuplicateConstantDefinition { name, spans })
+                },
+            ),
+        );
+        duplicates.extend(
+            find_duplicates(self.tables.iter().map(|t| (&t.name, &t.name, &t.span)))
+                .into_iter()
+                .map(|(name, spans)| {
+                    (joined(&spans), CodegenErrorKind::DuplicateTableDefinition { name, spans })
+                }),
+        );
+        duplicates.extend(
+            find_duplicates(self.functions.iter().map(|f| (&f.signature, &f.name, &f.span)))
+                .into_iter()
+                .map(|(name, spans)| {
+                    (joined(&spans), CodegenErrorKind::DuplicateFunctionDefinition { name, spans })
+                }),
+        );
+
+        duplicates
+            .into_iter()
+            .map(|(span, kind)| CodegenError { kind, span, token: None, call_stack: vec![] })
+            .collect()
+    }
+
     /// Derives the FreeStoragePointers into their bytes32 representation
+    ///
+    /// Explicit storage slots keep their literal value and are skipped when assigning free
+    /// storage pointers.
     pub fn derive_storage_pointers(&mut self) {
         let mut storage_pointers: Vec<(String, [u8; 32])> = Vec::new();
         let mut last_assigned_free_pointer = 0;
@@ -205,8 +427,10 @@ impl Contract {
     ///       updated out `last_p` tracker value
     ///     - If it's a literal constant, we can set the constant value directly to the literal if
     ///       not already set
-    ///     - If it's a macro invocation, look for the macro definition and recurse into that macro
-    ///       definition using `recurse_ast_constants`
+    ///     - If it's a macro invocation, look for the macro definition and walk that macro
+    ///       definition before the remaining statements
+    ///     - Macros already on the recursion path, such as a macro passed to its own `__codesize`,
+    ///       are skipped
     pub fn recurse_ast_constants(
         &self,
         macro_def: &MacroDefinition,
@@ -214,14 +438,39 @@ impl Contract {
         last_p: &mut i32,
         checking_constructor: bool,
     ) {
-        let mut statements = macro_def.statements.clone();
+        // Macros are walked depth-first on an explicit stack of frames, each holding the macro's
+        // remaining statements and the invoked macros still to be walked, so that deeply nested
+        // macros don't exhaust the native stack
+        let mut frames = vec![(macro_def, macro_def.statements.clone(), 0, checking_constructor)];
+        let mut pending: Vec<Vec<(&MacroDefinition, bool)>> = vec![vec![]];
 
-        let mut i = 0;
         loop {
-            if i >= statements.len() {
-                break;
+            // Walk the macros found in the last statement before moving on to the next one
+            let next = pending.last_mut().and_then(|p| (!p.is_empty()).then(|| p.remove(0)));
+            if let Some((md, checking_constructor)) = next {
+                if frames.iter().any(|(def, ..)| def.name == md.name) {
+                    tracing::debug!(target: "ast", "MACRO \"{}\" ALREADY ON RECURSION PATH", md.name);
+                } else {
+                    frames.push((md, md.statements.clone(), 0, checking_constructor));
+                    pending.push(vec![]);
+                }
+                continue
+            }
+
+            let Some((macro_def, statements, i, checking_constructor)) = frames.last_mut() else {
+                break
+            };
+            let (macro_def, checking_constructor) = (*macro_def, *checking_constructor);
+            if *i >= statements.len() {
+                frames.pop();
+                pending.pop();
+                continue
             }
-            match &statements[i].clone().ty {
+            let statement = statements[*i].clone();
+            *i += 1;
+
+            let mut invoked = vec![];
+            match &statement.ty {
                 StatementType::Constant(const_name) => {
                     self.assign_free_storage_pointers(
                         const_name,
@@ -256,27 +505,8 @@ impl Contract {
                         );
                     }
 
-                    match self
-                        .macros
-                        .iter()
-                        .filter(|md| md.name.eq(&mi.macro_name))
-                        .collect::<Vec<&MacroDefinition>>()
-                        .first()
-                    {
-                        Some(&md) => {
-                            if md.name.eq("CONSTRUCTOR") {
-                                if !checking_constructor {
-                                    self.recurse_ast_constants(md, storage_pointers, last_p, true);
-                                }
-                            } else {
-                                self.recurse_ast_constants(
-                                    md,
-                                    storage_pointers,
-                                    last_p,
-                                    checking_constructor,
-                                );
-                            }
-                        }
+                    match self.macros.iter().find(|md| md.name.eq(&mi.macro_name)) {
+                        Some(md) => invoked.push(md),
                         None => {
                             tracing::warn!(target: "ast", "MACRO \"{}\" INVOKED BUT NOT FOUND IN AST!", mi.macro_name)
                         }
@@ -286,32 +516,8 @@ impl Contract {
                     tracing::debug!(target: "ast", "Deriving Storage Pointers: Found builtin function {:?}", bfc.kind);
                     for a in &bfc.args {
                         if let Some(name) = &a.name {
-                            match self
-                                .macros
-                                .iter()
-                                .filter(|md| md.name.eq(name))
-                                .collect::<Vec<&MacroDefinition>>()
-                                .first()
-                            {
-                                Some(&md) => {
-                                    if md.name.eq("CONSTRUCTOR") {
-                                        if !checking_constructor {
-                                            self.recurse_ast_constants(
-                                                md,
-                                                storage_pointers,
-                                                last_p,
-                                                true,
-                                            );
-                                        }
-                                    } else {
-                                        self.recurse_ast_constants(
-                                            md,
-                                            storage_pointers,
-                                            last_p,
-                                            checking_constructor,
-                                        );
-                                    }
-                                }
+                            match self.macros.iter().find(|md| md.name.eq(name)) {
+                                Some(md) => invoked.push(md),
                                 None => {
                                     tracing::warn!(target: "ast", "BUILTIN HAS ARG NAME \"{}\" BUT NOT FOUND IN AST!", name)
                                 }
@@ -321,19 +527,23 @@ impl Contract {
                 }
                 StatementType::Label(l) => {
                     for state in l.inner.iter().rev() {
-                        statements.insert(i + 1, state.clone());
+                        statements.insert(*i, state.clone());
                     }
                 }
                 _ => {}
             }
-            i += 1;
-        }
 
-        // Breadth-first
-        // if !macros_to_recurse.is_empty() {
-        //     let next_md = macros_to_recurse.remove(0);
-        //     self.recurse_ast_constants(next_md, storage_pointers, last_p, macros_to_recurse);
-        // }
+            // The constructor is only walked once, when not already checking it
+            if let Some(p) = pending.last_mut() {
+                p.extend(invoked.into_iter().filter_map(|md| {
+                    if md.name.eq("CONSTRUCTOR") {
+                        (!checking_constructor).then_some((md, true))
+                    } else {
+                        Some((md, checking_constructor))
+                    }
+                }));
+            }
+        }
     }
 
     fn assign_free_storage_pointers(
@@ -352,6 +562,8 @@ impl Contract {
             .is_none()
         {
             tracing::debug!(target: "ast", "No storage pointer already set for \"{}\"!", const_name);
+            let reserved_slots =
+                self.explicit_storage_slots().into_iter().map(|(_, slot)| slot).collect::<Vec<_>>();
             // Get the associated constant
             match self
                 .constants
@@ -366,6 +578,10 @@ impl Contract {
                     let new_value = match c.value {
                         ConstVal::Literal(l) => l,
                         ConstVal::FreeStoragePointer(_) => {
+                            // Skip slots that are explicitly assigned to other constants
+                            while reserved_slots.contains(&str_to_bytes32(&format!("{last_p}"))) {
+                                *last_p += 1;
+                            }
                             let old_p = *last_p;
                             *last_p += 1;
                             str_to_bytes32(&format!("{old_p}"))
@@ -402,6 +618,209 @@ impl Contract {
             }
         }
     }
+
+    /// Coalesces runs of memory clears in every macro body
+    ///
+    /// Two or more consecutive `0x00 <slot> mstore` sequences are rewritten so that the zero is
+    /// only pushed once and duplicated for each following store:
+    ///
+    /// `0x00 0x00 mstore 0x00 0x20 mstore` => `0x00 dup1 0x00 mstore 0x20 mstore`
+    ///
+    /// Every duplicated zero saves a byte at the same gas cost. The duplicated zero sits below the
+    /// slot of each store, so the run needs one more stack slot than the original: the example
+    /// above reaches a depth of 3 where the original peaks at 2. Since `PUSH0` is cheaper than
+    /// `DUP1`, zeros that are pushed with `PUSH0` are left untouched.
+    pub fn coalesce_memory_clears(&mut self, evm_version: &EVMVersion) {
+        for macro_def in self.macros.iter_mut() {
+            tracing::debug!(target: "ast", "COALESCING MEMORY CLEARS IN MACRO \"{}\"", macro_def.name);
+            macro_def.statements =
+                coalesce_memory_clears(evm_version, std::mem::take(&mut macro_def.statements));
+        }
+    }
+
+    /// Folds arithmetic on constant literals in every macro body
+    ///
+    /// Two literals followed by an `ADD`, `SUB`, `MUL`, `AND`, `OR` or `XOR` are replaced by a
+    /// single literal of the computed value, wrapping like the EVM does:
+    ///
+    /// `0x20 0x20 add` => `0x40`
+    ///
+    /// Folds are repeated, so `0x01 0x02 add 0x03 mul` becomes `0x09`. Literals with an explicit
+    /// push opcode keep their width and are never folded, and neither are sequences split by a
+    /// label.
+    pub fn fold_constants(&mut self) {
+        for macro_def in self.macros.iter_mut() {
+            tracing::debug!(target: "ast", "FOLDING CONSTANTS IN MACRO \"{}\"", macro_def.name);
+            macro_def.statements = fold_constants(std::mem::take(&mut macro_def.statements));
+        }
+    }
+}
+
+/// Returns the name and the spans of every definition of each key that is defined more than
+/// once, ordered by the position of the first redefinition
+fn find_duplicates<'a, K: Ord + 'a>(
+    definitions: impl Iterator<Item = (&'a K, &'a String, &'a AstSpan)>,
+) -> Vec<(String, Vec<AstSpan>)> {
+    let mut seen: BTreeMap<&K, (&String, Vec<AstSpan>)> = BTreeMap::new();
+    let mut duplicates = vec![];
+    for (key, name, span) in definitions {
+        let (first_name, spans) = seen.entry(key).or_insert((name, vec![]));
+        spans.push(span.clone());
+        if spans.len() == 2 {
+            duplicates.push((key, first_name.to_string()));
+        }
+    }
+    duplicates.into_iter().map(|(key, name)| (name, seen.remove(key).unwrap().1)).collect()
+}
+
+/// Collects the constants pushed directly before an `SLOAD` or `SSTORE`, walking label bodies in
+/// the order they are emitted
+fn collect_slot_constants(
+    statements: &[Statement],
+    previous: &mut Option<String>,
+    slot_names: &mut BTreeSet<String>,
+) {
+    for statement in statements {
+        match &statement.ty {
+            StatementType::Opcode(Opcode::Sload | Opcode::Sstore) => {
+                if let Some(name) = previous.take() {
+                    slot_names.insert(name);
+                }
+            }
+            StatementType::Constant(name) => *previous = Some(name.clone()),
+            StatementType::Label(label) => {
+                *previous = None;
+                collect_slot_constants(&label.inner, previous, slot_names);
+            }
+            _ => *previous = None,
+        }
+    }
+}
+
+/// Returns the number of statements and the value of the literal pushed at the start of
+/// `statements`, if any
+fn literal_push(statements: &[Statement]) -> Option<(usize, Literal)> {
+    match statements {
+        [Statement { ty: StatementType::Literal(l), .. }, ..] => Some((1, *l)),
+        [Statement { ty: StatementType::Opcode(o), .. }, Statement { ty: StatementType::Literal(l), .. }, ..]
+            if o.is_value_push() =>
+        {
+            Some((2, *l))
+        }
+        _ => None,
+    }
+}
+
+/// Returns the number of statements pushing the zero and the total number of statements in the
+/// memory clear at the start of `statements`, if any
+fn memory_clear_len(evm_version: &EVMVersion, statements: &[Statement]) -> Option<(usize, usize)> {
+    let (zero_len, zero) = literal_push(statements)?;
+    // A bare zero literal is pushed with PUSH0, which is already cheaper than DUP1
+    if zero != Literal::default() || (zero_len == 1 && evm_version.has_push0()) {
+        return None
+    }
+    let (slot_len, _) = literal_push(&statements[zero_len..])?;
+    match statements.get(zero_len + slot_len) {
+        Some(Statement { ty: StatementType::Opcode(Opcode::Mstore), .. }) => {
+            Some((zero_len, zero_len + slot_len + 1))
+        }
+        _ => None,
+    }
+}
+
+/// Coalesces runs of memory clears in a list of statements, recursing into labels
+fn coalesce_memory_clears(evm_version: &EVMVersion, statements: Vec<Statement>) -> Vec<Statement> {
+    let mut optimized = Vec::with_capacity(statements.len());
+    let mut i = 0;
+    while i < statements.len() {
+        // Collect the run of memory clears starting at this statement
+        let mut run = vec![];
+        let mut end = i;
+        while let Some((zero_len, len)) = memory_clear_len(evm_version, &statements[end..]) {
+            run.push((end, zero_len, len));
+            end += len;
+        }
+
+        if run.len() > 1 {
+            tracing::debug!(target: "ast", "COALESCING {} MEMORY CLEARS", run.len());
+            // Only the first zero is pushed, the rest are duplicated right before each store
+            let (first, zero_len, _) = run[0];
+            optimized.extend_from_slice(&statements[first..first + zero_len]);
+            for (n, (start, zero_len, len)) in run.iter().enumerate() {
+                if n != run.len() - 1 {
+                    optimized.push(Statement {
+                        ty: StatementType::Opcode(Opcode::Dup1),
+                        span: statements[*start].span.clone(),
+                    });
+                }
+                optimized.extend_from_slice(&statements[start + zero_len..start + len]);
+            }
+            i = end;
+            continue
+        }
+
+        // Keep push opcodes together with their literal
+        let len = literal_push(&statements[i..]).map(|(len, _)| len).unwrap_or(1);
+        for statement in &statements[i..i + len] {
+            let mut statement = statement.clone();
+            if let StatementType::Label(l) = &mut statement.ty {
+                l.inner = coalesce_memory_clears(evm_version, std::mem::take(&mut l.inner));
+            }
+            optimized.push(statement);
+        }
+        i += len;
+    }
+    optimized
+}
+
+/// Folds arithmetic on constant literals in a list of statements, recursing into labels
+fn fold_constants(statements: Vec<Statement>) -> Vec<Statement> {
+    let mut folded: Vec<Statement> = Vec::with_capacity(statements.len());
+    for mut statement in statements {
+        if let StatementType::Label(l) = &mut statement.ty {
+            l.inner = fold_constants(std::mem::take(&mut l.inner));
+        }
+
+        let operands = match (&statement.ty, &folded[..]) {
+            (
+                StatementType::Opcode(op),
+                [rest @ .., Statement { ty: StatementType::Literal(a), .. }, Statement { ty: StatementType::Literal(b), .. }],
+            ) if !rest.last().is_some_and(
+                |p| matches!(&p.ty, StatementType::Opcode(o) if o.is_value_push()),
+            ) =>
+            {
+                Some((*op, U256::from_big_endian(a), U256::from_big_endian(b)))
+            }
+            _ => None,
+        };
+
+        // `b` is on top of the stack, so it is the first operand
+        let value = operands.and_then(|(op, a, b)| match op {
+            Opcode::Add => Some(b.overflowing_add(a).0),
+            Opcode::Sub => Some(b.overflowing_sub(a).0),
+            Opcode::Mul => Some(b.overflowing_mul(a).0),
+            Opcode::And => Some(b & a),
+            Opcode::Or => Some(b | a),
+            Opcode::Xor => Some(b ^ a),
+            _ => None,
+        });
+
+        match value {
+            Some(value) => {
+                tracing::debug!(target: "ast", "FOLDED CONSTANT {:#x}", value);
+                let b = folded.pop().unwrap();
+                let a = folded.pop().unwrap();
+                let mut literal = Literal::default();
+                value.to_big_endian(&mut literal);
+                folded.push(Statement {
+                    ty: StatementType::Literal(literal),
+                    span: AstSpan([a.span.0, b.span.0, statement.span.0].concat()),
+                });
+            }
+            None => folded.push(statement),
+        }
+    }
+    folded
 }
 
 /// An argument's location
@@ -427,6 +846,8 @@ pub struct Argument {
     pub name: Option<String>,
     /// Is the argument indexed? TODO: should be valid for event arguments ONLY
     pub indexed: bool,
+    /// The value of a macro parameter when an invocation omits it
+    pub default: Option<MacroArg>,
     /// The argument span
     pub span: AstSpan,
 }
@@ -499,6 +920,8 @@ pub struct TableDefinition {
     pub size: Literal,
     /// The table span
     pub span: AstSpan,
+    /// Where the table is laid out relative to the code
+    pub placement: TablePlacement,
 }
 
 impl TableDefinition {
@@ -510,10 +933,20 @@ impl TableDefinition {
         size: Literal,
         span: AstSpan,
     ) -> Self {
-        TableDefinition { name, kind, statements, size, span }
+        TableDefinition { name, kind, statements, size, span, placement: TablePlacement::After }
     }
 }
 
+/// The placement of a table in the generated bytecode
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TablePlacement {
+    /// The table is laid out before the code, starting at offset 0
+    Before,
+    /// The table is appended after the code
+    #[default]
+    After,
+}
+
 /// A Table Kind
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub enum TableKind {
@@ -562,6 +995,7 @@ pub struct MacroDefinition {
 
 impl ToIRBytecode<CodegenError> for MacroDefinition {
     fn to_irbytecode(&self, evm_version: &EVMVersion) -> Result<IRBytecode, CodegenError> {
+        MacroDefinition::check_opcode_support(evm_version, &self.statements)?;
         let inner_irbytes: Vec<IRBytes> =
             MacroDefinition::to_irbytes(evm_version, &self.statements);
         Ok(IRBytecode(inner_irbytes))
@@ -595,6 +1029,40 @@ impl MacroDefinition {
         }
     }
 
+    /// Returns true if the macro is decorated with the `view` flag
+    pub fn is_view(&self) -> bool {
+        self.decorator.as_ref().is_some_and(|d| d.flags.contains(&DecoratorFlag::View))
+    }
+
+    /// Checks that every opcode in the statements, including those in labels, is available in the
+    /// targeted EVM version
+    pub fn check_opcode_support(
+        evm_version: &EVMVersion,
+        statements: &[Statement],
+    ) -> Result<(), CodegenError> {
+        for statement in statements {
+            match &statement.ty {
+                StatementType::Opcode(o) if !evm_version.supports_opcode(o) => {
+                    tracing::error!(target: "codegen", "UNSUPPORTED OPCODE {:?} FOR EVM VERSION {}", o, evm_version);
+                    return Err(CodegenError {
+                        kind: CodegenErrorKind::UnsupportedOpcode {
+                            opcode: *o,
+                            evm_version: evm_version.to_string(),
+                        },
+                        span: statement.span.clone(),
+                        token: None,
+                        call_stack: vec![],
+                    })
+                }
+                StatementType::Label(l) => {
+                    MacroDefinition::check_opcode_support(evm_version, &l.inner)?
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
     /// Translate statements into IRBytes
     pub fn to_irbytes<'a>(
         evm_version: &EVMVersion,
@@ -713,10 +1181,38 @@ pub struct MacroInvocation {
     pub macro_name: String,
     /// A list of Macro arguments
     pub args: Vec<MacroArg>,
+    /// The parameter name of each argument, empty if the arguments are positional
+    pub arg_names: Vec<String>,
     /// The Macro Invocation Span
     pub span: AstSpan,
 }
 
+impl MacroInvocation {
+    /// Gets the argument passed for a parameter of the invoked macro
+    ///
+    /// Named arguments are matched by parameter name, positional arguments by the position of
+    /// the parameter in the macro definition. An omitted argument falls back to the default
+    /// value of the parameter, if it declares one.
+    pub fn arg_for<'a>(
+        &'a self,
+        macro_def: &'a MacroDefinition,
+        param: &str,
+    ) -> Option<&'a MacroArg> {
+        let pos = if self.arg_names.is_empty() {
+            macro_def.parameters.iter().position(|p| p.name.as_deref() == Some(param))
+        } else {
+            self.arg_names.iter().position(|name| name == param)
+        };
+        pos.and_then(|pos| self.args.get(pos)).or_else(|| {
+            macro_def
+                .parameters
+                .iter()
+                .find(|p| p.name.as_deref() == Some(param))
+                .and_then(|p| p.default.as_ref())
+        })
+    }
+}
+
 /// An argument passed when invoking a maco
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub enum MacroArg {
@@ -726,6 +1222,8 @@ pub enum MacroArg {
     Ident(String),
     /// An Arg Call
     ArgCall(String),
+    /// A Builtin Function Call Argument
+    Builtin(BuiltinFunctionCall),
 }
 
 /// Free Storage Pointer Unit Struct
@@ -810,6 +1308,16 @@ pub enum BuiltinFunctionKind {
     DynConstructorArg,
     /// Inject Raw Bytes
     Verbatim,
+    /// Compile time shift left function
+    Shl,
+    /// Compile time shift right function
+    Shr,
+    /// Calldata ABI word load function
+    CalldataArg,
+    /// Explicit invalid opcode function
+    Invalid,
+    /// Code offset function
+    Codeoffset,
 }
 
 impl From<String> for BuiltinFunctionKind {
@@ -824,6 +1332,11 @@ impl From<String> for BuiltinFunctionKind {
             "__RIGHTPAD" => BuiltinFunctionKind::RightPad,
             "__CODECOPY_DYN_ARG" => BuiltinFunctionKind::DynConstructorArg,
             "__VERBATIM" => BuiltinFunctionKind::Verbatim,
+            "__SHL" => BuiltinFunctionKind::Shl,
+            "__SHR" => BuiltinFunctionKind::Shr,
+            "__CALLDATA_ARG" => BuiltinFunctionKind::CalldataArg,
+            "__INVALID" => BuiltinFunctionKind::Invalid,
+            "__codeoffset" => BuiltinFunctionKind::Codeoffset,
             _ => panic!("Invalid Builtin Function Kind"), /* This should never be reached,
                                                            * builtins are validated with a
                                                            * `try_from` call in the lexer. */
@@ -845,6 +1358,11 @@ impl TryFrom<&String> for BuiltinFunctionKind {
             "__RIGHTPAD" => Ok(BuiltinFunctionKind::RightPad),
             "__CODECOPY_DYN_ARG" => Ok(BuiltinFunctionKind::DynConstructorArg),
             "__VERBATIM" => Ok(BuiltinFunctionKind::Verbatim),
+            "__SHL" => Ok(BuiltinFunctionKind::Shl),
+            "__SHR" => Ok(BuiltinFunctionKind::Shr),
+            "__CALLDATA_ARG" => Ok(BuiltinFunctionKind::CalldataArg),
+            "__INVALID" => Ok(BuiltinFunctionKind::Invalid),
+            "__codeoffset" => Ok(BuiltinFunctionKind::Codeoffset),
             _ => Err(()),
         }
     }
@@ -904,9 +1422,8 @@ impl Display for StatementType {
 
 /// A decorator tag
 ///
-/// At the moment, the decorator tag can only be placed over test definitions. Developers
-/// can use decorators to define environment variables and other metadata for their individual
-/// tests.
+/// Developers can use decorators to define environment variables and other metadata for their
+/// individual tests, or to mark a macro as `view`.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Decorator {
     /// Vector of flags passed within the decorator
@@ -920,6 +1437,8 @@ pub enum DecoratorFlag {
     Calldata(String),
     /// Sets the value of the test call transaction
     Value(Literal),
+    /// Marks a macro as only reading state
+    View,
 }
 
 impl TryFrom<&String> for DecoratorFlag {
@@ -929,6 +1448,7 @@ impl TryFrom<&String> for DecoratorFlag {
         match value.as_str() {
             "calldata" => Ok(DecoratorFlag::Calldata(String::default())),
             "value" => Ok(DecoratorFlag::Value(Literal::default())),
+            "view" => Ok(DecoratorFlag::View),
             _ => Err(()),
         }
     }
@@ -7,7 +7,7 @@ use crate::{
     prelude::{AstSpan, Statement, TableDefinition},
 };
 use std::{
-    collections::{BTreeMap, BTreeSet},
+    collections::{BTreeMap, BTreeSet, HashMap},
     fmt::{self, Display},
 };
 
@@ -81,6 +81,15 @@ pub struct BytecodeRes {
     pub table_instances: Jumps,
     /// Utilized Tables
     pub utilized_tables: Vec<TableDefinition>,
+    /// Macro Start Offsets
+    pub macro_offsets: LabelIndices,
+    /// Macro Byte Lengths
+    pub macro_lengths: LabelIndices,
+    /// Code Offset Instances
+    pub codeoffset_instances: Jumps,
+    /// Source Spans and their enclosing macro by starting offset, inner spans following the
+    /// invocations they are part of
+    pub source_spans: Provenance,
 }
 
 impl Display for BytecodeRes {
@@ -123,3 +132,79 @@ pub type CircularCodeSizeIndices = BTreeSet<(String, usize)>;
 
 /// Type for a map of bytecode indexes to `Jumps`. Represents a Jump Table.
 pub type JumpTable = BTreeMap<usize, Jumps>;
+
+/// Type to map the offset of each instruction to the source span it was generated from
+pub type SourceMap = BTreeMap<usize, AstSpan>;
+
+/// Type to map macro names to their starting offset and byte length in the runtime bytecode
+pub type MacroMap = HashMap<String, (usize, usize)>;
+
+/// Type for a list of bytecode offsets annotated with the macro and source span they were
+/// generated from
+pub type Provenance = Vec<(usize, String, AstSpan)>;
+
+/// A single disassembled instruction
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Instruction {
+    /// The offset of the instruction in the bytecode
+    pub offset: usize,
+    /// The instruction mnemonic followed by its push data, if any. E.g. `PUSH1 0x01`
+    pub text: String,
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+/// A single line of an instruction level diff between two bytecodes
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum InstructionDiff {
+    /// An instruction present in both bytecodes, at its offset in the new bytecode
+    Unchanged(Instruction),
+    /// An instruction only present in the old bytecode
+    Removed(Instruction),
+    /// An instruction only present in the new bytecode
+    Added(Instruction),
+    /// An instruction of the old bytecode replaced by one of the new bytecode
+    Changed {
+        /// The old instruction
+        old: Instruction,
+        /// The new instruction
+        new: Instruction,
+    },
+}
+
+impl Display for InstructionDiff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InstructionDiff::Unchanged(i) => write!(f, "  {i} at offset {:#04x}", i.offset),
+            InstructionDiff::Removed(i) => write!(f, "- {i} at offset {:#04x}", i.offset),
+            InstructionDiff::Added(i) => write!(f, "+ {i} at offset {:#04x}", i.offset),
+            InstructionDiff::Changed { old, new } => {
+                write!(f, "~ {old} became {new} at offset {:#04x}", new.offset)
+            }
+        }
+    }
+}
+
+/// The first differing instruction of two bytecodes
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct BytecodeDiff {
+    /// The offset of the differing instruction, which is the same in both bytecodes
+    pub offset: usize,
+    /// The instruction of the old bytecode, `None` if the old bytecode ends at the offset
+    pub old: Option<Instruction>,
+    /// The instruction of the new bytecode, `None` if the new bytecode ends at the offset
+    pub new: Option<Instruction>,
+}
+
+impl Display for BytecodeDiff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let text = |i: &Option<Instruction>| {
+            i.as_ref().map_or_else(|| "end of bytecode".to_string(), |i| i.text.clone())
+        };
+        write!(f, "{} became {} at offset {:#04x}", text(&self.old), text(&self.new), self.offset)
+    }
+}
@@ -55,6 +55,26 @@ pub fn format_even_bytes(hex: String) -> String {
     }
 }
 
+/// Convert a string of decimal digits to its hex string, without a `0x` prefix
+///
+/// The size of the number is not capped, so callers need to check that it fits their type.
+pub fn decimal_to_hex(digits: &str) -> String {
+    // Big-endian bytes of the number parsed so far
+    let mut bytes: Vec<u8> = vec![0];
+    for digit in digits.chars().filter_map(|c| c.to_digit(10)) {
+        let mut carry = digit;
+        for byte in bytes.iter_mut().rev() {
+            let value = *byte as u32 * 10 + carry;
+            *byte = value as u8;
+            carry = value >> 8;
+        }
+        if carry > 0 {
+            bytes.insert(0, carry as u8);
+        }
+    }
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
 /// Convert string slice to Vec<u8>, size not capped
 pub fn str_to_vec(s: &str) -> Result<Vec<u8>, std::num::ParseIntError> {
     let bytes: Result<Vec<u8>, _> =
@@ -72,21 +92,17 @@ pub fn hash_bytes(dest: &mut [u8], to_hash: &String) {
 /// Converts a value literal to its smallest equivalent `PUSHX` bytecode
 pub fn literal_gen(evm_version: &EVMVersion, l: &[u8; 32]) -> String {
     let hex_literal: String = bytes32_to_string(l, false);
-    match hex_literal.as_str() {
-        "00" => format_push0(evm_version, hex_literal),
-        _ => format_literal(hex_literal),
-    }
-}
-
-fn format_push0(evm_version: &EVMVersion, hex_literal: String) -> String {
-    if evm_version.has_push0() {
-        Opcode::Push0.to_string()
-    } else {
-        format_literal(hex_literal)
+    if hex_literal == "00" && evm_version.has_push0() {
+        return Opcode::Push0.to_string()
     }
+    format_literal(hex_literal).expect("a word always fits a push")
 }
 
 /// Converts a literal into its bytecode string representation
-pub fn format_literal(hex_literal: String) -> String {
-    format!("{:02x}{hex_literal}", 95 + hex_literal.len() / 2)
+///
+/// Returns `None` if the literal is empty or wider than 32 bytes, as no push opcode takes it.
+pub fn format_literal(hex_literal: String) -> Option<String> {
+    let hex_literal = format_even_bytes(hex_literal);
+    let push = Opcode::push_for_bytes(hex_literal.len() / 2)?;
+    Some(format!("{push}{hex_literal}"))
 }
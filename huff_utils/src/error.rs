@@ -67,6 +67,10 @@ pub enum ParserErrorKind {
     InvalidDecoratorFlagArg(TokenKind),
     /// Duplicate MACRO
     DuplicateMacro(String),
+    /// Macro-local constant shadowing another constant
+    ShadowedConstant(String),
+    /// Macro invocation mixing named and positional arguments
+    MixedMacroArgs,
 }
 
 /// A Lexing Error
@@ -97,6 +101,30 @@ pub enum LexicalErrorKind {
     InvalidArraySize(String),
     /// Invalid Primitive EVM Type
     InvalidPrimitiveType(String),
+    /// Invalid Binary Literal
+    /// Expected at least one and at most 256 binary digits
+    InvalidBinaryLiteral(String),
+    /// Misplaced Underscore
+    /// Underscores in number literals are only allowed between two digits
+    MisplacedUnderscore(String),
+    /// Invalid Escape Sequence
+    /// Expected `\xNN` with two hex digits
+    InvalidEscapeSequence(String),
+    /// Literal Too Large
+    /// Literals can be at most 32 bytes wide, holds the number of bytes written
+    LiteralTooLarge(usize),
+    /// Negative Literal Too Small
+    /// Negative literals can be at least -2^255, the smallest 256-bit signed value
+    NegativeLiteralTooSmall(String),
+    /// Missing Conditional Name
+    /// An `#ifdef` directive must be followed by the name it checks
+    MissingConditionalName,
+    /// Unmatched Directive
+    /// An `#else` or `#endif` directive without an open `#ifdef`, or a second `#else`
+    UnmatchedDirective(String),
+    /// Unterminated Conditional
+    /// An `#ifdef` directive without a closing `#endif`, holds the name it checks
+    UnterminatedConditional(String),
 }
 
 impl Spanned for LexicalError {
@@ -116,6 +144,28 @@ impl<W: Write> Report<W> for LexicalError {
             LexicalErrorKind::InvalidPrimitiveType(str) => {
                 write!(f.out, "Invalid Primitive EVM Type '{str}'")
             }
+            LexicalErrorKind::InvalidBinaryLiteral(str) => {
+                write!(f.out, "Invalid binary literal '{str}'")
+            }
+            LexicalErrorKind::MisplacedUnderscore(str) => {
+                write!(f.out, "Misplaced underscore in literal '{str}'")
+            }
+            LexicalErrorKind::InvalidEscapeSequence(str) => {
+                write!(f.out, "Invalid escape sequence '{str}'")
+            }
+            LexicalErrorKind::LiteralTooLarge(len) => {
+                write!(f.out, "Literal is {len} bytes wide, at most 32 bytes are allowed")
+            }
+            LexicalErrorKind::NegativeLiteralTooSmall(str) => {
+                write!(f.out, "Negative literal '{str}' is smaller than -2^255")
+            }
+            LexicalErrorKind::MissingConditionalName => {
+                write!(f.out, "Missing name after '#ifdef'")
+            }
+            LexicalErrorKind::UnmatchedDirective(d) => write!(f.out, "Unmatched '{d}' directive"),
+            LexicalErrorKind::UnterminatedConditional(name) => {
+                write!(f.out, "Missing '#endif' for '#ifdef {name}'")
+            }
         }
     }
 }
@@ -129,12 +179,21 @@ pub struct CodegenError {
     pub span: AstSpan,
     /// An Optional Token Kind
     pub token: Option<TokenKind>,
+    /// The macros being generated when the error occured, outermost first, with the span each
+    /// was entered from. Empty if the error didn't occur while generating a macro.
+    pub call_stack: Vec<(String, AstSpan)>,
 }
 
 impl CodegenError {
     /// Public associated function to instatiate a new CodegenError.
     pub fn new(kind: CodegenErrorKind, spans: AstSpan, token: Option<TokenKind>) -> Self {
-        Self { kind, span: spans, token }
+        Self { kind, span: spans, token, call_stack: vec![] }
+    }
+
+    /// Prepends the macros the error occured within to its call stack
+    pub fn within(mut self, call_stack: impl IntoIterator<Item = (String, AstSpan)>) -> Self {
+        self.call_stack = call_stack.into_iter().chain(self.call_stack).collect();
+        self
     }
 }
 
@@ -157,6 +216,8 @@ pub enum CodegenErrorKind {
     MissingConstantDefinition(String),
     /// Missing Error Definition
     MissingErrorDefinition(String),
+    /// Missing Table Definition
+    MissingTableDefinition(String),
     /// Abi Generation Failure
     AbiGenerationFailure,
     /// Unmatched Jump
@@ -183,6 +244,200 @@ pub enum CodegenErrorKind {
     TestInvocation(String),
     /// Incorrect dynamic argument index
     InvalidDynArgIndex,
+    /// Push data runs past the end of the bytecode
+    TruncatedPushData(usize),
+    /// A warning raised while warnings are denied
+    DeniedWarning(CodegenWarningKind),
+    /// A view macro that changes state
+    StateChangeInView(String, Opcode),
+    /// A macro with parameters compiled as an entry point
+    ParameterizedEntryPoint(String),
+    /// A chain of `__codesize` calls that depends on its own size
+    CircularCodesize(Vec<String>),
+    /// A function without a dispatch handler label
+    MissingDispatchHandler(String),
+    /// A function sharing its name, and so its dispatch handler label, with another function
+    OverloadedDispatchFunction(String),
+    /// Conflicting definitions of the same name in linked contracts
+    LinkCollision(String),
+    /// A malformed function, event or error signature
+    InvalidSignature(String),
+    /// An `INVALID` opcode not emitted through the `__INVALID()` builtin
+    IncidentalInvalid(String),
+    /// A table whose size doesn't fit in a usize
+    InvalidTableSize(String),
+    /// A macro passed to `__codeoffset` that isn't placed in the bytecode
+    MissingCodeOffset(String),
+    /// A macro passed to `__codeoffset` placed past the 2 bytes its offset is pushed with
+    CodeOffsetOverflow(String, usize),
+    /// A chain of macro invocations that invokes itself
+    RecursiveMacroInvocation(Vec<String>),
+    /// A code size that doesn't fit in the 2 byte size pushed by the bootstrap code
+    BootstrapSizeOverflow(usize),
+    /// A constructor argument that doesn't match its declared type
+    InvalidConstructorArgument {
+        /// The position of the argument
+        index: usize,
+        /// The declared type
+        expected: String,
+        /// The provided value
+        got: String,
+    },
+    /// Failed to serialize an artifact to json
+    SerdeJson(String),
+    /// A macro body whose net stack effect doesn't match its declared `takes` and `returns`
+    StackImbalance {
+        /// The name of the macro
+        macro_name: String,
+        /// The declared number of returned stack items
+        expected: usize,
+        /// The computed stack height, negative if the body pops more items than it takes
+        got: isize,
+    },
+    /// A byte that doesn't correspond to a known opcode, with its offset
+    UnknownOpcode(usize, u8),
+    /// An opcode that isn't available in the targeted EVM version
+    UnsupportedOpcode {
+        /// The unsupported opcode
+        opcode: Opcode,
+        /// The targeted EVM version
+        evm_version: String,
+    },
+    /// Two constants used as explicit storage slots that hold the same slot
+    StorageSlotCollision(String, String),
+    /// An entry point macro that doesn't declare `takes(0) returns(0)`
+    InvalidEntryMacroSignature {
+        /// The name of the macro
+        macro_name: String,
+        /// The declared number of taken stack items
+        takes: usize,
+        /// The declared number of returned stack items
+        returns: usize,
+    },
+    /// A macro parameter that is never referenced in the macro body
+    UnusedMacroParameter {
+        /// The name of the macro
+        macro_name: String,
+        /// The name of the parameter
+        param: String,
+    },
+    /// A macro invocation that omits an argument for a parameter without a default
+    MissingMacroArgument {
+        /// The name of the macro
+        macro_name: String,
+        /// The name of the parameter
+        param: String,
+    },
+    /// A macro defined more than once
+    DuplicateMacroDefinition {
+        /// The name of the macro
+        name: String,
+        /// The spans of every definition
+        spans: Vec<AstSpan>,
+    },
+    /// A jump target known at compile time that isn't a `JUMPDEST`
+    InvalidJumpDestination {
+        /// The offset jumped to
+        offset: usize,
+    },
+    /// A constant defined more than once
+    DuplicateConstantDefinition {
+        /// The name of the constant
+        name: String,
+        /// The spans of every definition
+        spans: Vec<AstSpan>,
+    },
+    /// A table defined more than once
+    DuplicateTableDefinition {
+        /// The name of the table
+        name: String,
+        /// The spans of every definition
+        spans: Vec<AstSpan>,
+    },
+    /// A function with the same selector defined more than once
+    DuplicateFunctionDefinition {
+        /// The name of the function
+        name: String,
+        /// The spans of every definition
+        spans: Vec<AstSpan>,
+    },
+}
+
+/// A Code Generation Warning
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CodegenWarning {
+    /// The kind of code generation warning
+    pub kind: CodegenWarningKind,
+    /// The Span where the warning occured
+    pub span: AstSpan,
+}
+
+/// The Code Generation Warning Kind
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum CodegenWarningKind {
+    /// A macro that is never invoked
+    UnusedMacro(String),
+    /// A constant that is never referenced
+    UnusedConstant(String),
+    /// Runtime bytecode larger than the contract size limit
+    ContractSizeExceeded(usize, usize),
+    /// A jump to a computed destination
+    ComputedJump(String),
+    /// A label that can neither be jumped to nor fallen through to
+    UnreachableLabel(String),
+    /// A hardcoded address used as a call target
+    CallTarget(String),
+    /// A DUP or SWAP executed while items lie beyond its reach, with the macro name and the
+    /// tracked stack height
+    StackTooDeep(String, usize),
+    /// A macro with the same body as an earlier macro, with the earlier and the duplicate name
+    DuplicateMacroBody(String, String),
+    /// A macro whose stack balance can't be verified, e.g. because it contains labels or jumps
+    UnverifiedStack(String),
+    /// A table without statements passed to `__tablesize` or `__tablestart`
+    EmptyTable(String),
+    /// A label that is never jumped to or otherwise referenced
+    UnreferencedLabel(String),
+}
+
+impl fmt::Display for CodegenWarningKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CodegenWarningKind::UnusedMacro(name) => write!(f, "Unused macro \"{name}\""),
+            CodegenWarningKind::UnusedConstant(name) => write!(f, "Unused constant \"{name}\""),
+            CodegenWarningKind::ContractSizeExceeded(size, limit) => write!(
+                f,
+                "Runtime bytecode size of {size} bytes exceeds the {limit} byte contract size limit by {} bytes",
+                size.saturating_sub(*limit)
+            ),
+            CodegenWarningKind::ComputedJump(name) => {
+                write!(f, "Jump to a computed destination in macro \"{name}\"")
+            }
+            CodegenWarningKind::UnreachableLabel(name) => {
+                write!(f, "Unreachable label \"{name}\" follows a terminating opcode")
+            }
+            CodegenWarningKind::CallTarget(address) => {
+                write!(f, "Hardcoded address {address} used as a call target")
+            }
+            CodegenWarningKind::StackTooDeep(name, height) => write!(
+                f,
+                "Stack height of {height} in macro \"{name}\" leaves items beyond the reach of DUP16 and SWAP16"
+            ),
+            CodegenWarningKind::DuplicateMacroBody(original, duplicate) => write!(
+                f,
+                "Macro \"{duplicate}\" has the same body as macro \"{original}\" and could be consolidated"
+            ),
+            CodegenWarningKind::UnverifiedStack(name) => {
+                write!(f, "Stack balance of macro \"{name}\" can't be verified")
+            }
+            CodegenWarningKind::EmptyTable(name) => {
+                write!(f, "Table \"{name}\" is empty, so its size and start are meaningless")
+            }
+            CodegenWarningKind::UnreferencedLabel(name) => {
+                write!(f, "Label \"{name}\" is never referenced")
+            }
+        }
+    }
 }
 
 impl Spanned for CodegenError {
@@ -219,6 +474,9 @@ impl<W: Write> Report<W> for CodegenError {
             CodegenErrorKind::MissingErrorDefinition(ed) => {
                 write!(f.out, "Missing Error Definition for \"{ed}\"!")
             }
+            CodegenErrorKind::MissingTableDefinition(td) => {
+                write!(f.out, "Missing Table Definition for \"{td}\"!")
+            }
             CodegenErrorKind::AbiGenerationFailure => write!(f.out, "Abi generation failure!"),
             CodegenErrorKind::UnmatchedJumpLabel => write!(f.out, "Unmatched jump label!"),
             CodegenErrorKind::IOError(ioe) => write!(f.out, "IO ERROR: {ioe:?}"),
@@ -247,6 +505,101 @@ impl<W: Write> Report<W> for CodegenError {
             CodegenErrorKind::InvalidDynArgIndex => {
                 write!(f.out, "Invalid Dynamic Constructor Argument Index")
             }
+            CodegenErrorKind::TruncatedPushData(pc) => {
+                write!(f.out, "Truncated push data at pc: {pc}")
+            }
+            CodegenErrorKind::DeniedWarning(w) => {
+                write!(f.out, "Denied warning: {w}")
+            }
+            CodegenErrorKind::StateChangeInView(name, op) => {
+                write!(f.out, "View macro \"{name}\" changes state with opcode: {}", op.to_mnemonic())
+            }
+            CodegenErrorKind::ParameterizedEntryPoint(name) => {
+                write!(
+                    f.out,
+                    "Entry point macro \"{name}\" may not take parameters, invoke it from a wrapper macro without parameters instead"
+                )
+            }
+            CodegenErrorKind::CircularCodesize(cycle) => {
+                write!(f.out, "Circular __codesize reference: {}", cycle.join(" -> "))
+            }
+            CodegenErrorKind::MissingDispatchHandler(name) => {
+                write!(f.out, "Missing dispatch handler label \"{name}\" for function \"{name}\"")
+            }
+            CodegenErrorKind::OverloadedDispatchFunction(name) => {
+                write!(f.out, "Overloaded function \"{name}\" can't share a dispatch handler label")
+            }
+            CodegenErrorKind::LinkCollision(name) => {
+                write!(f.out, "Conflicting definitions of \"{name}\" in linked contracts")
+            }
+            CodegenErrorKind::InvalidSignature(sig) => {
+                write!(f.out, "Invalid signature: \"{sig}\"")
+            }
+            CodegenErrorKind::IncidentalInvalid(name) => {
+                write!(f.out, "INVALID opcode in macro \"{name}\" not emitted through __INVALID()")
+            }
+            CodegenErrorKind::InvalidTableSize(name) => {
+                write!(f.out, "Invalid size for table \"{name}\"")
+            }
+            CodegenErrorKind::MissingCodeOffset(name) => {
+                write!(f.out, "Macro \"{name}\" passed to __codeoffset is not in the bytecode")
+            }
+            CodegenErrorKind::CodeOffsetOverflow(name, offset) => {
+                write!(
+                    f.out,
+                    "Offset {offset:#x} of macro \"{name}\" passed to __codeoffset doesn't fit in 2 bytes"
+                )
+            }
+            CodegenErrorKind::RecursiveMacroInvocation(cycle) => {
+                write!(f.out, "Recursive macro invocation: {}", cycle.join(" -> "))
+            }
+            CodegenErrorKind::BootstrapSizeOverflow(size) => {
+                write!(f.out, "Code size of {size} bytes is too large for the bootstrap code")
+            }
+            CodegenErrorKind::InvalidConstructorArgument { index, expected, got } => {
+                write!(f.out, "Constructor argument {index} \"{got}\" is not a valid {expected}")
+            }
+            CodegenErrorKind::SerdeJson(e) => write!(f.out, "JSON SERIALIZATION ERROR: {e}"),
+            CodegenErrorKind::StackImbalance { macro_name, expected, got } => write!(
+                f.out,
+                "Macro \"{macro_name}\" declares {expected} returned stack items but leaves {got}"
+            ),
+            CodegenErrorKind::UnknownOpcode(pc, byte) => {
+                write!(f.out, "Unknown opcode {byte:#04x} at pc: {pc}")
+            }
+            CodegenErrorKind::UnsupportedOpcode { opcode, evm_version } => write!(
+                f.out,
+                "Opcode {} is not supported by the {evm_version} EVM version",
+                opcode.to_mnemonic()
+            ),
+            CodegenErrorKind::StorageSlotCollision(first, second) => {
+                write!(f.out, "Storage slot constants \"{first}\" and \"{second}\" hold the same slot")
+            }
+            CodegenErrorKind::InvalidEntryMacroSignature { macro_name, takes, returns } => write!(
+                f.out,
+                "Entry point macro \"{macro_name}\" declares takes({takes}) returns({returns}), expected takes(0) returns(0)"
+            ),
+            CodegenErrorKind::UnusedMacroParameter { macro_name, param } => {
+                write!(f.out, "Parameter \"{param}\" of macro \"{macro_name}\" is never used")
+            }
+            CodegenErrorKind::MissingMacroArgument { macro_name, param } => {
+                write!(f.out, "Invocation of macro \"{macro_name}\" is missing argument \"{param}\"")
+            }
+            CodegenErrorKind::DuplicateMacroDefinition { name, spans } => {
+                write!(f.out, "Macro \"{name}\" is defined {} times", spans.len())
+            }
+            CodegenErrorKind::InvalidJumpDestination { offset } => {
+                write!(f.out, "Jump target 0x{offset:02x} is not a JUMPDEST")
+            }
+            CodegenErrorKind::DuplicateConstantDefinition { name, spans } => {
+                write!(f.out, "Constant \"{name}\" is defined {} times", spans.len())
+            }
+            CodegenErrorKind::DuplicateTableDefinition { name, spans } => {
+                write!(f.out, "Table \"{name}\" is defined {} times", spans.len())
+            }
+            CodegenErrorKind::DuplicateFunctionDefinition { name, spans } => {
+                write!(f.out, "Function \"{name}\" is defined {} times", spans.len())
+            }
         }
     }
 }
@@ -266,6 +619,8 @@ pub enum CompilerError {
     CodegenError(CodegenError),
     /// Multiple Failed Compiles
     FailedCompiles(Vec<CompilerError>),
+    /// Files that include each other, starting and ending with the same file
+    CircularInclude(Vec<String>),
 }
 
 impl fmt::Display for CompilerError {
@@ -307,6 +662,77 @@ impl fmt::Display for CompilerError {
                         le.span.source_seg()
                     )
                 }
+                LexicalErrorKind::InvalidBinaryLiteral(b) => {
+                    write!(
+                        f,
+                        "\nError: Invalid Binary Literal: \"{}\" {}{}\n",
+                        b,
+                        le.span.identifier(),
+                        le.span.source_seg()
+                    )
+                }
+                LexicalErrorKind::MisplacedUnderscore(l) => {
+                    write!(
+                        f,
+                        "\nError: Misplaced Underscore In Literal: \"{}\" {}{}\n",
+                        l,
+                        le.span.identifier(),
+                        le.span.source_seg()
+                    )
+                }
+                LexicalErrorKind::InvalidEscapeSequence(s) => {
+                    write!(
+                        f,
+                        "\nError: Invalid Escape Sequence: \"{}\" {}{}\n",
+                        s,
+                        le.span.identifier(),
+                        le.span.source_seg()
+                    )
+                }
+                LexicalErrorKind::LiteralTooLarge(len) => {
+                    write!(
+                        f,
+                        "\nError: Literal Too Large: {} bytes, at most 32 allowed {}{}\n",
+                        len,
+                        le.span.identifier(),
+                        le.span.source_seg()
+                    )
+                }
+                LexicalErrorKind::NegativeLiteralTooSmall(l) => {
+                    write!(
+                        f,
+                        "\nError: Negative Literal Too Small: \"{}\" {}{}\n",
+                        l,
+                        le.span.identifier(),
+                        le.span.source_seg()
+                    )
+                }
+                LexicalErrorKind::MissingConditionalName => {
+                    write!(
+                        f,
+                        "\nError: Missing Conditional Name {}{}\n",
+                        le.span.identifier(),
+                        le.span.source_seg()
+                    )
+                }
+                LexicalErrorKind::UnmatchedDirective(d) => {
+                    write!(
+                        f,
+                        "\nError: Unmatched Directive: \"{}\" {}{}\n",
+                        d,
+                        le.span.identifier(),
+                        le.span.source_seg()
+                    )
+                }
+                LexicalErrorKind::UnterminatedConditional(name) => {
+                    write!(
+                        f,
+                        "\nError: Unterminated Conditional: \"{}\" {}{}\n",
+                        name,
+                        le.span.identifier(),
+                        le.span.source_seg()
+                    )
+                }
             },
             CompilerError::FileUnpackError(ue) => match ue {
                 UnpackError::InvalidDirectory(id) => {
@@ -521,6 +947,21 @@ impl fmt::Display for CompilerError {
                         pe.spans.error(pe.hint.as_ref())
                     )
                 }
+                ParserErrorKind::ShadowedConstant(cn) => {
+                    write!(
+                        f,
+                        "\nError: Macro-local constant \"{}\" shadows another constant \n{}\n",
+                        cn,
+                        pe.spans.error(pe.hint.as_ref())
+                    )
+                }
+                ParserErrorKind::MixedMacroArgs => {
+                    write!(
+                        f,
+                        "\nError: Macro invocation mixes named and positional arguments \n{}\n",
+                        pe.spans.error(pe.hint.as_ref())
+                    )
+                }
             },
             CompilerError::PathBufRead(os_str) => {
                 write!(
@@ -529,106 +970,352 @@ impl fmt::Display for CompilerError {
                     os_str.as_os_str().to_str().unwrap_or("<unknown import>")
                 )
             }
-            CompilerError::CodegenError(ce) => match &ce.kind {
-                CodegenErrorKind::LockingError => {
-                    write!(f, "\nError: Synchronisation Failure\n")
-                }
-                CodegenErrorKind::StoragePointersNotDerived => {
-                    write!(f, "\nError: Storage Pointers Not Derived\n{}\n", ce.span.error(None))
-                }
-                CodegenErrorKind::InvalidMacroStatement => {
-                    write!(f, "\nError: Invalid Macro Statement\n{}\n", ce.span.error(None))
-                }
-                CodegenErrorKind::MissingMacroDefinition(md) => {
-                    write!(
+            CompilerError::CodegenError(ce) => {
+                match &ce.kind {
+                    CodegenErrorKind::LockingError => {
+                        write!(f, "\nError: Synchronisation Failure\n")
+                    }
+                    CodegenErrorKind::StoragePointersNotDerived => {
+                        write!(
+                            f,
+                            "\nError: Storage Pointers Not Derived\n{}\n",
+                            ce.span.error(None)
+                        )
+                    }
+                    CodegenErrorKind::InvalidMacroStatement => {
+                        write!(f, "\nError: Invalid Macro Statement\n{}\n", ce.span.error(None))
+                    }
+                    CodegenErrorKind::MissingMacroDefinition(md) => {
+                        write!(
+                            f,
+                            "\nError: Missing Macro Definition For \"{}\"\n{}",
+                            md,
+                            ce.span.file()
+                        )
+                    }
+                    CodegenErrorKind::InvalidMacroInvocation(mmi) => {
+                        write!(
+                            f,
+                            "\nError: Missing Macro Definition For Invocation: \"{}\"\n{}\n",
+                            mmi,
+                            ce.span.error(None)
+                        )
+                    }
+                    CodegenErrorKind::MissingFunctionInterface(func) => {
+                        write!(
+                            f,
+                            "\nError: Missing Function Interface: \"{}\"\n{}\n",
+                            func,
+                            ce.span.error(None)
+                        )
+                    }
+                    CodegenErrorKind::MissingEventInterface(event) => {
+                        write!(
+                            f,
+                            "\nError: Missing Event Interface: \"{}\"\n{}\n",
+                            event,
+                            ce.span.error(None)
+                        )
+                    }
+                    CodegenErrorKind::MissingConstantDefinition(_) => {
+                        write!(f, "\nError: Missing Constant Definition\n{}\n", ce.span.error(None))
+                    }
+                    CodegenErrorKind::MissingErrorDefinition(_) => {
+                        write!(f, "\nError: Missing Error Definition\n{}\n", ce.span.error(None))
+                    }
+                    CodegenErrorKind::MissingTableDefinition(td) => {
+                        write!(
+                            f,
+                            "\nError: Missing Table Definition For \"{}\"\n{}\n",
+                            td,
+                            ce.span.error(None)
+                        )
+                    }
+                    CodegenErrorKind::AbiGenerationFailure => {
+                        write!(f, "\nError: ABI Generation Failed\n{}\n", ce.span.error(None))
+                    }
+                    CodegenErrorKind::IOError(ioe) => {
+                        write!(f, "\nError: IO Error: {ioe}\n{}", ce.span.file())
+                    }
+                    CodegenErrorKind::SerdeJson(e) => {
+                        write!(f, "\nError: JSON Serialization Error: {e}\n{}", ce.span.file())
+                    }
+                    CodegenErrorKind::StackImbalance { macro_name, expected, got } => {
+                        write!(
                         f,
-                        "\nError: Missing Macro Definition For \"{}\"\n{}",
-                        md,
-                        ce.span.file()
-                    )
-                }
-                CodegenErrorKind::InvalidMacroInvocation(mmi) => {
-                    write!(
-                        f,
-                        "\nError: Missing Macro Definition For Invocation: \"{}\"\n{}\n",
-                        mmi,
+                        "\nError: Macro \"{}\" Declares {} Returned Stack Items But Leaves {}\n{}\n",
+                        macro_name,
+                        expected,
+                        got,
                         ce.span.error(None)
                     )
-                }
-                CodegenErrorKind::MissingFunctionInterface(func) => {
-                    write!(
+                    }
+                    CodegenErrorKind::UnknownOpcode(pc, byte) => {
+                        write!(f, "\nError: Unknown Opcode {byte:#04x} At PC {pc}\n")
+                    }
+                    CodegenErrorKind::UnsupportedOpcode { opcode, evm_version } => {
+                        write!(
+                            f,
+                            "\nError: Opcode {} Is Not Supported By The {} EVM Version\n{}\n",
+                            opcode.to_mnemonic(),
+                            evm_version,
+                            ce.span.error(None)
+                        )
+                    }
+                    CodegenErrorKind::StorageSlotCollision(first, second) => {
+                        write!(
+                            f,
+                            "\nError: Storage Slot Constants \"{}\" And \"{}\" Hold The Same Slot\n{}\n",
+                            first,
+                            second,
+                            ce.span.error(None)
+                        )
+                    }
+                    CodegenErrorKind::InvalidEntryMacroSignature { macro_name, takes, returns } => {
+                        write!(
+                            f,
+                            "\nError: Entry Point Macro \"{}\" Declares Takes({}) Returns({}), Expected Takes(0) Returns(0)\n{}\n",
+                            macro_name,
+                            takes,
+                            returns,
+                            ce.span.error(None)
+                        )
+                    }
+                    CodegenErrorKind::UnusedMacroParameter { macro_name, param } => {
+                        write!(
+                            f,
+                            "\nError: Parameter \"{}\" Of Macro \"{}\" Is Never Used\n{}\n",
+                            param,
+                            macro_name,
+                            ce.span.error(None)
+                        )
+                    }
+                    CodegenErrorKind::MissingMacroArgument { macro_name, param } => {
+                        write!(
+                            f,
+                            "\nError: Invocation Of Macro \"{}\" Is Missing Argument \"{}\"\n{}\n",
+                            macro_name,
+                            param,
+                            ce.span.error(None)
+                        )
+                    }
+                    CodegenErrorKind::DuplicateMacroDefinition { name, .. } => {
+                        write!(
+                            f,
+                            "\nError: Macro \"{}\" Is Defined More Than Once\n{}\n",
+                            name,
+                            ce.span.error(None)
+                        )
+                    }
+                    CodegenErrorKind::InvalidJumpDestination { offset } => {
+                        write!(
+                            f,
+                            "\nError: Jump Target 0x{:02x} Is Not A JUMPDEST\n{}\n",
+                            offset,
+                            ce.span.error(None)
+                        )
+                    }
+                    CodegenErrorKind::DuplicateConstantDefinition { name, .. } => {
+                        write!(
+                            f,
+                            "\nError: Constant \"{}\" Is Defined More Than Once\n{}\n",
+                            name,
+                            ce.span.error(None)
+                        )
+                    }
+                    CodegenErrorKind::DuplicateTableDefinition { name, .. } => {
+                        write!(
+                            f,
+                            "\nError: Table \"{}\" Is Defined More Than Once\n{}\n",
+                            name,
+                            ce.span.error(None)
+                        )
+                    }
+                    CodegenErrorKind::DuplicateFunctionDefinition { name, .. } => {
+                        write!(
+                            f,
+                            "\nError: Function \"{}\" Is Defined More Than Once\n{}\n",
+                            name,
+                            ce.span.error(None)
+                        )
+                    }
+                    CodegenErrorKind::UnkownArgcallType => {
+                        write!(f, "\nError: Unknown Arg Call Type\n{}\n", ce.span.error(None))
+                    }
+                    CodegenErrorKind::MissingMacroInvocation(mmi) => {
+                        write!(
+                            f,
+                            "\nError: Missing Macro Invocation: \"{}\"\n{}\n",
+                            mmi,
+                            ce.span.error(None)
+                        )
+                    }
+                    CodegenErrorKind::UnmatchedJumpLabel => {
+                        write!(f, "\nError: Unmatched Jump Label\n{}\n", ce.span.error(None))
+                    }
+                    CodegenErrorKind::UsizeConversion(_) => {
+                        write!(f, "\nError: Usize Conversion\n{}\n", ce.span.error(None))
+                    }
+                    CodegenErrorKind::InvalidArguments(_) => {
+                        write!(f, "\nError: Invalid Arguments\n{}\n", ce.span.error(None))
+                    }
+                    CodegenErrorKind::InvalidHex(_) => {
+                        write!(f, "\nError: Invalid Hex\n{}\n", ce.span.error(None))
+                    }
+                    CodegenErrorKind::InvalidTableStatement(_) => {
+                        write!(f, "\nError: Invalid Table Statement\n{}\n", ce.span.error(None))
+                    }
+                    CodegenErrorKind::InvalidCodeLength(_) => {
+                        write!(f, "\nError: Invalid Code Length\n{}\n", ce.span.error(None))
+                    }
+                    CodegenErrorKind::TestInvocation(_) => {
+                        write!(f, "\nError: Test Invocation\n{}\n", ce.span.error(None))
+                    }
+                    CodegenErrorKind::InvalidDynArgIndex => {
+                        write!(
+                            f,
+                            "\nError: Invalid Dynamic Constructor Argument Index:\n{}\n",
+                            ce.span.error(None)
+                        )
+                    }
+                    CodegenErrorKind::TruncatedPushData(pc) => {
+                        write!(f, "\nError: Truncated Push Data At PC {pc}\n")
+                    }
+                    CodegenErrorKind::DeniedWarning(w) => {
+                        write!(f, "\nError: Denied Warning: {w}\n{}\n", ce.span.error(None))
+                    }
+                    CodegenErrorKind::ParameterizedEntryPoint(name) => {
+                        write!(
                         f,
-                        "\nError: Missing Function Interface: \"{}\"\n{}\n",
-                        func,
+                        "\nError: Entry Point Macro \"{}\" May Not Take Parameters. Invoke It From A Wrapper Macro Without Parameters Instead\n{}\n",
+                        name,
                         ce.span.error(None)
                     )
-                }
-                CodegenErrorKind::MissingEventInterface(event) => {
-                    write!(
+                    }
+                    CodegenErrorKind::CircularCodesize(cycle) => {
+                        write!(
+                            f,
+                            "\nError: Circular __codesize Reference: {}\n{}\n",
+                            cycle.join(" -> "),
+                            ce.span.error(None)
+                        )
+                    }
+                    CodegenErrorKind::MissingDispatchHandler(name) => {
+                        write!(
+                            f,
+                            "\nError: Missing Dispatch Handler Label \"{}\"\n{}\n",
+                            name,
+                            ce.span.error(None)
+                        )
+                    }
+                    CodegenErrorKind::OverloadedDispatchFunction(name) => {
+                        write!(
+                            f,
+                            "\nError: Overloaded Function \"{}\" Can't Share A Dispatch Handler Label\n{}\n",
+                            name,
+                            ce.span.error(None)
+                        )
+                    }
+                    CodegenErrorKind::LinkCollision(name) => {
+                        write!(
+                            f,
+                            "\nError: Conflicting Definitions Of \"{}\" In Linked Contracts\n{}\n",
+                            name,
+                            ce.span.error(None)
+                        )
+                    }
+                    CodegenErrorKind::InvalidSignature(sig) => {
+                        write!(
+                            f,
+                            "\nError: Invalid Signature \"{}\"\n{}\n",
+                            sig,
+                            ce.span.error(None)
+                        )
+                    }
+                    CodegenErrorKind::IncidentalInvalid(name) => {
+                        write!(
                         f,
-                        "\nError: Missing Event Interface: \"{}\"\n{}\n",
-                        event,
+                        "\nError: INVALID Opcode In Macro \"{}\" Not Emitted Through __INVALID()\n{}\n",
+                        name,
                         ce.span.error(None)
                     )
-                }
-                CodegenErrorKind::MissingConstantDefinition(_) => {
-                    write!(f, "\nError: Missing Constant Definition\n{}\n", ce.span.error(None))
-                }
-                CodegenErrorKind::MissingErrorDefinition(_) => {
-                    write!(f, "\nError: Missing Error Definition\n{}\n", ce.span.error(None))
-                }
-                CodegenErrorKind::AbiGenerationFailure => {
-                    write!(f, "\nError: ABI Generation Failed\n{}\n", ce.span.error(None))
-                }
-                CodegenErrorKind::IOError(ioe) => {
-                    write!(f, "\nError: IO Error: {ioe}\n{}", ce.span.file())
-                }
-                CodegenErrorKind::UnkownArgcallType => {
-                    write!(f, "\nError: Unknown Arg Call Type\n{}\n", ce.span.error(None))
-                }
-                CodegenErrorKind::MissingMacroInvocation(mmi) => {
-                    write!(
+                    }
+                    CodegenErrorKind::InvalidTableSize(name) => {
+                        write!(
+                            f,
+                            "\nError: Invalid Size For Table \"{}\"\n{}\n",
+                            name,
+                            ce.span.error(None)
+                        )
+                    }
+                    CodegenErrorKind::MissingCodeOffset(name) => {
+                        write!(
                         f,
-                        "\nError: Missing Macro Invocation: \"{}\"\n{}\n",
-                        mmi,
+                        "\nError: Macro \"{}\" Passed To __codeoffset Is Not In The Bytecode\n{}\n",
+                        name,
                         ce.span.error(None)
                     )
-                }
-                CodegenErrorKind::UnmatchedJumpLabel => {
-                    write!(f, "\nError: Unmatched Jump Label\n{}\n", ce.span.error(None))
-                }
-                CodegenErrorKind::UsizeConversion(_) => {
-                    write!(f, "\nError: Usize Conversion\n{}\n", ce.span.error(None))
-                }
-                CodegenErrorKind::InvalidArguments(_) => {
-                    write!(f, "\nError: Invalid Arguments\n{}\n", ce.span.error(None))
-                }
-                CodegenErrorKind::InvalidHex(_) => {
-                    write!(f, "\nError: Invalid Hex\n{}\n", ce.span.error(None))
-                }
-                CodegenErrorKind::InvalidTableStatement(_) => {
-                    write!(f, "\nError: Invalid Table Statement\n{}\n", ce.span.error(None))
-                }
-                CodegenErrorKind::InvalidCodeLength(_) => {
-                    write!(f, "\nError: Invalid Code Length\n{}\n", ce.span.error(None))
-                }
-                CodegenErrorKind::TestInvocation(_) => {
-                    write!(f, "\nError: Test Invocation\n{}\n", ce.span.error(None))
-                }
-                CodegenErrorKind::InvalidDynArgIndex => {
-                    write!(
+                    }
+                    CodegenErrorKind::CodeOffsetOverflow(name, offset) => {
+                        write!(
+                            f,
+                            "\nError: Offset {:#x} Of Macro \"{}\" Passed To __codeoffset Doesn't Fit In 2 Bytes\n{}\n",
+                            offset,
+                            name,
+                            ce.span.error(None)
+                        )
+                    }
+                    CodegenErrorKind::RecursiveMacroInvocation(cycle) => {
+                        write!(
+                            f,
+                            "\nError: Recursive Macro Invocation: {}\n{}\n",
+                            cycle.join(" -> "),
+                            ce.span.error(None)
+                        )
+                    }
+                    CodegenErrorKind::BootstrapSizeOverflow(size) => {
+                        write!(
                         f,
-                        "\nError: Invalid Dynamic Constructor Argument Index:\n{}\n",
+                        "\nError: Code Size Of {} Bytes Is Too Large For The Bootstrap Code\n{}\n",
+                        size,
                         ce.span.error(None)
                     )
+                    }
+                    CodegenErrorKind::InvalidConstructorArgument { index, expected, got } => {
+                        write!(
+                            f,
+                            "\nError: Constructor Argument {} \"{}\" Is Not A Valid {}\n{}\n",
+                            index,
+                            got,
+                            expected,
+                            ce.span.error(None)
+                        )
+                    }
+                    CodegenErrorKind::StateChangeInView(name, op) => {
+                        write!(
+                            f,
+                            "\nError: View Macro \"{}\" Changes State With Opcode: {}\n{}\n",
+                            name,
+                            op.to_mnemonic(),
+                            ce.span.error(None)
+                        )
+                    }
+                }?;
+                if !ce.call_stack.is_empty() {
+                    let macros = ce.call_stack.iter().map(|(name, _)| name.as_str());
+                    writeln!(f, "In: {}", macros.collect::<Vec<_>>().join(" -> "))?;
                 }
-            },
+                Ok(())
+            }
             CompilerError::FailedCompiles(v) => {
                 v.iter().for_each(|ce| {
                     let _ = write!(f, "{ce}");
                 });
                 Ok(())
             }
+            CompilerError::CircularInclude(cycle) => {
+                write!(f, "\nError: Circular Include: {}", cycle.join(" -> "))
+            }
         }
     }
 }
@@ -1,4 +1,5 @@
 use phf::phf_map;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 use strum_macros::EnumString;
 
@@ -315,8 +316,13 @@ pub static OPCODES_MAP: phf::Map<&'static str, Opcode> = phf_map! {
 
 /// EVM Opcodes
 /// References <https://evm.codes>
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, EnumString)]
-#[strum(serialize_all = "lowercase")]
+///
+/// Mnemonics are parsed case-insensitively, so `MSTORE`, `mstore` and `MStore` are all
+/// `Opcode::Mstore`.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, EnumString, Serialize, Deserialize,
+)]
+#[strum(serialize_all = "lowercase", ascii_case_insensitive)]
 pub enum Opcode {
     /// Halts execution.
     Stop,
@@ -779,6 +785,347 @@ impl Opcode {
         opcode_str.to_string()
     }
 
+    /// Returns the mnemonic of an Opcode, e.g. `"MSTORE"`
+    ///
+    /// Unlike `Display`, which writes the hex encoded opcode for code generation, the mnemonic is
+    /// meant for disassembly listings and messages.
+    pub fn to_mnemonic(&self) -> &'static str {
+        match self {
+            Opcode::Stop => "STOP",
+            Opcode::Add => "ADD",
+            Opcode::Mul => "MUL",
+            Opcode::Sub => "SUB",
+            Opcode::Div => "DIV",
+            Opcode::Sdiv => "SDIV",
+            Opcode::Mod => "MOD",
+            Opcode::Smod => "SMOD",
+            Opcode::Addmod => "ADDMOD",
+            Opcode::Mulmod => "MULMOD",
+            Opcode::Exp => "EXP",
+            Opcode::Signextend => "SIGNEXTEND",
+            Opcode::Lt => "LT",
+            Opcode::Gt => "GT",
+            Opcode::Slt => "SLT",
+            Opcode::Sgt => "SGT",
+            Opcode::Eq => "EQ",
+            Opcode::Iszero => "ISZERO",
+            Opcode::And => "AND",
+            Opcode::Or => "OR",
+            Opcode::Xor => "XOR",
+            Opcode::Not => "NOT",
+            Opcode::Byte => "BYTE",
+            Opcode::Shl => "SHL",
+            Opcode::Shr => "SHR",
+            Opcode::Sar => "SAR",
+            Opcode::Sha3 => "SHA3",
+            Opcode::Address => "ADDRESS",
+            Opcode::Balance => "BALANCE",
+            Opcode::Origin => "ORIGIN",
+            Opcode::Caller => "CALLER",
+            Opcode::Callvalue => "CALLVALUE",
+            Opcode::Calldataload => "CALLDATALOAD",
+            Opcode::Calldatasize => "CALLDATASIZE",
+            Opcode::Calldatacopy => "CALLDATACOPY",
+            Opcode::Codesize => "CODESIZE",
+            Opcode::Codecopy => "CODECOPY",
+            Opcode::Gasprice => "GASPRICE",
+            Opcode::Extcodesize => "EXTCODESIZE",
+            Opcode::Extcodecopy => "EXTCODECOPY",
+            Opcode::Returndatasize => "RETURNDATASIZE",
+            Opcode::Returndatacopy => "RETURNDATACOPY",
+            Opcode::Blockhash => "BLOCKHASH",
+            Opcode::Coinbase => "COINBASE",
+            Opcode::Timestamp => "TIMESTAMP",
+            Opcode::Number => "NUMBER",
+            Opcode::Difficulty => "DIFFICULTY",
+            Opcode::Prevrandao => "PREVRANDAO",
+            Opcode::Gaslimit => "GASLIMIT",
+            Opcode::Chainid => "CHAINID",
+            Opcode::Selfbalance => "SELFBALANCE",
+            Opcode::Basefee => "BASEFEE",
+            Opcode::Blobhash => "BLOBHASH",
+            Opcode::Blobbasefee => "BLOBBASEFEE",
+            Opcode::Pop => "POP",
+            Opcode::Mload => "MLOAD",
+            Opcode::Mstore => "MSTORE",
+            Opcode::Mstore8 => "MSTORE8",
+            Opcode::Sload => "SLOAD",
+            Opcode::Sstore => "SSTORE",
+            Opcode::Jump => "JUMP",
+            Opcode::Jumpi => "JUMPI",
+            Opcode::Pc => "PC",
+            Opcode::Msize => "MSIZE",
+            Opcode::Gas => "GAS",
+            Opcode::Jumpdest => "JUMPDEST",
+            Opcode::Push0 => "PUSH0",
+            Opcode::Push1 => "PUSH1",
+            Opcode::Push2 => "PUSH2",
+            Opcode::Push3 => "PUSH3",
+            Opcode::Push4 => "PUSH4",
+            Opcode::Push5 => "PUSH5",
+            Opcode::Push6 => "PUSH6",
+            Opcode::Push7 => "PUSH7",
+            Opcode::Push8 => "PUSH8",
+            Opcode::Push9 => "PUSH9",
+            Opcode::Push10 => "PUSH10",
+            Opcode::Push11 => "PUSH11",
+            Opcode::Push12 => "PUSH12",
+            Opcode::Push13 => "PUSH13",
+            Opcode::Push14 => "PUSH14",
+            Opcode::Push15 => "PUSH15",
+            Opcode::Push16 => "PUSH16",
+            Opcode::Push17 => "PUSH17",
+            Opcode::Push18 => "PUSH18",
+            Opcode::Push19 => "PUSH19",
+            Opcode::Push20 => "PUSH20",
+            Opcode::Push21 => "PUSH21",
+            Opcode::Push22 => "PUSH22",
+            Opcode::Push23 => "PUSH23",
+            Opcode::Push24 => "PUSH24",
+            Opcode::Push25 => "PUSH25",
+            Opcode::Push26 => "PUSH26",
+            Opcode::Push27 => "PUSH27",
+            Opcode::Push28 => "PUSH28",
+            Opcode::Push29 => "PUSH29",
+            Opcode::Push30 => "PUSH30",
+            Opcode::Push31 => "PUSH31",
+            Opcode::Push32 => "PUSH32",
+            Opcode::Dup1 => "DUP1",
+            Opcode::Dup2 => "DUP2",
+            Opcode::Dup3 => "DUP3",
+            Opcode::Dup4 => "DUP4",
+            Opcode::Dup5 => "DUP5",
+            Opcode::Dup6 => "DUP6",
+            Opcode::Dup7 => "DUP7",
+            Opcode::Dup8 => "DUP8",
+            Opcode::Dup9 => "DUP9",
+            Opcode::Dup10 => "DUP10",
+            Opcode::Dup11 => "DUP11",
+            Opcode::Dup12 => "DUP12",
+            Opcode::Dup13 => "DUP13",
+            Opcode::Dup14 => "DUP14",
+            Opcode::Dup15 => "DUP15",
+            Opcode::Dup16 => "DUP16",
+            Opcode::Swap1 => "SWAP1",
+            Opcode::Swap2 => "SWAP2",
+            Opcode::Swap3 => "SWAP3",
+            Opcode::Swap4 => "SWAP4",
+            Opcode::Swap5 => "SWAP5",
+            Opcode::Swap6 => "SWAP6",
+            Opcode::Swap7 => "SWAP7",
+            Opcode::Swap8 => "SWAP8",
+            Opcode::Swap9 => "SWAP9",
+            Opcode::Swap10 => "SWAP10",
+            Opcode::Swap11 => "SWAP11",
+            Opcode::Swap12 => "SWAP12",
+            Opcode::Swap13 => "SWAP13",
+            Opcode::Swap14 => "SWAP14",
+            Opcode::Swap15 => "SWAP15",
+            Opcode::Swap16 => "SWAP16",
+            Opcode::Log0 => "LOG0",
+            Opcode::Log1 => "LOG1",
+            Opcode::Log2 => "LOG2",
+            Opcode::Log3 => "LOG3",
+            Opcode::Log4 => "LOG4",
+            Opcode::Tload => "TLOAD",
+            Opcode::Tstore => "TSTORE",
+            Opcode::Mcopy => "MCOPY",
+            Opcode::Create => "CREATE",
+            Opcode::Call => "CALL",
+            Opcode::Callcode => "CALLCODE",
+            Opcode::Return => "RETURN",
+            Opcode::Delegatecall => "DELEGATECALL",
+            Opcode::Create2 => "CREATE2",
+            Opcode::Staticcall => "STATICCALL",
+            Opcode::Revert => "REVERT",
+            Opcode::Invalid => "INVALID",
+            Opcode::Selfdestruct => "SELFDESTRUCT",
+            Opcode::Extcodehash => "EXTCODEHASH",
+        }
+    }
+
+    /// Translates an Opcode into its byte
+    pub fn to_u8(&self) -> u8 {
+        u8::from_str_radix(&self.string(), 16).unwrap_or_default()
+    }
+
+    /// Translates a byte into its Opcode, if the byte is a defined opcode
+    ///
+    /// `0x44` translates to `PREVRANDAO`, which replaced `DIFFICULTY` at the merge.
+    pub fn from_u8(byte: u8) -> Option<Opcode> {
+        let opcode = match byte {
+            0x00 => Opcode::Stop,
+            0x01 => Opcode::Add,
+            0x02 => Opcode::Mul,
+            0x03 => Opcode::Sub,
+            0x04 => Opcode::Div,
+            0x05 => Opcode::Sdiv,
+            0x06 => Opcode::Mod,
+            0x07 => Opcode::Smod,
+            0x08 => Opcode::Addmod,
+            0x09 => Opcode::Mulmod,
+            0x0a => Opcode::Exp,
+            0x0b => Opcode::Signextend,
+            0x10 => Opcode::Lt,
+            0x11 => Opcode::Gt,
+            0x12 => Opcode::Slt,
+            0x13 => Opcode::Sgt,
+            0x14 => Opcode::Eq,
+            0x15 => Opcode::Iszero,
+            0x16 => Opcode::And,
+            0x17 => Opcode::Or,
+            0x18 => Opcode::Xor,
+            0x19 => Opcode::Not,
+            0x1a => Opcode::Byte,
+            0x1b => Opcode::Shl,
+            0x1c => Opcode::Shr,
+            0x1d => Opcode::Sar,
+            0x20 => Opcode::Sha3,
+            0x30 => Opcode::Address,
+            0x31 => Opcode::Balance,
+            0x32 => Opcode::Origin,
+            0x33 => Opcode::Caller,
+            0x34 => Opcode::Callvalue,
+            0x35 => Opcode::Calldataload,
+            0x36 => Opcode::Calldatasize,
+            0x37 => Opcode::Calldatacopy,
+            0x38 => Opcode::Codesize,
+            0x39 => Opcode::Codecopy,
+            0x3a => Opcode::Gasprice,
+            0x3b => Opcode::Extcodesize,
+            0x3c => Opcode::Extcodecopy,
+            0x3d => Opcode::Returndatasize,
+            0x3e => Opcode::Returndatacopy,
+            0x3f => Opcode::Extcodehash,
+            0x40 => Opcode::Blockhash,
+            0x41 => Opcode::Coinbase,
+            0x42 => Opcode::Timestamp,
+            0x43 => Opcode::Number,
+            0x44 => Opcode::Prevrandao,
+            0x45 => Opcode::Gaslimit,
+            0x46 => Opcode::Chainid,
+            0x47 => Opcode::Selfbalance,
+            0x48 => Opcode::Basefee,
+            0x49 => Opcode::Blobhash,
+            0x4a => Opcode::Blobbasefee,
+            0x50 => Opcode::Pop,
+            0x51 => Opcode::Mload,
+            0x52 => Opcode::Mstore,
+            0x53 => Opcode::Mstore8,
+            0x54 => Opcode::Sload,
+            0x55 => Opcode::Sstore,
+            0x56 => Opcode::Jump,
+            0x57 => Opcode::Jumpi,
+            0x58 => Opcode::Pc,
+            0x59 => Opcode::Msize,
+            0x5a => Opcode::Gas,
+            0x5b => Opcode::Jumpdest,
+            0x5c => Opcode::Tload,
+            0x5d => Opcode::Tstore,
+            0x5e => Opcode::Mcopy,
+            0x5f => Opcode::Push0,
+            0x60 => Opcode::Push1,
+            0x61 => Opcode::Push2,
+            0x62 => Opcode::Push3,
+            0x63 => Opcode::Push4,
+            0x64 => Opcode::Push5,
+            0x65 => Opcode::Push6,
+            0x66 => Opcode::Push7,
+            0x67 => Opcode::Push8,
+            0x68 => Opcode::Push9,
+            0x69 => Opcode::Push10,
+            0x6a => Opcode::Push11,
+            0x6b => Opcode::Push12,
+            0x6c => Opcode::Push13,
+            0x6d => Opcode::Push14,
+            0x6e => Opcode::Push15,
+            0x6f => Opcode::Push16,
+            0x70 => Opcode::Push17,
+            0x71 => Opcode::Push18,
+            0x72 => Opcode::Push19,
+            0x73 => Opcode::Push20,
+            0x74 => Opcode::Push21,
+            0x75 => Opcode::Push22,
+            0x76 => Opcode::Push23,
+            0x77 => Opcode::Push24,
+            0x78 => Opcode::Push25,
+            0x79 => Opcode::Push26,
+            0x7a => Opcode::Push27,
+            0x7b => Opcode::Push28,
+            0x7c => Opcode::Push29,
+            0x7d => Opcode::Push30,
+            0x7e => Opcode::Push31,
+            0x7f => Opcode::Push32,
+            0x80 => Opcode::Dup1,
+            0x81 => Opcode::Dup2,
+            0x82 => Opcode::Dup3,
+            0x83 => Opcode::Dup4,
+            0x84 => Opcode::Dup5,
+            0x85 => Opcode::Dup6,
+            0x86 => Opcode::Dup7,
+            0x87 => Opcode::Dup8,
+            0x88 => Opcode::Dup9,
+            0x89 => Opcode::Dup10,
+            0x8a => Opcode::Dup11,
+            0x8b => Opcode::Dup12,
+            0x8c => Opcode::Dup13,
+            0x8d => Opcode::Dup14,
+            0x8e => Opcode::Dup15,
+            0x8f => Opcode::Dup16,
+            0x90 => Opcode::Swap1,
+            0x91 => Opcode::Swap2,
+            0x92 => Opcode::Swap3,
+            0x93 => Opcode::Swap4,
+            0x94 => Opcode::Swap5,
+            0x95 => Opcode::Swap6,
+            0x96 => Opcode::Swap7,
+            0x97 => Opcode::Swap8,
+            0x98 => Opcode::Swap9,
+            0x99 => Opcode::Swap10,
+            0x9a => Opcode::Swap11,
+            0x9b => Opcode::Swap12,
+            0x9c => Opcode::Swap13,
+            0x9d => Opcode::Swap14,
+            0x9e => Opcode::Swap15,
+            0x9f => Opcode::Swap16,
+            0xa0 => Opcode::Log0,
+            0xa1 => Opcode::Log1,
+            0xa2 => Opcode::Log2,
+            0xa3 => Opcode::Log3,
+            0xa4 => Opcode::Log4,
+            0xf0 => Opcode::Create,
+            0xf1 => Opcode::Call,
+            0xf2 => Opcode::Callcode,
+            0xf3 => Opcode::Return,
+            0xf4 => Opcode::Delegatecall,
+            0xf5 => Opcode::Create2,
+            0xfa => Opcode::Staticcall,
+            0xfd => Opcode::Revert,
+            0xfe => Opcode::Invalid,
+            0xff => Opcode::Selfdestruct,
+            _ => return None,
+        };
+        Some(opcode)
+    }
+
+    /// Returns true if the current opcode is a push opcode, including `PUSH0`
+    pub fn is_push(&self) -> bool {
+        *self == Opcode::Push0 || self.is_value_push()
+    }
+
+    /// Returns the number of data bytes following a push opcode that takes a literal value
+    pub fn push_size(&self) -> Option<u8> {
+        self.is_value_push().then(|| self.to_u8() - Opcode::Push0.to_u8())
+    }
+
+    /// Returns the smallest push opcode taking `n` data bytes
+    ///
+    /// Returns `None` if `n` is zero, as `PUSH0` takes no data, or wider than 32 bytes.
+    pub fn push_for_bytes(n: usize) -> Option<Opcode> {
+        let n = u8::try_from(n).ok().filter(|n| (1..=32).contains(n))?;
+        Opcode::from_u8(Opcode::Push0.to_u8() + n)
+    }
+
     /// Returns true if the current opcode is a push opcode that takes a literal value
     pub fn is_value_push(&self) -> bool {
         matches!(
@@ -818,6 +1165,208 @@ impl Opcode {
         )
     }
 
+    /// Returns true if the current opcode is one of `DUP1` to `DUP16`
+    pub fn is_dup(&self) -> bool {
+        (Opcode::Dup1.to_u8()..=Opcode::Dup16.to_u8()).contains(&self.to_u8())
+    }
+
+    /// Returns true if the current opcode is one of `SWAP1` to `SWAP16`
+    pub fn is_swap(&self) -> bool {
+        (Opcode::Swap1.to_u8()..=Opcode::Swap16.to_u8()).contains(&self.to_u8())
+    }
+
+    /// Returns true if the opcode always writes state, i.e. it is not allowed in a static context.
+    ///
+    /// `CALL` and `CALLCODE` only write state when sending value, so they are not included.
+    pub fn is_state_changing(&self) -> bool {
+        matches!(
+            self,
+            Opcode::Sstore |
+                Opcode::Tstore |
+                Opcode::Log0 |
+                Opcode::Log1 |
+                Opcode::Log2 |
+                Opcode::Log3 |
+                Opcode::Log4 |
+                Opcode::Create |
+                Opcode::Create2 |
+                Opcode::Selfdestruct
+        )
+    }
+
+    /// Returns true if execution never continues to the next instruction after the opcode.
+    pub fn is_terminator(&self) -> bool {
+        matches!(
+            self,
+            Opcode::Jump |
+                Opcode::Stop |
+                Opcode::Return |
+                Opcode::Revert |
+                Opcode::Invalid |
+                Opcode::Selfdestruct
+        )
+    }
+
+    /// Returns the number of stack items the opcode consumes
+    pub fn stack_inputs(&self) -> u8 {
+        let byte = self.to_u8();
+        match self {
+            o if (Opcode::Dup1..=Opcode::Dup16).contains(o) => byte - 0x7f,
+            o if (Opcode::Swap1..=Opcode::Swap16).contains(o) => byte - 0x8e,
+            o if (Opcode::Log0..=Opcode::Log4).contains(o) => byte - 0x9e,
+            Opcode::Call | Opcode::Callcode => 7,
+            Opcode::Delegatecall | Opcode::Staticcall => 6,
+            Opcode::Extcodecopy | Opcode::Create2 => 4,
+            Opcode::Addmod |
+            Opcode::Mulmod |
+            Opcode::Calldatacopy |
+            Opcode::Codecopy |
+            Opcode::Returndatacopy |
+            Opcode::Mcopy |
+            Opcode::Create => 3,
+            Opcode::Add |
+            Opcode::Mul |
+            Opcode::Sub |
+            Opcode::Div |
+            Opcode::Sdiv |
+            Opcode::Mod |
+            Opcode::Smod |
+            Opcode::Exp |
+            Opcode::Signextend |
+            Opcode::Lt |
+            Opcode::Gt |
+            Opcode::Slt |
+            Opcode::Sgt |
+            Opcode::Eq |
+            Opcode::And |
+            Opcode::Or |
+            Opcode::Xor |
+            Opcode::Byte |
+            Opcode::Shl |
+            Opcode::Shr |
+            Opcode::Sar |
+            Opcode::Sha3 |
+            Opcode::Mstore |
+            Opcode::Mstore8 |
+            Opcode::Sstore |
+            Opcode::Jumpi |
+            Opcode::Tstore |
+            Opcode::Return |
+            Opcode::Revert => 2,
+            Opcode::Iszero |
+            Opcode::Not |
+            Opcode::Balance |
+            Opcode::Calldataload |
+            Opcode::Extcodesize |
+            Opcode::Extcodehash |
+            Opcode::Blockhash |
+            Opcode::Blobhash |
+            Opcode::Pop |
+            Opcode::Mload |
+            Opcode::Sload |
+            Opcode::Jump |
+            Opcode::Tload |
+            Opcode::Selfdestruct => 1,
+            _ => 0,
+        }
+    }
+
+    /// Returns the number of stack items the opcode produces
+    pub fn stack_outputs(&self) -> u8 {
+        let byte = self.to_u8();
+        match self {
+            o if (Opcode::Dup1..=Opcode::Dup16).contains(o) => byte - 0x7e,
+            o if (Opcode::Swap1..=Opcode::Swap16).contains(o) => byte - 0x8e,
+            o if (Opcode::Push0..=Opcode::Push32).contains(o) => 1,
+            Opcode::Stop |
+            Opcode::Calldatacopy |
+            Opcode::Codecopy |
+            Opcode::Extcodecopy |
+            Opcode::Returndatacopy |
+            Opcode::Pop |
+            Opcode::Mstore |
+            Opcode::Mstore8 |
+            Opcode::Sstore |
+            Opcode::Jump |
+            Opcode::Jumpi |
+            Opcode::Jumpdest |
+            Opcode::Tstore |
+            Opcode::Mcopy |
+            Opcode::Log0 |
+            Opcode::Log1 |
+            Opcode::Log2 |
+            Opcode::Log3 |
+            Opcode::Log4 |
+            Opcode::Return |
+            Opcode::Revert |
+            Opcode::Invalid |
+            Opcode::Selfdestruct => 0,
+            _ => 1,
+        }
+    }
+
+    /// Returns the static gas cost of the opcode
+    ///
+    /// Opcodes with dynamic costs return their minimum, e.g. the warm access cost for account
+    /// and storage accesses. Memory expansion, copy and access list costs are not included.
+    pub fn min_gas(&self) -> u64 {
+        match self {
+            Opcode::Stop | Opcode::Return | Opcode::Revert | Opcode::Invalid => 0,
+            Opcode::Jumpdest => 1,
+            Opcode::Address |
+            Opcode::Origin |
+            Opcode::Caller |
+            Opcode::Callvalue |
+            Opcode::Calldatasize |
+            Opcode::Codesize |
+            Opcode::Gasprice |
+            Opcode::Returndatasize |
+            Opcode::Coinbase |
+            Opcode::Timestamp |
+            Opcode::Number |
+            Opcode::Difficulty |
+            Opcode::Prevrandao |
+            Opcode::Gaslimit |
+            Opcode::Chainid |
+            Opcode::Basefee |
+            Opcode::Blobbasefee |
+            Opcode::Pop |
+            Opcode::Pc |
+            Opcode::Msize |
+            Opcode::Gas |
+            Opcode::Push0 => 2,
+            Opcode::Mul |
+            Opcode::Div |
+            Opcode::Sdiv |
+            Opcode::Mod |
+            Opcode::Smod |
+            Opcode::Signextend |
+            Opcode::Selfbalance => 5,
+            Opcode::Addmod | Opcode::Mulmod | Opcode::Jump => 8,
+            Opcode::Exp | Opcode::Jumpi => 10,
+            Opcode::Blockhash => 20,
+            Opcode::Sha3 => 30,
+            Opcode::Balance |
+            Opcode::Extcodesize |
+            Opcode::Extcodecopy |
+            Opcode::Extcodehash |
+            Opcode::Sload |
+            Opcode::Sstore |
+            Opcode::Tload |
+            Opcode::Tstore |
+            Opcode::Call |
+            Opcode::Callcode |
+            Opcode::Delegatecall |
+            Opcode::Staticcall => 100,
+            o if (Opcode::Log0..=Opcode::Log4).contains(o) => {
+                375 * (self.stack_inputs() as u64 - 1)
+            }
+            Opcode::Selfdestruct => 5000,
+            Opcode::Create | Opcode::Create2 => 32000,
+            _ => 3,
+        }
+    }
+
     /// Prefixes the literal if necessary
     pub fn prefix_push_literal(&self, literal: &str) -> String {
         if self.is_value_push() {
@@ -840,16 +1389,7 @@ impl Opcode {
 
     /// Checks if the value overflows the given push opcode
     pub fn push_overflows(&self, literal: &str) -> bool {
-        if self.is_value_push() {
-            if let Ok(len) = u8::from_str_radix(&self.to_string(), 16) {
-                if len >= 96 {
-                    let size = (len - 96 + 1) * 2;
-                    return literal.len() > size as usize;
-                }
-            }
-        }
-
-        false
+        self.push_size().is_some_and(|size| literal.len() > size as usize * 2)
     }
 }
 
@@ -860,6 +1400,15 @@ impl fmt::Display for Opcode {
     }
 }
 
+impl TryFrom<u8> for Opcode {
+    type Error = u8;
+
+    /// Translates a byte into its Opcode, returning the byte if it isn't a defined opcode
+    fn try_from(byte: u8) -> Result<Self, Self::Error> {
+        Opcode::from_u8(byte).ok_or(byte)
+    }
+}
+
 impl From<Opcode> for String {
     fn from(o: Opcode) -> Self {
         o.string()
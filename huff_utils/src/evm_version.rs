@@ -1,4 +1,5 @@
-use std::cmp::PartialOrd;
+use crate::evm::Opcode;
+use std::{cmp::PartialOrd, fmt};
 
 /// Evm Version
 ///
@@ -10,6 +11,8 @@ pub enum SupportedEVMVersions {
     Paris,
     /// Introduce Push0, compiler will use by default
     Shanghai,
+    /// Introduce Mcopy, Blobhash and Blobbasefee
+    Cancun,
 }
 
 #[derive(Debug)]
@@ -28,6 +31,35 @@ impl EVMVersion {
     pub fn has_push0(&self) -> bool {
         self.version >= SupportedEVMVersions::Shanghai
     }
+
+    /// All versions after cancun will support mcopy
+    pub fn has_mcopy(&self) -> bool {
+        self.version >= SupportedEVMVersions::Cancun
+    }
+
+    /// All versions after cancun will support blobhash and blobbasefee
+    pub fn has_blobs(&self) -> bool {
+        self.version >= SupportedEVMVersions::Cancun
+    }
+
+    /// Whether an opcode is available in this version
+    pub fn supports_opcode(&self, opcode: &Opcode) -> bool {
+        match opcode {
+            Opcode::Mcopy => self.has_mcopy(),
+            Opcode::Blobhash | Opcode::Blobbasefee => self.has_blobs(),
+            _ => true,
+        }
+    }
+}
+
+impl fmt::Display for EVMVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.version {
+            SupportedEVMVersions::Paris => write!(f, "paris"),
+            SupportedEVMVersions::Shanghai => write!(f, "shanghai"),
+            SupportedEVMVersions::Cancun => write!(f, "cancun"),
+        }
+    }
 }
 
 impl Default for EVMVersion {
@@ -50,6 +82,7 @@ impl From<Option<String>> for EVMVersion {
 impl From<String> for EVMVersion {
     fn from(version: String) -> Self {
         match version.as_str() {
+            "cancun" => Self::new(SupportedEVMVersions::Cancun),
             "shanghai" => Self::new(SupportedEVMVersions::Shanghai),
             "paris" => Self::new(SupportedEVMVersions::Paris),
             _ => Self::default(),
@@ -27,6 +27,11 @@ pub struct FullFileSource<'a> {
 }
 
 impl<'a> FullFileSource<'a> {
+    /// Converts a byte offset in the flattened source to a 1-based `(line, column)` pair
+    pub fn line_col(&self, offset: usize) -> (usize, usize) {
+        line_col(self.source, offset)
+    }
+
     /// Get the relative span
     pub fn relative_span(&self, span: Ref<'a, Span>) -> Option<Span> {
         self.spans
@@ -201,7 +206,7 @@ impl Remapper {
 #[derive(Debug, Default, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone)]
 pub struct FileSource {
     /// File ID
-    #[serde(skip)]
+    #[serde(default)]
     pub id: Uuid,
     /// File Path
     pub path: String,
@@ -214,6 +219,29 @@ pub struct FileSource {
 }
 
 impl FileSource {
+    /// Converts a byte offset in the source to a 1-based `(line, column)` pair, if the source is
+    /// loaded
+    pub fn line_col(&self, offset: usize) -> Option<(usize, usize)> {
+        self.source.as_deref().map(|s| line_col(s, offset))
+    }
+
+    /// Hashes the source of the file and, recursively, the hashes of its dependencies
+    ///
+    /// Only file contents are hashed, so the hash changes whenever the file or any file it
+    /// includes changes, but not when the file is moved.
+    pub fn content_hash(&self) -> String {
+        let mut source_hash = [0u8; 32];
+        hash_bytes(&mut source_hash, &self.source.clone().unwrap_or_default());
+        let mut preimage = source_hash.iter().map(|b| format!("{b:02x}")).collect::<String>();
+        for dependency in self.dependencies.iter().flatten() {
+            preimage.push_str(&dependency.content_hash());
+        }
+
+        let mut hash = [0u8; 32];
+        hash_bytes(&mut hash, &preimage);
+        hash.iter().map(|b| format!("{b:02x}")).collect()
+    }
+
     /// Generates a fully flattened source code for the given `FileSource` and all its dependencies
     ///
     /// ### Examples
@@ -315,7 +343,7 @@ impl FileSource {
     }
 }
 
-use crate::time;
+use crate::{bytes_util::hash_bytes, time};
 use std::ops::{Add, Range};
 
 /// A Span is a section of a source file.
@@ -344,10 +372,16 @@ impl Span {
     }
 
     /// Produces a file identifier string for errors
+    ///
+    /// Points at the line and column of the start of the span if the source is loaded, otherwise
+    /// at its byte offsets.
     pub fn identifier(&self) -> String {
         self.file
             .as_ref()
-            .map(|f| format!("\n-> {}:{}-{}", f.path, self.start, self.end))
+            .map(|f| match f.line_col(self.start) {
+                Some((line, col)) => format!("\n-> {}:{line}:{col}", f.path),
+                None => format!("\n-> {}:{}-{}", f.path, self.start, self.end),
+            })
             .unwrap_or_default()
     }
 
@@ -430,3 +464,22 @@ impl<T> Spanned for WithSpan<T> {
         self.span.clone()
     }
 }
+
+/// Converts a byte offset in a source to a 1-based `(line, column)` pair
+///
+/// Columns count characters rather than bytes, so multi-byte UTF-8 characters take up a single
+/// column, and a `\r\n` line ending is a single line break. An offset inside a multi-byte
+/// character maps to that character, and an offset past the end of the source maps to its end.
+fn line_col(source: &str, offset: usize) -> (usize, usize) {
+    let mut end = offset.min(source.len());
+    while !source.is_char_boundary(end) {
+        end -= 1;
+    }
+    let before = &source[..end];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    let line = before.matches('\n').count() + 1;
+    let col =
+        before[line_start..].strip_suffix('\r').unwrap_or(&before[line_start..]).chars().count() +
+            1;
+    (line, col)
+}
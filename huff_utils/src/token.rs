@@ -1,10 +1,11 @@
 use crate::{evm::Opcode, files::Span, types::PrimitiveEVMType};
+use serde::{Deserialize, Serialize};
 use std::{fmt, fmt::Write};
 
 type Literal = [u8; 32];
 
 /// A single Token
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct Token {
     /// The kind of token
     pub kind: TokenKind,
@@ -20,12 +21,15 @@ impl Token {
 }
 
 /// The kind of token
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Serialize, Deserialize)]
 pub enum TokenKind {
     /// EOF Token
     Eof,
     /// A Comment
     Comment(String),
+    /// A region folding marker comment: `// region: name` or `// endregion`
+    /// The bool is true for the start of a region and false for its end
+    Region(String, bool),
     /// Division
     /// Lexing done at the comment level due to clash
     Div,
@@ -33,6 +37,12 @@ pub enum TokenKind {
     Define,
     /// "#include" keyword
     Include,
+    /// "#ifdef" directive, holding the name it checks
+    IfDef(String),
+    /// "#else" directive
+    Else,
+    /// "#endif" directive
+    EndIf,
     /// "macro" keyword
     Macro,
     /// "fn" keyword
@@ -47,6 +57,10 @@ pub enum TokenKind {
     Constant,
     /// "error" keyword
     Error,
+    /// "receive" keyword
+    Receive,
+    /// "fallback" keyword
+    Fallback,
     /// "takes" keyword
     Takes,
     /// "returns" keyword
@@ -148,9 +162,15 @@ impl fmt::Display for TokenKind {
         let x = match self {
             TokenKind::Eof => "EOF",
             TokenKind::Comment(s) => return write!(f, "Comment({s})"),
+            TokenKind::Region(name, start) => {
+                return write!(f, "{}Region({name})", if *start { "" } else { "End" })
+            }
             TokenKind::Div => "/",
             TokenKind::Define => "#define",
             TokenKind::Include => "#include",
+            TokenKind::IfDef(name) => return write!(f, "#ifdef {name}"),
+            TokenKind::Else => "#else",
+            TokenKind::EndIf => "#endif",
             TokenKind::Macro => "macro",
             TokenKind::Fn => "fn",
             TokenKind::Test => "test",
@@ -158,6 +178,8 @@ impl fmt::Display for TokenKind {
             TokenKind::Event => "event",
             TokenKind::Constant => "constant",
             TokenKind::Error => "error",
+            TokenKind::Receive => "receive",
+            TokenKind::Fallback => "fallback",
             TokenKind::View => "view",
             TokenKind::Pure => "pure",
             TokenKind::Payable => "payable",
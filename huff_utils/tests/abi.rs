@@ -1,4 +1,4 @@
-use huff_utils::abi::*;
+use huff_utils::{abi::*, bytes_util::bytes32_to_string, error::CodegenErrorKind};
 use std::collections::HashMap;
 
 #[test]
@@ -77,3 +77,111 @@ fn test_display_func_param_type() {
         }
     }
 }
+
+#[test]
+fn test_abi_to_typescript() {
+    let mut abi = Abi::new();
+    abi.functions.insert(
+        "balanceOf".to_string(),
+        Function {
+            name: "balanceOf".to_string(),
+            inputs: vec![FunctionParam {
+                name: "owner".to_string(),
+                kind: FunctionParamType::Address,
+                internal_type: None,
+            }],
+            outputs: vec![FunctionParam {
+                name: "".to_string(),
+                kind: FunctionParamType::Uint(256),
+                internal_type: None,
+            }],
+            constant: false,
+            state_mutability: huff_utils::ast::FunctionType::View,
+        },
+    );
+
+    let ts = abi.to_typescript();
+    let array = ts
+        .strip_prefix("export const abi = ")
+        .and_then(|ts| ts.strip_suffix(" as const;\n"))
+        .unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(array).unwrap();
+    assert_eq!(
+        parsed,
+        serde_json::json!([{
+            "type": "function",
+            "name": "balanceOf",
+            "inputs": [{ "name": "owner", "type": "address" }],
+            "outputs": [{ "name": "", "type": "uint256" }],
+            "stateMutability": "view",
+        }])
+    );
+}
+
+#[test]
+fn test_normalize_signature() {
+    assert_eq!(
+        normalize_signature("transfer(address, uint )").unwrap(),
+        "transfer(address,uint256)"
+    );
+    assert_eq!(
+        normalize_signature(" Swap( (uint, int8)[2], byte[] ,bytes ) ").unwrap(),
+        "Swap((uint256,int8)[2],bytes1[],bytes)"
+    );
+    assert_eq!(normalize_signature("totalSupply()").unwrap(), "totalSupply()");
+
+    for sig in [
+        "transfer",
+        "(address)",
+        "transfer(address",
+        "transfer(uint,,bool)",
+        "f(foo)",
+        "f(uint[x])",
+    ] {
+        assert_eq!(
+            normalize_signature(sig).unwrap_err().kind,
+            CodegenErrorKind::InvalidSignature(sig.to_string())
+        );
+    }
+}
+
+#[test]
+fn test_keccak256_selector() {
+    for (sig, selector) in [
+        ("transfer(address,uint256)", "a9059cbb"),
+        ("transfer(address, uint)", "a9059cbb"),
+        ("balanceOf(address)", "70a08231"),
+        ("totalSupply()", "18160ddd"),
+        ("transferFrom( address , address , uint256 )", "23b872dd"),
+        ("multicall(bytes[])", "ac9650d8"),
+        (
+            "exactInputSingle((address,address,uint24,address,uint,uint256,uint,uint160))",
+            "414bf389",
+        ),
+    ] {
+        assert_eq!(
+            format!("{:08x}", u32::from_be_bytes(keccak256_selector(sig).unwrap())),
+            selector,
+            "{sig}"
+        );
+    }
+
+    // Aliases are expanded inside fixed size arrays and tuples too
+    assert_eq!(
+        keccak256_selector("f(uint[2],(int,byte)[])").unwrap(),
+        keccak256_selector("f(uint256[2],(int256,bytes1)[])").unwrap()
+    );
+
+    assert_eq!(
+        keccak256_selector("transfer(address").unwrap_err().kind,
+        CodegenErrorKind::InvalidSignature("transfer(address".to_string())
+    );
+}
+
+#[test]
+fn test_keccak256_hash() {
+    assert_eq!(
+        bytes32_to_string(&keccak256_hash("Transfer(address, address, uint)").unwrap(), false),
+        "ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"
+    );
+}
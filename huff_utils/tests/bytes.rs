@@ -18,3 +18,11 @@ fn test_hex_to_usize() {
         assert_eq!(converted_usize, i);
     }
 }
+
+#[test]
+fn test_format_literal_boundaries() {
+    assert_eq!(format_literal(String::new()), None);
+    assert_eq!(format_literal("1".to_string()), Some("6001".to_string()));
+    assert_eq!(format_literal("ff".repeat(32)), Some(format!("7f{}", "ff".repeat(32))));
+    assert_eq!(format_literal("ff".repeat(33)), None);
+}
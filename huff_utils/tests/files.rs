@@ -126,3 +126,34 @@ fn test_localize_file() {
             .unwrap();
     assert_eq!(localized, "./random_dir/Address.huff");
 }
+
+#[test]
+fn test_line_col() {
+    let source = "#define macro MAIN() = takes(0) returns(0) {\r\n    // é → ü\r\n    0x01\n}";
+    let file = files::FileSource {
+        path: "main.huff".to_string(),
+        source: Some(source.to_string()),
+        ..Default::default()
+    };
+
+    assert_eq!(file.line_col(0), Some((1, 1)));
+    // The `\r` and `\n` of a CRLF line ending are both the end of the first line
+    assert_eq!(file.line_col(44), Some((1, 45)));
+    assert_eq!(file.line_col(45), Some((1, 45)));
+    assert_eq!(file.line_col(46), Some((2, 1)));
+    // Multi-byte characters take up a single column
+    let literal = source.find("0x01").unwrap();
+    assert_eq!(file.line_col(source.find('ü').unwrap()), Some((2, 12)));
+    assert_eq!(file.line_col(source.find('ü').unwrap() + 1), Some((2, 12)));
+    assert_eq!(file.line_col(literal), Some((3, 5)));
+    assert_eq!(file.line_col(source.len() - 1), Some((4, 1)));
+    assert_eq!(file.line_col(source.len() + 10), Some((4, 2)));
+
+    let full_source = files::FullFileSource { source, file: None, spans: vec![] };
+    assert_eq!(full_source.line_col(literal), (3, 5));
+    assert_eq!(files::FileSource::default().line_col(0), None);
+
+    // Errors point at the line and column of the start of the span
+    let span = Span { start: literal, end: literal + 3, file: Some(Arc::new(file)) };
+    assert_eq!(span.identifier(), "\n-> main.huff:3:5");
+}
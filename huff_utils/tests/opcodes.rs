@@ -0,0 +1,133 @@
+use huff_utils::prelude::*;
+use std::str::FromStr;
+
+#[test]
+fn test_opcode_stack_effects() {
+    let effect = |o: Opcode| (o.stack_inputs(), o.stack_outputs());
+
+    assert_eq!(effect(Opcode::Add), (2, 1));
+    assert_eq!(effect(Opcode::Addmod), (3, 1));
+    assert_eq!(effect(Opcode::Iszero), (1, 1));
+    assert_eq!(effect(Opcode::Caller), (0, 1));
+    assert_eq!(effect(Opcode::Mstore), (2, 0));
+    assert_eq!(effect(Opcode::Jumpdest), (0, 0));
+    assert_eq!(effect(Opcode::Stop), (0, 0));
+    assert_eq!(effect(Opcode::Call), (7, 1));
+    assert_eq!(effect(Opcode::Staticcall), (6, 1));
+    assert_eq!(effect(Opcode::Create2), (4, 1));
+    assert_eq!(effect(Opcode::Mcopy), (3, 0));
+    assert_eq!(effect(Opcode::Tload), (1, 1));
+    assert_eq!(effect(Opcode::Selfdestruct), (1, 0));
+}
+
+#[test]
+fn test_opcode_family_stack_effects() {
+    assert_eq!((Opcode::Push0.stack_inputs(), Opcode::Push0.stack_outputs()), (0, 1));
+    assert_eq!((Opcode::Push1.stack_inputs(), Opcode::Push1.stack_outputs()), (0, 1));
+    assert_eq!((Opcode::Push32.stack_inputs(), Opcode::Push32.stack_outputs()), (0, 1));
+    assert_eq!((Opcode::Dup1.stack_inputs(), Opcode::Dup1.stack_outputs()), (1, 2));
+    assert_eq!((Opcode::Dup16.stack_inputs(), Opcode::Dup16.stack_outputs()), (16, 17));
+    assert_eq!((Opcode::Swap1.stack_inputs(), Opcode::Swap1.stack_outputs()), (2, 2));
+    assert_eq!((Opcode::Swap16.stack_inputs(), Opcode::Swap16.stack_outputs()), (17, 17));
+    assert_eq!((Opcode::Log0.stack_inputs(), Opcode::Log0.stack_outputs()), (2, 0));
+    assert_eq!((Opcode::Log2.stack_inputs(), Opcode::Log2.stack_outputs()), (4, 0));
+    assert_eq!((Opcode::Log4.stack_inputs(), Opcode::Log4.stack_outputs()), (6, 0));
+}
+
+#[test]
+fn test_opcode_min_gas() {
+    assert_eq!(Opcode::Stop.min_gas(), 0);
+    assert_eq!(Opcode::Jumpdest.min_gas(), 1);
+    assert_eq!(Opcode::Push0.min_gas(), 2);
+    assert_eq!(Opcode::Add.min_gas(), 3);
+    assert_eq!(Opcode::Push32.min_gas(), 3);
+    assert_eq!(Opcode::Dup16.min_gas(), 3);
+    assert_eq!(Opcode::Mul.min_gas(), 5);
+    assert_eq!(Opcode::Jump.min_gas(), 8);
+    assert_eq!(Opcode::Jumpi.min_gas(), 10);
+    assert_eq!(Opcode::Sha3.min_gas(), 30);
+    assert_eq!(Opcode::Sload.min_gas(), 100);
+    assert_eq!(Opcode::Log0.min_gas(), 375);
+    assert_eq!(Opcode::Log2.min_gas(), 1125);
+    assert_eq!(Opcode::Log4.min_gas(), 1875);
+    assert_eq!(Opcode::Create.min_gas(), 32000);
+}
+
+#[test]
+fn test_opcode_byte_round_trip() {
+    for opcode in OPCODES_MAP.values() {
+        // DIFFICULTY shares its byte with PREVRANDAO, which is what 0x44 decodes to
+        let expected = if *opcode == Opcode::Difficulty { Opcode::Prevrandao } else { *opcode };
+        assert_eq!(Opcode::from_u8(opcode.to_u8()), Some(expected));
+        assert_eq!(Opcode::try_from(opcode.to_u8()), Ok(expected));
+    }
+
+    assert_eq!(Opcode::from_u8(0x60), Some(Opcode::Push1));
+    assert_eq!(Opcode::from_u8(0x7f), Some(Opcode::Push32));
+    assert_eq!(Opcode::Push17.to_u8(), 0x70);
+}
+
+#[test]
+fn test_undefined_opcode_bytes() {
+    for byte in [0x0c, 0x21, 0x4b, 0xa5, 0xef, 0xfb] {
+        assert_eq!(Opcode::from_u8(byte), None);
+        assert_eq!(Opcode::try_from(byte), Err(byte));
+    }
+}
+
+#[test]
+fn test_push_helpers() {
+    assert!(Opcode::Push0.is_push());
+    assert!(Opcode::Push32.is_push());
+    assert!(!Opcode::Add.is_push());
+
+    assert_eq!(Opcode::Push0.push_size(), None);
+    assert_eq!(Opcode::Push1.push_size(), Some(1));
+    assert_eq!(Opcode::Push32.push_size(), Some(32));
+    assert_eq!(Opcode::Dup1.push_size(), None);
+
+    assert_eq!(Opcode::push_for_bytes(0), None);
+    assert_eq!(Opcode::push_for_bytes(1), Some(Opcode::Push1));
+    assert_eq!(Opcode::push_for_bytes(32), Some(Opcode::Push32));
+    assert_eq!(Opcode::push_for_bytes(33), None);
+}
+
+#[test]
+fn test_block_opcodes() {
+    for (mnemonic, opcode, byte, inputs) in [
+        ("basefee", Opcode::Basefee, 0x48, 0),
+        ("blobhash", Opcode::Blobhash, 0x49, 1),
+        ("blobbasefee", Opcode::Blobbasefee, 0x4a, 0),
+    ] {
+        assert_eq!(OPCODES_MAP.get(mnemonic), Some(&opcode));
+        assert_eq!(opcode.to_u8(), byte);
+        assert_eq!((opcode.stack_inputs(), opcode.stack_outputs()), (inputs, 1));
+    }
+
+    let shanghai = EVMVersion::new(SupportedEVMVersions::Shanghai);
+    let cancun = EVMVersion::new(SupportedEVMVersions::Cancun);
+    assert!(shanghai.supports_opcode(&Opcode::Basefee));
+    assert!(!shanghai.supports_opcode(&Opcode::Blobhash));
+    assert!(!shanghai.supports_opcode(&Opcode::Blobbasefee));
+    assert!(cancun.supports_opcode(&Opcode::Blobhash));
+    assert!(cancun.supports_opcode(&Opcode::Blobbasefee));
+}
+
+#[test]
+fn test_opcode_from_str_ignores_case() {
+    for mnemonic in ["MSTORE", "mstore", "MStore"] {
+        assert_eq!(Opcode::from_str(mnemonic), Ok(Opcode::Mstore));
+    }
+    assert!(Opcode::from_str("mstore9").is_err());
+}
+
+#[test]
+fn test_opcode_mnemonic() {
+    assert_eq!(Opcode::Mstore.to_mnemonic(), "MSTORE");
+    assert_eq!(Opcode::Mstore.to_string(), "52");
+
+    // Every mnemonic parses back to its opcode
+    for opcode in OPCODES_MAP.values() {
+        assert_eq!(Opcode::from_str(opcode.to_mnemonic()), Ok(*opcode));
+    }
+}